@@ -6,6 +6,12 @@ module sui_squad::account {
   const EONLY_AUTHORIZED_ACCOUNTS_CAN_EXECUTE_THIS_OPERATION: u64 = 1;
   const ENOT_FOUND_BALANCE: u64 = 2;
   const EMISMATCHED_TELEGRAM_ID: u64 = 3;
+  const EPROTOCOL_PAUSED: u64 = 4;
+  const EACCOUNT_FROZEN: u64 = 5;
+  const EONLY_OWNER_CAN_EXECUTE_THIS_OPERATION: u64 = 6;
+  const EOWNER_ALREADY_SET: u64 = 7;
+  const ESELF_CUSTODY_ACCOUNT: u64 = 8;
+  const ENO_PENDING_OWNER: u64 = 9;
 
   
   public struct Account has key, store {
@@ -19,10 +25,34 @@ module sui_squad::account {
     telegram_id: String,
   }
 
+  public struct FreezeEvent has copy, drop {
+    account_id: ID,
+    telegram_id: String,
+    frozen: bool,
+  }
+
+  public struct OwnerEvent has copy, drop {
+    account_id: ID,
+    telegram_id: String,
+    owner: address,
+    enabled: bool,
+  }
+
   public struct AccountBalance<phantom T> has copy, drop, store { }
 
+  /// Dynamic field key present while the account is frozen. Kept out of the struct so
+  /// accounts published before it remain valid after an upgrade.
+  public struct FrozenKey has copy, drop, store { }
+
+  /// Dynamic field key of the zkLogin address allowed to withdraw without the relayer.
+  public struct OwnerKey has copy, drop, store { }
+
+  /// Dynamic field key of the zkLogin address a relayer proposed as owner, until it accepts.
+  public struct PendingOwnerKey has copy, drop, store { }
+
   public entry fun create_new_account(admin: &Admin, telegram_id: String, ctx: &mut TxContext): ID {
-    assert!(admin.get_address() == ctx.sender(), EONLY_AUTHORIZED_ACCOUNTS_CAN_EXECUTE_THIS_OPERATION);
+    assert!(admin.is_authorized(ctx.sender()), EONLY_AUTHORIZED_ACCOUNTS_CAN_EXECUTE_THIS_OPERATION);
+    assert!(!admin.is_paused(), EPROTOCOL_PAUSED);
 
     let id = object::new(ctx);
     let account_id = object::uid_to_inner(&id);
@@ -57,7 +87,10 @@ module sui_squad::account {
   }
 
   public entry fun withdraw<T>(self: &mut Account, admin: &Admin, amount: u64, recipient: address, ctx: &mut TxContext) {
-    assert!(admin.get_address() == ctx.sender(), EONLY_AUTHORIZED_ACCOUNTS_CAN_EXECUTE_THIS_OPERATION);
+    assert!(admin.is_authorized(ctx.sender()), EONLY_AUTHORIZED_ACCOUNTS_CAN_EXECUTE_THIS_OPERATION);
+    assert!(!admin.is_paused(), EPROTOCOL_PAUSED);
+    assert!(!self.is_frozen(), EACCOUNT_FROZEN);
+    assert!(!df::exists_(&self.id, OwnerKey { }), ESELF_CUSTODY_ACCOUNT);
 
     let account_balance_type = AccountBalance<T> { };
     assert!(df::exists_(&self.id, account_balance_type), ENOT_FOUND_BALANCE);
@@ -66,7 +99,10 @@ module sui_squad::account {
   }
 
   public fun payment<T>(self: &mut Account, admin: &Admin, recipient: &mut Account, amount: u64, ctx: &mut TxContext) {
-    assert!(admin.get_address() == ctx.sender(), EONLY_AUTHORIZED_ACCOUNTS_CAN_EXECUTE_THIS_OPERATION);
+    assert!(admin.is_authorized(ctx.sender()), EONLY_AUTHORIZED_ACCOUNTS_CAN_EXECUTE_THIS_OPERATION);
+    assert!(!admin.is_paused(), EPROTOCOL_PAUSED);
+    assert!(!self.is_frozen(), EACCOUNT_FROZEN);
+    assert!(!df::exists_(&self.id, OwnerKey { }), ESELF_CUSTODY_ACCOUNT);
 
     let account_balance_type = AccountBalance<T> { };
 
@@ -84,6 +120,106 @@ module sui_squad::account {
     };
   }
 
+  /// First step of opting into self-custody: a relayer proposes the zkLogin address of the
+  /// account's Telegram user as owner. A new proposal replaces the previous one.
+  public entry fun propose_self_custody(self: &mut Account, admin: &Admin, owner: address, ctx: &mut TxContext) {
+    assert!(admin.is_authorized(ctx.sender()), EONLY_AUTHORIZED_ACCOUNTS_CAN_EXECUTE_THIS_OPERATION);
+    assert!(!admin.is_paused(), EPROTOCOL_PAUSED);
+    assert!(!df::exists_(&self.id, OwnerKey { }), EOWNER_ALREADY_SET);
+
+    if (df::exists_(&self.id, PendingOwnerKey { })) {
+      *df::borrow_mut<PendingOwnerKey, address>(&mut self.id, PendingOwnerKey { }) = owner;
+    } else {
+      df::add(&mut self.id, PendingOwnerKey { }, owner);
+    };
+  }
+
+  /// Second step: the proposed address accepts with its own signature. From then on only the
+  /// owner can move the funds, and only the owner can opt out again.
+  public entry fun enable_self_custody(self: &mut Account, admin: &Admin, ctx: &mut TxContext) {
+    assert!(!admin.is_paused(), EPROTOCOL_PAUSED);
+    assert!(!df::exists_(&self.id, OwnerKey { }), EOWNER_ALREADY_SET);
+    assert!(df::exists_(&self.id, PendingOwnerKey { }), ENO_PENDING_OWNER);
+
+    let owner: address = df::remove(&mut self.id, PendingOwnerKey { });
+
+    assert!(owner == ctx.sender(), EONLY_OWNER_CAN_EXECUTE_THIS_OPERATION);
+
+    df::add(&mut self.id, OwnerKey { }, owner);
+
+    event::emit(OwnerEvent {
+      account_id: self.account_id,
+      telegram_id: self.telegram_id,
+      owner,
+      enabled: true,
+    });
+  }
+
+  public entry fun disable_self_custody(self: &mut Account, ctx: &mut TxContext) {
+    assert!(self.is_owner(ctx.sender()), EONLY_OWNER_CAN_EXECUTE_THIS_OPERATION);
+
+    let owner: address = df::remove(&mut self.id, OwnerKey { });
+
+    event::emit(OwnerEvent {
+      account_id: self.account_id,
+      telegram_id: self.telegram_id,
+      owner,
+      enabled: false,
+    });
+  }
+
+  /// Withdrawal signed by the account owner's zkLogin address instead of a relayer.
+  public entry fun owner_withdraw<T>(self: &mut Account, admin: &Admin, amount: u64, recipient: address, ctx: &mut TxContext) {
+    assert!(self.is_owner(ctx.sender()), EONLY_OWNER_CAN_EXECUTE_THIS_OPERATION);
+    assert!(!admin.is_paused(), EPROTOCOL_PAUSED);
+    assert!(!self.is_frozen(), EACCOUNT_FROZEN);
+
+    let account_balance_type = AccountBalance<T> { };
+    assert!(df::exists_(&self.id, account_balance_type), ENOT_FOUND_BALANCE);
+    let balance: &mut Coin<T> = df::borrow_mut(&mut self.id, account_balance_type);
+    balance.split_and_transfer(amount, recipient, ctx);
+  }
+
+  public fun owner(self: &Account): Option<address> {
+    if (df::exists_(&self.id, OwnerKey { })) {
+      option::some(*df::borrow(&self.id, OwnerKey { }))
+    } else {
+      option::none()
+    }
+  }
+
+  fun is_owner(self: &Account, sender: address): bool {
+    df::exists_(&self.id, OwnerKey { }) && *df::borrow<OwnerKey, address>(&self.id, OwnerKey { }) == sender
+  }
+
+  public entry fun freeze_account(self: &mut Account, admin: &Admin, ctx: &mut TxContext) {
+    self.set_frozen(admin, true, ctx);
+  }
+
+  public entry fun unfreeze_account(self: &mut Account, admin: &Admin, ctx: &mut TxContext) {
+    self.set_frozen(admin, false, ctx);
+  }
+
+  fun set_frozen(self: &mut Account, admin: &Admin, frozen: bool, ctx: &mut TxContext) {
+    assert!(admin.get_address() == ctx.sender(), EONLY_AUTHORIZED_ACCOUNTS_CAN_EXECUTE_THIS_OPERATION);
+
+    if (frozen && !df::exists_(&self.id, FrozenKey { })) {
+      df::add(&mut self.id, FrozenKey { }, true);
+    } else if (!frozen && df::exists_(&self.id, FrozenKey { })) {
+      df::remove<FrozenKey, bool>(&mut self.id, FrozenKey { });
+    };
+
+    event::emit(FreezeEvent {
+      account_id: self.account_id,
+      telegram_id: self.telegram_id,
+      frozen,
+    });
+  }
+
+  public fun is_frozen(self: &Account): bool {
+    df::exists_(&self.id, FrozenKey { })
+  }
+
   public fun get_balance<T>(self: &Account): u64 {
     let account_balance_type = AccountBalance<T> { };
     assert!(df::exists_(&self.id, account_balance_type), ENOT_FOUND_BALANCE);
@@ -1,6 +1,14 @@
 module sui_squad::admin {
   use sui::package;
   use sui::event;
+  use sui::dynamic_field as df;
+  use sui::vec_set::{Self, VecSet};
+
+  const EONLY_ADMIN_CAN_EXECUTE_THIS_OPERATION: u64 = 1;
+  const ENO_PENDING_ADMIN: u64 = 2;
+  const EONLY_PENDING_ADMIN_CAN_ACCEPT: u64 = 3;
+  const ERELAYER_ALREADY_AUTHORIZED: u64 = 4;
+  const ERELAYER_NOT_AUTHORIZED: u64 = 5;
 
   public struct AdminCap has key {
     id: UID,
@@ -11,11 +19,39 @@ module sui_squad::admin {
     account: address,
   }
 
+  /// Dynamic field key present while the contract is under an emergency pause. Kept out of
+  /// the struct so `Admin` objects published before it remain valid after an upgrade.
+  public struct PausedKey has copy, drop, store { }
+
+  /// Dynamic field key holding the address nominated by `propose_admin` until it accepts.
+  public struct PendingAdminKey has copy, drop, store { }
+
+  /// Dynamic field key holding the `VecSet<address>` of authorized relayers.
+  public struct RelayersKey has copy, drop, store { }
+
   public struct AdminEvent has copy, drop{
     admin_id: ID,
     wallet: address,
   }
 
+  public struct PauseEvent has copy, drop {
+    admin_id: ID,
+    paused: bool,
+  }
+
+  public struct AdminRotationEvent has copy, drop {
+    admin_id: ID,
+    current: address,
+    proposed: address,
+    accepted: bool,
+  }
+
+  public struct RelayerEvent has copy, drop {
+    admin_id: ID,
+    relayer: address,
+    authorized: bool,
+  }
+
   public struct ADMIN has drop {}
 
   fun init(otw: ADMIN, ctx: &mut TxContext) {
@@ -27,7 +63,10 @@ module sui_squad::admin {
   }
 
   public fun initialize_admin(admin_cap: AdminCap, ctx: &mut TxContext) {
-    let admin = Admin { id: object::new(ctx), account: ctx.sender() };
+    let admin = Admin {
+      id: object::new(ctx),
+      account: ctx.sender(),
+    };
 
     let admin_id = object::uid_to_inner(&admin.id);
 
@@ -39,6 +78,125 @@ module sui_squad::admin {
     transfer::share_object(admin);
   }
 
+  public entry fun pause(self: &mut Admin, ctx: &mut TxContext) {
+    self.set_paused(true, ctx);
+  }
+
+  public entry fun unpause(self: &mut Admin, ctx: &mut TxContext) {
+    self.set_paused(false, ctx);
+  }
+
+  fun set_paused(self: &mut Admin, paused: bool, ctx: &mut TxContext) {
+    assert!(self.account == ctx.sender(), EONLY_ADMIN_CAN_EXECUTE_THIS_OPERATION);
+
+    if (paused && !df::exists_(&self.id, PausedKey { })) {
+      df::add(&mut self.id, PausedKey { }, true);
+    } else if (!paused && df::exists_(&self.id, PausedKey { })) {
+      df::remove<PausedKey, bool>(&mut self.id, PausedKey { });
+    };
+
+    event::emit(PauseEvent { admin_id: object::uid_to_inner(&self.id), paused });
+  }
+
+  public fun is_paused(self: &Admin): bool {
+    df::exists_(&self.id, PausedKey { })
+  }
+
+  /// First step of a key rotation: the current admin nominates the address that will replace it.
+  public entry fun propose_admin(self: &mut Admin, new_account: address, ctx: &mut TxContext) {
+    assert!(self.account == ctx.sender(), EONLY_ADMIN_CAN_EXECUTE_THIS_OPERATION);
+
+    if (df::exists_(&self.id, PendingAdminKey { })) {
+      *df::borrow_mut<PendingAdminKey, address>(&mut self.id, PendingAdminKey { }) = new_account;
+    } else {
+      df::add(&mut self.id, PendingAdminKey { }, new_account);
+    };
+
+    event::emit(AdminRotationEvent {
+      admin_id: object::uid_to_inner(&self.id),
+      current: self.account,
+      proposed: new_account,
+      accepted: false,
+    });
+  }
+
+  public entry fun cancel_admin_rotation(self: &mut Admin, ctx: &mut TxContext) {
+    assert!(self.account == ctx.sender(), EONLY_ADMIN_CAN_EXECUTE_THIS_OPERATION);
+
+    if (df::exists_(&self.id, PendingAdminKey { })) {
+      df::remove<PendingAdminKey, address>(&mut self.id, PendingAdminKey { });
+    };
+  }
+
+  /// Second step of a key rotation: the nominated address takes over the admin role.
+  public entry fun accept_admin(self: &mut Admin, ctx: &mut TxContext) {
+    assert!(df::exists_(&self.id, PendingAdminKey { }), ENO_PENDING_ADMIN);
+
+    let new_account: address = df::remove(&mut self.id, PendingAdminKey { });
+
+    assert!(new_account == ctx.sender(), EONLY_PENDING_ADMIN_CAN_ACCEPT);
+
+    let admin_id = object::uid_to_inner(&self.id);
+    let previous = self.account;
+
+    self.account = new_account;
+
+    event::emit(AdminRotationEvent { admin_id, current: previous, proposed: new_account, accepted: true });
+
+    event::emit(AdminEvent { admin_id, wallet: new_account });
+  }
+
+  public fun pending_admin(self: &Admin): Option<address> {
+    if (df::exists_(&self.id, PendingAdminKey { })) {
+      option::some(*df::borrow<PendingAdminKey, address>(&self.id, PendingAdminKey { }))
+    } else {
+      option::none()
+    }
+  }
+
+  /// Authorize an extra key to relay payments, withdrawals and account creation.
+  public entry fun add_relayer(self: &mut Admin, relayer: address, ctx: &mut TxContext) {
+    assert!(self.account == ctx.sender(), EONLY_ADMIN_CAN_EXECUTE_THIS_OPERATION);
+    assert!(!self.is_relayer(relayer), ERELAYER_ALREADY_AUTHORIZED);
+
+    if (!df::exists_(&self.id, RelayersKey { })) {
+      df::add(&mut self.id, RelayersKey { }, vec_set::empty<address>());
+    };
+
+    let relayers: &mut VecSet<address> = df::borrow_mut(&mut self.id, RelayersKey { });
+    relayers.insert(relayer);
+
+    event::emit(RelayerEvent { admin_id: object::uid_to_inner(&self.id), relayer, authorized: true });
+  }
+
+  public entry fun remove_relayer(self: &mut Admin, relayer: address, ctx: &mut TxContext) {
+    assert!(self.account == ctx.sender(), EONLY_ADMIN_CAN_EXECUTE_THIS_OPERATION);
+    assert!(self.is_relayer(relayer), ERELAYER_NOT_AUTHORIZED);
+
+    let relayers: &mut VecSet<address> = df::borrow_mut(&mut self.id, RelayersKey { });
+    relayers.remove(&relayer);
+
+    event::emit(RelayerEvent { admin_id: object::uid_to_inner(&self.id), relayer, authorized: false });
+  }
+
+  public fun relayers(self: &Admin): vector<address> {
+    if (df::exists_(&self.id, RelayersKey { })) {
+      *df::borrow<RelayersKey, VecSet<address>>(&self.id, RelayersKey { }).keys()
+    } else {
+      vector[]
+    }
+  }
+
+  fun is_relayer(self: &Admin, relayer: address): bool {
+    df::exists_(&self.id, RelayersKey { })
+      && df::borrow<RelayersKey, VecSet<address>>(&self.id, RelayersKey { }).contains(&relayer)
+  }
+
+  /// Whether `sender` may relay transactions: the admin itself or any authorized relayer.
+  public fun is_authorized(self: &Admin, sender: address): bool {
+    self.account == sender || self.is_relayer(sender)
+  }
+
   public(package) fun borrow_mut(self: &mut Admin): &mut UID {
     &mut self.id
   }
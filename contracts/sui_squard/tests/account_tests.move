@@ -21,6 +21,15 @@ module sui_squad::account_tests {
     coin::mint_for_testing<SUI>(amount, ts.ctx())
   }
 
+  /// The relayer proposes `owner`, which accepts in the next transaction.
+  fun enable_custody(ts: &mut Scenario, account: &mut Account, admin: &Admin, owner: address) {
+    account.propose_self_custody(admin, owner, ts.ctx());
+
+    ts.next_tx(owner);
+
+    account.enable_self_custody(admin, ts.ctx());
+  }
+
     #[test]
     fun test_create_account() {
         let mut ts = ts::begin(ADMIN);
@@ -287,4 +296,555 @@ module sui_squad::account_tests {
 
         abort 2
     }
+
+    #[test]
+    fun test_pay_account_after_unpause() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut admin_obj = ts.take_shared<Admin>();
+
+        let account_id = account::create_new_account(&admin_obj, string::utf8(b"test_tg"), ts.ctx());
+
+        let recipient_account_id = account::create_new_account(&admin_obj, string::utf8(b"test_2"), ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut account_obj = ts.take_shared_by_id<Account>(account_id);
+
+        let mut recipient_account_obj = ts.take_shared_by_id<Account>(recipient_account_id);
+
+        account_obj.fund(string::utf8(b"test_tg"), test_coin(&mut ts, 100));
+
+        admin_obj.pause(ts.ctx());
+
+        assert!(admin_obj.is_paused(), EVALUES_DOES_NOT_MATCH);
+
+        admin_obj.unpause(ts.ctx());
+
+        account_obj.payment<SUI>(&admin_obj, &mut recipient_account_obj, 50, ts.ctx());
+
+        assert!(recipient_account_obj.get_balance<SUI>() == 50, EVALUES_DOES_NOT_MATCH);
+
+        ts::return_shared(recipient_account_obj);
+
+        ts::return_shared(account_obj);
+
+        ts::return_shared(admin_obj);
+
+        ts::end(ts);
+    }
+
+    #[test, expected_failure(abort_code = sui_squad::account::EPROTOCOL_PAUSED)]
+    fun test_pay_account_when_paused() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut admin_obj = ts.take_shared<Admin>();
+
+        let account_id = account::create_new_account(&admin_obj, string::utf8(b"test_tg"), ts.ctx());
+
+        let recipient_account_id = account::create_new_account(&admin_obj, string::utf8(b"test_2"), ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut account_obj = ts.take_shared_by_id<Account>(account_id);
+
+        let mut recipient_account_obj = ts.take_shared_by_id<Account>(recipient_account_id);
+
+        account_obj.fund(string::utf8(b"test_tg"), test_coin(&mut ts, 100));
+
+        admin_obj.pause(ts.ctx());
+
+        account_obj.payment<SUI>(&admin_obj, &mut recipient_account_obj, 50, ts.ctx());
+
+        abort 1
+    }
+
+    #[test, expected_failure(abort_code = sui_squad::account::EPROTOCOL_PAUSED)]
+    fun test_withdraw_funds_when_paused() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut admin_obj = ts.take_shared<Admin>();
+
+        let account_id = account::create_new_account(&admin_obj, string::utf8(b"test_tg"), ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut account_obj = ts.take_shared_by_id<Account>(account_id);
+
+        account_obj.fund(string::utf8(b"test_tg"), test_coin(&mut ts, 100));
+
+        admin_obj.pause(ts.ctx());
+
+        account_obj.withdraw<SUI>(&admin_obj, 50, USER, ts.ctx());
+
+        abort 1
+    }
+
+    #[test, expected_failure(abort_code = sui_squad::account::EPROTOCOL_PAUSED)]
+    fun test_create_account_when_paused() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut admin_obj = ts.take_shared<Admin>();
+
+        admin_obj.pause(ts.ctx());
+
+        account::create_new_account(&admin_obj, string::utf8(b"test_tg"), ts.ctx());
+
+        abort 1
+    }
+
+    #[test, expected_failure(abort_code = sui_squad::account::EACCOUNT_FROZEN)]
+    fun test_withdraw_funds_from_frozen_account() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let admin_obj = ts.take_shared<Admin>();
+
+        let account_id = account::create_new_account(&admin_obj, string::utf8(b"test_tg"), ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut account_obj = ts.take_shared_by_id<Account>(account_id);
+
+        account_obj.fund(string::utf8(b"test_tg"), test_coin(&mut ts, 100));
+
+        account_obj.freeze_account(&admin_obj, ts.ctx());
+
+        account_obj.withdraw<SUI>(&admin_obj, 50, USER, ts.ctx());
+
+        abort 1
+    }
+
+    #[test, expected_failure(abort_code = sui_squad::account::EACCOUNT_FROZEN)]
+    fun test_pay_from_frozen_account() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let admin_obj = ts.take_shared<Admin>();
+
+        let account_id = account::create_new_account(&admin_obj, string::utf8(b"test_tg"), ts.ctx());
+
+        let recipient_account_id = account::create_new_account(&admin_obj, string::utf8(b"test_2"), ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut account_obj = ts.take_shared_by_id<Account>(account_id);
+
+        let mut recipient_account_obj = ts.take_shared_by_id<Account>(recipient_account_id);
+
+        account_obj.fund(string::utf8(b"test_tg"), test_coin(&mut ts, 100));
+
+        account_obj.freeze_account(&admin_obj, ts.ctx());
+
+        account_obj.payment<SUI>(&admin_obj, &mut recipient_account_obj, 50, ts.ctx());
+
+        abort 1
+    }
+
+    #[test]
+    fun test_withdraw_funds_after_unfreeze() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let admin_obj = ts.take_shared<Admin>();
+
+        let account_id = account::create_new_account(&admin_obj, string::utf8(b"test_tg"), ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut account_obj = ts.take_shared_by_id<Account>(account_id);
+
+        account_obj.fund(string::utf8(b"test_tg"), test_coin(&mut ts, 100));
+
+        account_obj.freeze_account(&admin_obj, ts.ctx());
+
+        assert!(account_obj.is_frozen(), EVALUES_DOES_NOT_MATCH);
+
+        account_obj.unfreeze_account(&admin_obj, ts.ctx());
+
+        account_obj.withdraw<SUI>(&admin_obj, 50, USER, ts.ctx());
+
+        assert!(account_obj.get_balance<SUI>() == 50, EVALUES_DOES_NOT_MATCH);
+
+        ts::return_shared(account_obj);
+
+        ts::return_shared(admin_obj);
+
+        ts::end(ts);
+    }
+
+    #[test, expected_failure(abort_code = sui_squad::account::EONLY_AUTHORIZED_ACCOUNTS_CAN_EXECUTE_THIS_OPERATION)]
+    fun test_freeze_account_with_invalid_admin() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let admin_obj = ts.take_shared<Admin>();
+
+        let account_id = account::create_new_account(&admin_obj, string::utf8(b"test_tg"), ts.ctx());
+
+        ts.next_tx(USER);
+
+        let mut account_obj = ts.take_shared_by_id<Account>(account_id);
+
+        account_obj.freeze_account(&admin_obj, ts.ctx());
+
+        abort 1
+    }
+
+    #[test]
+    fun test_relayer_creates_account_and_pays() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut admin_obj = ts.take_shared<Admin>();
+
+        admin_obj.add_relayer(RECIPIENT, ts.ctx());
+
+        ts.next_tx(RECIPIENT);
+
+        let account_id = account::create_new_account(&admin_obj, string::utf8(b"test_tg"), ts.ctx());
+
+        let recipient_account_id = account::create_new_account(&admin_obj, string::utf8(b"test_2"), ts.ctx());
+
+        ts.next_tx(RECIPIENT);
+
+        let mut account_obj = ts.take_shared_by_id<Account>(account_id);
+
+        let mut recipient_account_obj = ts.take_shared_by_id<Account>(recipient_account_id);
+
+        account_obj.fund(string::utf8(b"test_tg"), test_coin(&mut ts, 100));
+
+        account_obj.payment<SUI>(&admin_obj, &mut recipient_account_obj, 40, ts.ctx());
+
+        account_obj.withdraw<SUI>(&admin_obj, 10, USER, ts.ctx());
+
+        assert!(account_obj.get_balance<SUI>() == 50, EVALUES_DOES_NOT_MATCH);
+
+        assert!(recipient_account_obj.get_balance<SUI>() == 40, EVALUES_DOES_NOT_MATCH);
+
+        ts::return_shared(recipient_account_obj);
+
+        ts::return_shared(account_obj);
+
+        ts::return_shared(admin_obj);
+
+        ts::end(ts);
+    }
+
+    #[test, expected_failure(abort_code = sui_squad::account::EONLY_AUTHORIZED_ACCOUNTS_CAN_EXECUTE_THIS_OPERATION)]
+    fun test_removed_relayer_cannot_withdraw() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut admin_obj = ts.take_shared<Admin>();
+
+        let account_id = account::create_new_account(&admin_obj, string::utf8(b"test_tg"), ts.ctx());
+
+        admin_obj.add_relayer(RECIPIENT, ts.ctx());
+
+        admin_obj.remove_relayer(RECIPIENT, ts.ctx());
+
+        ts.next_tx(RECIPIENT);
+
+        let mut account_obj = ts.take_shared_by_id<Account>(account_id);
+
+        account_obj.fund(string::utf8(b"test_tg"), test_coin(&mut ts, 100));
+
+        account_obj.withdraw<SUI>(&admin_obj, 50, USER, ts.ctx());
+
+        abort 1
+    }
+
+    #[test]
+    fun test_owner_withdraws_funds() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let admin_obj = ts.take_shared<Admin>();
+
+        let account_id = account::create_new_account(&admin_obj, string::utf8(b"test_tg"), ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut account_obj = ts.take_shared_by_id<Account>(account_id);
+
+        account_obj.fund(string::utf8(b"test_tg"), test_coin(&mut ts, 1000));
+
+        enable_custody(&mut ts, &mut account_obj, &admin_obj, USER);
+
+        assert!(account_obj.owner() == option::some(USER), EVALUES_DOES_NOT_MATCH);
+
+        ts.next_tx(USER);
+
+        account_obj.owner_withdraw<SUI>(&admin_obj, 400, RECIPIENT, ts.ctx());
+
+        assert!(account_obj.get_balance<SUI>() == 600, EVALUES_DOES_NOT_MATCH);
+
+        ts.next_tx(RECIPIENT);
+
+        let coin = ts.take_from_sender<Coin<SUI>>();
+
+        assert!(coin.value() == 400, EVALUES_DOES_NOT_MATCH);
+
+        ts.return_to_sender(coin);
+
+        ts::return_shared(account_obj);
+
+        ts::return_shared(admin_obj);
+
+        ts::end(ts);
+    }
+
+    #[test, expected_failure(abort_code = sui_squad::account::EONLY_OWNER_CAN_EXECUTE_THIS_OPERATION)]
+    fun test_non_owner_cannot_owner_withdraw() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let admin_obj = ts.take_shared<Admin>();
+
+        let account_id = account::create_new_account(&admin_obj, string::utf8(b"test_tg"), ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut account_obj = ts.take_shared_by_id<Account>(account_id);
+
+        account_obj.fund(string::utf8(b"test_tg"), test_coin(&mut ts, 1000));
+
+        enable_custody(&mut ts, &mut account_obj, &admin_obj, USER);
+
+        ts.next_tx(ADMIN);
+
+        account_obj.owner_withdraw<SUI>(&admin_obj, 400, ADMIN, ts.ctx());
+
+        abort 1
+    }
+
+    #[test, expected_failure(abort_code = sui_squad::account::EOWNER_ALREADY_SET)]
+    fun test_relayer_cannot_replace_owner() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let admin_obj = ts.take_shared<Admin>();
+
+        let account_id = account::create_new_account(&admin_obj, string::utf8(b"test_tg"), ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut account_obj = ts.take_shared_by_id<Account>(account_id);
+
+        enable_custody(&mut ts, &mut account_obj, &admin_obj, USER);
+
+        ts.next_tx(ADMIN);
+
+        account_obj.propose_self_custody(&admin_obj, ADMIN, ts.ctx());
+
+        abort 1
+    }
+
+    #[test, expected_failure(abort_code = sui_squad::account::EONLY_OWNER_CAN_EXECUTE_THIS_OPERATION)]
+    fun test_owner_withdraw_after_disable() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let admin_obj = ts.take_shared<Admin>();
+
+        let account_id = account::create_new_account(&admin_obj, string::utf8(b"test_tg"), ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut account_obj = ts.take_shared_by_id<Account>(account_id);
+
+        account_obj.fund(string::utf8(b"test_tg"), test_coin(&mut ts, 1000));
+
+        enable_custody(&mut ts, &mut account_obj, &admin_obj, USER);
+
+        ts.next_tx(USER);
+
+        account_obj.disable_self_custody(ts.ctx());
+
+        assert!(account_obj.owner().is_none(), EVALUES_DOES_NOT_MATCH);
+
+        account_obj.owner_withdraw<SUI>(&admin_obj, 400, USER, ts.ctx());
+
+        abort 1
+    }
+
+    #[test, expected_failure(abort_code = sui_squad::account::EACCOUNT_FROZEN)]
+    fun test_owner_withdraw_from_frozen_account() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let admin_obj = ts.take_shared<Admin>();
+
+        let account_id = account::create_new_account(&admin_obj, string::utf8(b"test_tg"), ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut account_obj = ts.take_shared_by_id<Account>(account_id);
+
+        account_obj.fund(string::utf8(b"test_tg"), test_coin(&mut ts, 1000));
+
+        enable_custody(&mut ts, &mut account_obj, &admin_obj, USER);
+
+        ts.next_tx(ADMIN);
+
+        account_obj.freeze_account(&admin_obj, ts.ctx());
+
+        ts.next_tx(USER);
+
+        account_obj.owner_withdraw<SUI>(&admin_obj, 400, USER, ts.ctx());
+
+        abort 1
+    }
+
+    #[test, expected_failure(abort_code = sui_squad::account::ESELF_CUSTODY_ACCOUNT)]
+    fun test_relayer_cannot_withdraw_from_self_custody_account() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let admin_obj = ts.take_shared<Admin>();
+
+        let account_id = account::create_new_account(&admin_obj, string::utf8(b"test_tg"), ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut account_obj = ts.take_shared_by_id<Account>(account_id);
+
+        account_obj.fund(string::utf8(b"test_tg"), test_coin(&mut ts, 1000));
+
+        enable_custody(&mut ts, &mut account_obj, &admin_obj, USER);
+
+        ts.next_tx(ADMIN);
+
+        account_obj.withdraw<SUI>(&admin_obj, 400, ADMIN, ts.ctx());
+
+        abort 1
+    }
+
+    #[test, expected_failure(abort_code = sui_squad::account::ESELF_CUSTODY_ACCOUNT)]
+    fun test_relayer_cannot_pay_from_self_custody_account() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let admin_obj = ts.take_shared<Admin>();
+
+        let account_id = account::create_new_account(&admin_obj, string::utf8(b"test_tg"), ts.ctx());
+
+        let recipient_account_id = account::create_new_account(&admin_obj, string::utf8(b"test_2"), ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut account_obj = ts.take_shared_by_id<Account>(account_id);
+
+        let mut recipient_account_obj = ts.take_shared_by_id<Account>(recipient_account_id);
+
+        account_obj.fund(string::utf8(b"test_tg"), test_coin(&mut ts, 1000));
+
+        enable_custody(&mut ts, &mut account_obj, &admin_obj, USER);
+
+        ts.next_tx(ADMIN);
+
+        account_obj.payment<SUI>(&admin_obj, &mut recipient_account_obj, 400, ts.ctx());
+
+        abort 1
+    }
+
+    #[test, expected_failure(abort_code = sui_squad::account::EONLY_OWNER_CAN_EXECUTE_THIS_OPERATION)]
+    fun test_relayer_cannot_accept_self_custody() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let admin_obj = ts.take_shared<Admin>();
+
+        let account_id = account::create_new_account(&admin_obj, string::utf8(b"test_tg"), ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut account_obj = ts.take_shared_by_id<Account>(account_id);
+
+        account_obj.propose_self_custody(&admin_obj, USER, ts.ctx());
+
+        account_obj.enable_self_custody(&admin_obj, ts.ctx());
+
+        abort 1
+    }
+
+    #[test, expected_failure(abort_code = sui_squad::account::ENO_PENDING_OWNER)]
+    fun test_enable_self_custody_without_proposal() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let admin_obj = ts.take_shared<Admin>();
+
+        let account_id = account::create_new_account(&admin_obj, string::utf8(b"test_tg"), ts.ctx());
+
+        ts.next_tx(USER);
+
+        let mut account_obj = ts.take_shared_by_id<Account>(account_id);
+
+        account_obj.enable_self_custody(&admin_obj, ts.ctx());
+
+        abort 1
+    }
 }
@@ -1,10 +1,13 @@
 
 #[test_only]
 module sui_squad::admin_tests {
-    use sui_squad::admin;
+    use sui_squad::admin::{Self, Admin};
     use sui::test_scenario::{Self as ts};
 
+    const EVALUES_DOES_NOT_MATCH: u64 = 1;
+
     const ADMIN: address = @0x100;
+    const USER: address = @0x200;
 
     #[test]
     fun test_create_admin() {
@@ -16,4 +19,272 @@ module sui_squad::admin_tests {
 
         ts::end(ts);
     }
+
+    #[test]
+    fun test_pause_and_unpause() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut admin_obj = ts.take_shared<Admin>();
+
+        assert!(!admin_obj.is_paused(), EVALUES_DOES_NOT_MATCH);
+
+        admin_obj.pause(ts.ctx());
+
+        assert!(admin_obj.is_paused(), EVALUES_DOES_NOT_MATCH);
+
+        admin_obj.unpause(ts.ctx());
+
+        assert!(!admin_obj.is_paused(), EVALUES_DOES_NOT_MATCH);
+
+        ts::return_shared(admin_obj);
+
+        ts::end(ts);
+    }
+
+    #[test, expected_failure(abort_code = sui_squad::admin::EONLY_ADMIN_CAN_EXECUTE_THIS_OPERATION)]
+    fun test_pause_with_invalid_admin() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(USER);
+
+        let mut admin_obj = ts.take_shared<Admin>();
+
+        admin_obj.pause(ts.ctx());
+
+        abort 1
+    }
+
+    #[test]
+    fun test_rotate_admin() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut admin_obj = ts.take_shared<Admin>();
+
+        admin_obj.propose_admin(USER, ts.ctx());
+
+        assert!(admin_obj.pending_admin() == option::some(USER), EVALUES_DOES_NOT_MATCH);
+
+        ts::return_shared(admin_obj);
+
+        ts.next_tx(USER);
+
+        let mut admin_obj = ts.take_shared<Admin>();
+
+        admin_obj.accept_admin(ts.ctx());
+
+        assert!(admin_obj.get_address() == USER, EVALUES_DOES_NOT_MATCH);
+
+        assert!(admin_obj.pending_admin().is_none(), EVALUES_DOES_NOT_MATCH);
+
+        admin_obj.pause(ts.ctx());
+
+        assert!(admin_obj.is_paused(), EVALUES_DOES_NOT_MATCH);
+
+        ts::return_shared(admin_obj);
+
+        ts::end(ts);
+    }
+
+    #[test]
+    fun test_propose_admin_again_replaces_pending() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut admin_obj = ts.take_shared<Admin>();
+
+        admin_obj.propose_admin(@0x300, ts.ctx());
+
+        admin_obj.propose_admin(USER, ts.ctx());
+
+        assert!(admin_obj.pending_admin() == option::some(USER), EVALUES_DOES_NOT_MATCH);
+
+        ts::return_shared(admin_obj);
+
+        ts.next_tx(USER);
+
+        let mut admin_obj = ts.take_shared<Admin>();
+
+        admin_obj.accept_admin(ts.ctx());
+
+        assert!(admin_obj.get_address() == USER, EVALUES_DOES_NOT_MATCH);
+
+        ts::return_shared(admin_obj);
+
+        ts::end(ts);
+    }
+
+    #[test, expected_failure(abort_code = sui_squad::admin::EONLY_ADMIN_CAN_EXECUTE_THIS_OPERATION)]
+    fun test_previous_admin_loses_access_after_rotation() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut admin_obj = ts.take_shared<Admin>();
+
+        admin_obj.propose_admin(USER, ts.ctx());
+
+        ts::return_shared(admin_obj);
+
+        ts.next_tx(USER);
+
+        let mut admin_obj = ts.take_shared<Admin>();
+
+        admin_obj.accept_admin(ts.ctx());
+
+        ts::return_shared(admin_obj);
+
+        ts.next_tx(ADMIN);
+
+        let mut admin_obj = ts.take_shared<Admin>();
+
+        admin_obj.pause(ts.ctx());
+
+        abort 1
+    }
+
+    #[test, expected_failure(abort_code = sui_squad::admin::EONLY_ADMIN_CAN_EXECUTE_THIS_OPERATION)]
+    fun test_propose_admin_with_invalid_admin() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(USER);
+
+        let mut admin_obj = ts.take_shared<Admin>();
+
+        admin_obj.propose_admin(USER, ts.ctx());
+
+        abort 1
+    }
+
+    #[test, expected_failure(abort_code = sui_squad::admin::EONLY_PENDING_ADMIN_CAN_ACCEPT)]
+    fun test_accept_admin_with_wrong_account() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut admin_obj = ts.take_shared<Admin>();
+
+        admin_obj.propose_admin(USER, ts.ctx());
+
+        admin_obj.accept_admin(ts.ctx());
+
+        abort 1
+    }
+
+    #[test, expected_failure(abort_code = sui_squad::admin::ENO_PENDING_ADMIN)]
+    fun test_accept_admin_after_cancel() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut admin_obj = ts.take_shared<Admin>();
+
+        admin_obj.propose_admin(USER, ts.ctx());
+
+        admin_obj.cancel_admin_rotation(ts.ctx());
+
+        ts::return_shared(admin_obj);
+
+        ts.next_tx(USER);
+
+        let mut admin_obj = ts.take_shared<Admin>();
+
+        admin_obj.accept_admin(ts.ctx());
+
+        abort 1
+    }
+
+    #[test]
+    fun test_add_and_remove_relayer() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut admin_obj = ts.take_shared<Admin>();
+
+        admin_obj.add_relayer(USER, ts.ctx());
+
+        assert!(admin_obj.is_authorized(USER), EVALUES_DOES_NOT_MATCH);
+
+        assert!(admin_obj.relayers() == vector[USER], EVALUES_DOES_NOT_MATCH);
+
+        admin_obj.remove_relayer(USER, ts.ctx());
+
+        assert!(!admin_obj.is_authorized(USER), EVALUES_DOES_NOT_MATCH);
+
+        assert!(admin_obj.is_authorized(ADMIN), EVALUES_DOES_NOT_MATCH);
+
+        ts::return_shared(admin_obj);
+
+        ts::end(ts);
+    }
+
+    #[test, expected_failure(abort_code = sui_squad::admin::EONLY_ADMIN_CAN_EXECUTE_THIS_OPERATION)]
+    fun test_add_relayer_with_invalid_admin() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(USER);
+
+        let mut admin_obj = ts.take_shared<Admin>();
+
+        admin_obj.add_relayer(USER, ts.ctx());
+
+        abort 1
+    }
+
+    #[test, expected_failure(abort_code = sui_squad::admin::ERELAYER_ALREADY_AUTHORIZED)]
+    fun test_add_relayer_twice() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut admin_obj = ts.take_shared<Admin>();
+
+        admin_obj.add_relayer(USER, ts.ctx());
+
+        admin_obj.add_relayer(USER, ts.ctx());
+
+        abort 1
+    }
+
+    #[test, expected_failure(abort_code = sui_squad::admin::ERELAYER_NOT_AUTHORIZED)]
+    fun test_remove_unknown_relayer() {
+        let mut ts = ts::begin(ADMIN);
+
+        admin::init_test( ts.ctx());
+
+        ts.next_tx(ADMIN);
+
+        let mut admin_obj = ts.take_shared<Admin>();
+
+        admin_obj.remove_relayer(USER, ts.ctx());
+
+        abort 1
+    }
 }
@@ -0,0 +1,9 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Per-user mapping of labels (e.g. `cold`) to Sui addresses.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AddressBook {
+    pub entries: BTreeMap<String, String>,
+}
@@ -0,0 +1,96 @@
+use anyhow::{Result, anyhow};
+use sled::Db;
+use sui_squad_core::{helpers::address::normalize_sui_address, suins::is_sui_name};
+
+use super::dto::AddressBook;
+
+const ADDRESS_BOOK_TREE: &str = "address_book";
+const MAX_LABEL_LENGTH: usize = 32;
+
+pub fn get_address_book(user_id: &str, db: Db) -> Result<AddressBook> {
+    let tree = db.open_tree(ADDRESS_BOOK_TREE)?;
+
+    match tree.get(user_id)? {
+        Some(bytes) => Ok(serde_json::from_slice(&bytes)?),
+        None => Ok(AddressBook::default()),
+    }
+}
+
+pub fn save_address_book(user_id: &str, address_book: &AddressBook, db: Db) -> Result<()> {
+    let tree = db.open_tree(ADDRESS_BOOK_TREE)?;
+    let bytes = serde_json::to_vec(address_book)?;
+    tree.insert(user_id, bytes)?;
+
+    Ok(())
+}
+
+/// Labels are case-insensitive and limited to letters, digits, `-` and `_`.
+pub fn normalize_label(label: &str) -> Result<String> {
+    let label = label.trim().to_lowercase();
+
+    if label.is_empty()
+        || label.len() > MAX_LABEL_LENGTH
+        || !label
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(anyhow!(
+            "Invalid label '{}'. Use up to {} letters, digits, '-' or '_'",
+            label,
+            MAX_LABEL_LENGTH
+        ));
+    }
+
+    Ok(label)
+}
+
+pub fn add_address(user_id: &str, label: &str, address: &str, db: Db) -> Result<String> {
+    let label = normalize_label(label)?;
+    let address = normalize_sui_address(address).ok_or_else(|| {
+        anyhow!(
+            "Invalid Sui address '{}'. Expected 0x followed by 64 hex characters",
+            address
+        )
+    })?;
+
+    let mut address_book = get_address_book(user_id, db.clone())?;
+    address_book.entries.insert(label.clone(), address);
+    save_address_book(user_id, &address_book, db)?;
+
+    Ok(label)
+}
+
+pub fn remove_address(user_id: &str, label: &str, db: Db) -> Result<Option<String>> {
+    let label = normalize_label(label)?;
+
+    let mut address_book = get_address_book(user_id, db.clone())?;
+    let removed = address_book.entries.remove(&label);
+    save_address_book(user_id, &address_book, db)?;
+
+    Ok(removed)
+}
+
+/// Resolve a withdrawal destination that is a raw Sui address, a SuiNS name or an address book label.
+///
+/// SuiNS names are passed through untouched; the server resolves them when building the withdrawal.
+pub fn resolve_address(user_id: &str, destination: &str, db: Db) -> Result<String> {
+    if let Some(address) = normalize_sui_address(destination) {
+        return Ok(address);
+    }
+
+    if is_sui_name(destination) {
+        return Ok(destination.trim().to_lowercase());
+    }
+
+    let label = normalize_label(destination.trim_start_matches('@'))
+        .map_err(|_| anyhow!("'{}' is not a valid Sui address or label", destination))?;
+
+    let address_book = get_address_book(user_id, db)?;
+
+    address_book.entries.get(&label).cloned().ok_or_else(|| {
+        anyhow!(
+            "'{}' is not a valid Sui address or .sui name and is not in your address book",
+            destination
+        )
+    })
+}
@@ -0,0 +1,357 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use sled::Db;
+use sui_squad_core::{
+    commands::admin::{AdminAction, AdminCommand, QuotaOverride, QuotaScope, admin_help},
+    helpers::dtos::SpendingLimits,
+};
+use teloxide::{
+    Bot,
+    prelude::*,
+    types::{Message, ParseMode},
+};
+
+use crate::{
+    credentials::{dto::Credentials, helpers::get_credentials},
+    services::services::Services,
+    usage::{
+        dto::UsageRecord,
+        helpers::{default_quota, set_quota_override, usage_summary},
+    },
+};
+
+/// Heaviest users and chats listed in the stats.
+const TOP_USAGE: usize = 5;
+
+fn format_sui(amount: u64) -> String {
+    format!("{} SUI", amount as f64 / 1_000_000_000f64)
+}
+
+async fn handle_stats(services: &Services, token: String) -> String {
+    match services.admin_stats(token).await {
+        Ok(stats) => {
+            let relayers = stats
+                .relayers
+                .iter()
+                .map(|relayer| {
+                    format!(
+                        "\n• <code>{}</code> {} {}",
+                        relayer.address,
+                        format_sui(relayer.gas_balance),
+                        if relayer.authorized {
+                            "✅"
+                        } else {
+                            "⚠️ not authorized"
+                        }
+                    )
+                })
+                .collect::<String>();
+
+            format!(
+                "📈 <b>Sui Squad stats</b>\n\n\
+                 Admin: <code>{}</code>\n\
+                 Admin gas: {}\n\
+                 Accounts: {}\n\
+                 Paused: {}\n\
+                 Emergency pause: {}\n\
+                 Spent last 24h: {}\n\
+                 Spent last 7 days: {}\n\
+                 Relayers: {}",
+                stats.admin_address,
+                format_sui(stats.admin_gas_balance),
+                stats.accounts,
+                if stats.paused {
+                    "yes ⏸️"
+                } else {
+                    "no ▶️"
+                },
+                if stats.chain_paused { "yes 🚨" } else { "no" },
+                format_sui(stats.spent_daily),
+                format_sui(stats.spent_weekly),
+                if relayers.is_empty() {
+                    "none".to_string()
+                } else {
+                    relayers
+                },
+            )
+        }
+        Err(e) => format!("❌ Failed to get stats: {}", e),
+    }
+}
+
+fn format_limits(limits: &SpendingLimits) -> String {
+    let format_limit =
+        |limit: Option<u64>| limit.map(format_sui).unwrap_or_else(|| "none".to_string());
+
+    format!(
+        "daily {}, weekly {}, per transaction {}",
+        format_limit(limits.daily),
+        format_limit(limits.weekly),
+        format_limit(limits.per_transaction)
+    )
+}
+
+fn format_top_usage(usage: &[(String, UsageRecord)]) -> String {
+    if usage.is_empty() {
+        return " none".to_string();
+    }
+
+    usage
+        .iter()
+        .map(|(id, record)| {
+            format!(
+                "\n• <code>{}</code> {} tokens, {} prompts",
+                id, record.tokens, record.requests
+            )
+        })
+        .collect()
+}
+
+fn ai_usage_stats(db: Db) -> String {
+    match usage_summary(TOP_USAGE, db) {
+        Ok(summary) => format!(
+            "🤖 <b>AI usage</b>\n\n\
+             Today: {} tokens, {} prompts\n\
+             Last 7 days: {} tokens\n\
+             Top users today:{}\n\
+             Top chats today:{}",
+            summary.today.tokens,
+            summary.today.requests,
+            summary.week_tokens,
+            format_top_usage(&summary.top_users),
+            format_top_usage(&summary.top_chats),
+        ),
+        Err(e) => format!("❌ Failed to get AI usage: {}", e),
+    }
+}
+
+fn handle_quota(scope: QuotaScope, id: i64, quota: QuotaOverride, db: Db) -> String {
+    let whose = match scope {
+        QuotaScope::User => format!("user {}", id),
+        QuotaScope::Chat => format!("chat {}", id),
+    };
+
+    if let Err(e) = set_quota_override(scope, id, quota, db) {
+        return format!("❌ Failed to update the quota: {}", e);
+    }
+
+    let limit = match quota {
+        QuotaOverride::Tokens(tokens) => Some(tokens),
+        QuotaOverride::Unlimited => None,
+        QuotaOverride::Default => default_quota(scope),
+    };
+
+    match limit {
+        Some(tokens) => format!("✅ Daily AI quota of {} set to {} tokens", whose, tokens),
+        None => format!("✅ Daily AI quota of {} set to unlimited", whose),
+    }
+}
+
+async fn handle_user(services: &Services, token: String, telegram_id: String) -> String {
+    match services.admin_user(token, telegram_id).await {
+        Ok(info) => {
+            let account = info
+                .account_id
+                .map(|id| format!("<code>{}</code>", id))
+                .unwrap_or_else(|| "none".to_string());
+
+            let balance = info
+                .balance
+                .map(format_sui)
+                .unwrap_or_else(|| "no balance".to_string());
+
+            let limits = info
+                .limits
+                .map(|limits| {
+                    format!(
+                        "Spent 24h: {}\nSpent 7 days: {}\nPersonal limits: {}\nEffective limits: {}",
+                        format_sui(limits.spent_daily),
+                        format_sui(limits.spent_weekly),
+                        format_limits(&limits.user_limits),
+                        format_limits(&limits.effective)
+                    )
+                })
+                .unwrap_or_default();
+
+            let frozen = match info.frozen {
+                Some(true) => "yes 🧊",
+                Some(false) => "no",
+                None => "-",
+            };
+
+            format!(
+                "👤 <b>User {}</b>\n\nAccount: {}\nBalance: {}\nFrozen: {}\n{}",
+                info.telegram_id, account, balance, frozen, limits
+            )
+        }
+        Err(e) => format!("❌ Failed to get user: {}", e),
+    }
+}
+
+async fn handle_pause(services: &Services, token: String, paused: bool) -> String {
+    match services.admin_pause(token, paused).await {
+        Ok(response) if response.paused => {
+            "⏸️ Paused. The server will refuse to sign new transactions.".to_string()
+        }
+        Ok(_) => "▶️ Resumed. Transactions are being signed again.".to_string(),
+        Err(e) => format!("❌ Failed to update pause state: {}", e),
+    }
+}
+
+async fn handle_chain_pause(services: &Services, token: String, paused: bool) -> String {
+    match services.admin_chain_pause(token, paused).await {
+        Ok(response) if response.paused => format!(
+            "🚨 Emergency pause active. The contract rejects payments, withdrawals and new accounts.\n\nTx: <code>{}</code>",
+            response.digest
+        ),
+        Ok(response) => format!(
+            "✅ Emergency pause lifted.\n\nTx: <code>{}</code>",
+            response.digest
+        ),
+        Err(e) => format!("❌ Failed to update emergency pause: {}", e),
+    }
+}
+
+async fn handle_freeze(
+    services: &Services,
+    token: String,
+    telegram_id: String,
+    frozen: bool,
+) -> String {
+    match services.admin_freeze(token, telegram_id, frozen).await {
+        Ok(response) => format!(
+            "{} Account of user {} {}.\n\nTx: <code>{}</code>",
+            if response.frozen { "🧊" } else { "✅" },
+            response.telegram_id,
+            if response.frozen {
+                "frozen"
+            } else {
+                "unfrozen"
+            },
+            response.digest
+        ),
+        Err(e) => format!("❌ Failed to update account freeze: {}", e),
+    }
+}
+
+async fn handle_relayer(
+    services: &Services,
+    token: String,
+    address: String,
+    authorized: bool,
+) -> String {
+    match services.admin_relayer(token, address, authorized).await {
+        Ok(response) => format!(
+            "{} Relayer <code>{}</code> {}.\n\nTx: <code>{}</code>",
+            if response.authorized { "✅" } else { "🚫" },
+            response.address,
+            if response.authorized {
+                "authorized"
+            } else {
+                "revoked"
+            },
+            response.digest
+        ),
+        Err(e) => format!("❌ Failed to update relayer: {}", e),
+    }
+}
+
+/// Send `text` to every user with stored credentials, returning (delivered, failed).
+async fn broadcast(bot: &Bot, db: &Db, text: &str) -> (usize, usize) {
+    let mut delivered = 0;
+    let mut failed = 0;
+
+    for entry in db.iter() {
+        let Ok((_, bytes)) = entry else {
+            failed += 1;
+            continue;
+        };
+
+        let Ok(credentials) = serde_json::from_slice::<Credentials>(&bytes) else {
+            continue;
+        };
+
+        match bot.send_message(credentials.user_id, text).await {
+            Ok(_) => delivered += 1,
+            Err(e) => {
+                println!("❌ Broadcast to {} failed: {}", credentials.user_id, e);
+                failed += 1;
+            }
+        }
+
+        // Stay well below Telegram's limit of 30 messages per second
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    (delivered, failed)
+}
+
+pub async fn answer_admin(
+    bot: Bot,
+    msg: Message,
+    cmd: AdminCommand,
+    services: Services,
+    db: Db,
+) -> Result<()> {
+    let AdminCommand::Admin(args) = cmd;
+
+    let action = match args.parse::<AdminAction>() {
+        Ok(action) => action,
+        Err(e) => {
+            bot.send_message(msg.chat.id, format!("❌ {}\n\n{}", e, admin_help()))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let username = msg.from.clone().and_then(|user| user.username);
+
+    let token = username
+        .and_then(|username| get_credentials(&username, db.clone()))
+        .map(|credentials| credentials.jwt);
+
+    let Some(token) = token else {
+        bot.send_message(msg.chat.id, "❌ Please /login first")
+            .await?;
+        return Ok(());
+    };
+
+    let text = match action {
+        AdminAction::Help => admin_help(),
+        AdminAction::Stats => format!(
+            "{}\n\n{}",
+            handle_stats(&services, token).await,
+            ai_usage_stats(db.clone())
+        ),
+        AdminAction::User(telegram_id) => handle_user(&services, token, telegram_id).await,
+        AdminAction::Pause => handle_pause(&services, token, true).await,
+        AdminAction::Resume => handle_pause(&services, token, false).await,
+        AdminAction::ChainPause => handle_chain_pause(&services, token, true).await,
+        AdminAction::ChainResume => handle_chain_pause(&services, token, false).await,
+        AdminAction::Freeze(telegram_id) => {
+            handle_freeze(&services, token, telegram_id, true).await
+        }
+        AdminAction::Unfreeze(telegram_id) => {
+            handle_freeze(&services, token, telegram_id, false).await
+        }
+        AdminAction::AddRelayer(address) => handle_relayer(&services, token, address, true).await,
+        AdminAction::RemoveRelayer(address) => {
+            handle_relayer(&services, token, address, false).await
+        }
+        AdminAction::Quota { scope, id, quota } => handle_quota(scope, id, quota, db.clone()),
+        AdminAction::Broadcast(text) => {
+            let (delivered, failed) = broadcast(&bot, &db, &text).await;
+            format!(
+                "📣 Broadcast delivered to {} users ({} failed)",
+                delivered, failed
+            )
+        }
+    };
+
+    bot.send_message(msg.chat.id, text)
+        .parse_mode(ParseMode::Html)
+        .await?;
+
+    Ok(())
+}
@@ -1,14 +1,22 @@
+use std::sync::Arc;
+
 use anyhow::Result;
 use sled::Db;
 use squad_connect::client::squad_connect::SquadConnect;
 use sui_squad_core::{
-    ai::ResponsesClient, 
-    commands::bot_commands::Command,
+    ai::LlmBackend, 
+    commands::{bot_commands::Command, wizard::WizardCommand},
     conversation::ConversationCache
 };
 use teloxide::{prelude::*, types::Message, utils::command::BotCommands, Bot};
 
-use crate::bot_manage::handlers::{handle_fund, handle_login};
+use crate::{
+    bot_manage::handlers::{
+        handle_address_book, handle_custody, handle_fund, handle_limits, handle_login,
+        handle_memory, handle_quota, handle_reset, handle_wallet,
+    },
+    services::services::Services,
+};
 
 use super::handlers::{handle_prompt};
 
@@ -16,20 +24,34 @@ pub async fn answer(
     bot: Bot,
     msg: Message,
     cmd: Command,
-    responses_client: ResponsesClient,
+    llm: Arc<dyn LlmBackend>,
     squad_connect_client: SquadConnect,
     conversation_cache: ConversationCache,
+    services: Services,
     db: Db,
 ) -> Result<()> {
     match cmd {
-        Command::Help => bot.send_message(msg.chat.id, Command::descriptions().to_string()).await?,
+        Command::Help => {
+            bot.send_message(
+                msg.chat.id,
+                format!("{}\n\n{}", Command::descriptions(), WizardCommand::descriptions()),
+            )
+            .await?
+        }
         Command::Login => handle_login(bot, msg, db).await?,
-        Command::Fund => handle_fund(bot, msg, squad_connect_client).await?,
+        Command::Fund => handle_fund(bot, msg, squad_connect_client, services).await?,
+        Command::Wallet => handle_wallet(bot, msg, squad_connect_client, services).await?,
+        Command::Limits(args) => handle_limits(bot, msg, args, services, db).await?,
+        Command::AddressBook(args) => handle_address_book(bot, msg, args, db).await?,
+        Command::Custody => handle_custody(bot, msg, squad_connect_client, services).await?,
+        Command::Reset => handle_reset(bot, msg, conversation_cache).await?,
+        Command::Memory(args) => handle_memory(bot, msg, args, conversation_cache).await?,
+        Command::Quota => handle_quota(bot, msg, db).await?,
         Command::Prompt(prompt_text) => handle_prompt(
             bot, 
             msg, 
             prompt_text, 
-            responses_client, 
+            llm, 
             squad_connect_client,
             conversation_cache,
             db,
@@ -38,7 +60,7 @@ pub async fn answer(
             bot, 
             msg, 
             prompt_text, 
-            responses_client, 
+            llm, 
             squad_connect_client,
             conversation_cache,
             db
@@ -1,12 +1,4 @@
-use serde::{Deserialize, Serialize};
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct State {
-    pub telegram_id: String,
-    pub max_epoch: u64,
-    pub public_key: String,
-    pub randomness: String,
-}
+use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
 pub struct BalanceObject {
@@ -58,16 +50,3 @@ pub struct CoinFields {
     pub balance: String,
     pub id: IdField,
 }
-
-impl From<(String, u64, String, String)> for State {
-    fn from(state: (String, u64, String, String)) -> Self {
-        let (telegram_id, max_epoch, public_key, randomness) = state;
-
-        Self {
-            telegram_id,
-            max_epoch,
-            public_key,
-            randomness,
-        }
-    }
-}
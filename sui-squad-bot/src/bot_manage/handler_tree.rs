@@ -1,16 +1,26 @@
 use anyhow::Result;
-use sui_squad_core::commands::bot_commands::{Command, LoginState};
+use sui_squad_core::commands::{
+    admin::AdminCommand,
+    bot_commands::Command,
+    wizard::{DialogueState, WizardCommand},
+};
 use teloxide::{
     Bot,
-    dispatching::{DpHandlerDescription, HandlerExt, UpdateFilterExt, dialogue::InMemStorage},
+    dispatching::{DpHandlerDescription, HandlerExt, UpdateFilterExt},
     dptree::{self, Handler},
     prelude::{DependencyMap, Requester},
-    types::{Message, Update},
+    types::{CallbackQuery, Message, Update},
 };
 
-use crate::middleware::{auth::auth, user::check_user};
+use crate::{
+    dialogue::{
+        DialogueStorage,
+        handlers::{answer_wizard, handle_wizard_callback, handle_wizard_message},
+    },
+    middleware::{admin::is_operator, auth::auth, user::check_user},
+};
 
-use super::answer::answer;
+use super::{admin::answer_admin, answer::answer};
 
 async fn handle_unauthenticated(bot: Bot, msg: Message) -> Result<()> {
     bot.send_message(
@@ -26,10 +36,26 @@ Please use `/login` to authenticate, or `/fund` to set up your account with Goog
 }
 
 pub fn handler_tree() -> Handler<'static, DependencyMap, Result<()>, DpHandlerDescription> {
-    Update::filter_message()
-        .enter_dialogue::<Message, InMemStorage<LoginState>, LoginState>()
+    let messages = Update::filter_message()
+        .enter_dialogue::<Message, DialogueStorage, DialogueState>()
+        .branch(
+            // 0. Branch for operators listed in ADMIN_TELEGRAM_IDS
+            dptree::entry()
+                .filter_command::<AdminCommand>()
+                .filter(is_operator)
+                .filter_async(auth)
+                .endpoint(answer_admin),
+        )
+        .branch(
+            // 1. Branch for guided flows of authenticated users
+            dptree::entry()
+                .filter_command::<WizardCommand>()
+                .filter_async(auth)
+                .filter_async(check_user)
+                .endpoint(answer_wizard),
+        )
         .branch(
-            // 1. Branch for authenticated users
+            // 2. Branch for authenticated users
             dptree::entry()
                 .filter_async(auth)
                 .filter_async(check_user)
@@ -37,7 +63,7 @@ pub fn handler_tree() -> Handler<'static, DependencyMap, Result<()>, DpHandlerDe
                 .endpoint(answer),
         )
         .branch(
-            // 2. Branch for public commands for new users
+            // 3. Branch for public commands for new users
             dptree::entry()
                 .filter_command::<Command>()
                 .filter(|cmd: Command| {
@@ -46,9 +72,21 @@ pub fn handler_tree() -> Handler<'static, DependencyMap, Result<()>, DpHandlerDe
                 .endpoint(answer),
         )
         .branch(
-            // 3. Fallback for unauthenticated users trying protected commands
+            // 4. Plain text answering the current step of a guided flow
+            dptree::case![DialogueState::Wizard(wizard)]
+                .filter(|msg: Message| msg.text().is_some_and(|text| !text.starts_with('/')))
+                .endpoint(handle_wizard_message),
+        )
+        .branch(
+            // 5. Fallback for unauthenticated users trying protected commands
             dptree::entry()
                 .filter_command::<Command>()
                 .endpoint(handle_unauthenticated),
-        )
+        );
+
+    let callbacks = Update::filter_callback_query()
+        .enter_dialogue::<CallbackQuery, DialogueStorage, DialogueState>()
+        .endpoint(handle_wizard_callback);
+
+    dptree::entry().branch(messages).branch(callbacks)
 }
@@ -1,37 +1,104 @@
 use crate::{
-    bot_manage::dto::BalanceObject,
+    address_book::helpers::{add_address, get_address_book, remove_address, resolve_address},
+    bot_manage::{dto::BalanceObject, stream::StreamingReply},
     credentials::{
         dto::Credentials,
         helpers::{get_credentials, save_credentials},
     },
     services::services::Services,
-    tools::schema::get_schema,
+    tools::{ToolContext, ToolRegistry},
+    usage::{
+        dto::QuotaStatus,
+        helpers::{exceeded_quota, quota_status, record_usage, reported_or_estimated},
+    },
+    wallet::helpers::wallet_info,
 };
 use anyhow::Result as AnyhowResult;
 
 use reqwest::Url;
 use sled::Db;
 use squad_connect::client::squad_connect::SquadConnect;
-use std::{env, path::PathBuf};
+use std::{env, path::PathBuf, sync::Arc, time::Duration};
 use sui_sdk::{rpc_types::EventFilter, types::base_types::ObjectID};
 use sui_squad_core::{
-    ai::ResponsesClient,
+    ai::{LlmBackend, LlmResponse, TokenUsage},
+    commands::admin::QuotaScope,
     conversation::ConversationCache,
-    helpers::dtos::{PaymentRequest, WithdrawRequest},
-    package::dto::Event,
+    error::CoreError,
+    helpers::dtos::{
+        LimitsRequest, LimitsResponse, LoginPurpose, PaymentRequest, SessionRequest,
+        SpendingLimits, WithdrawRequest,
+    },
+    package::{dto::Event, helpers::is_sui_balance_field},
 };
 use teloxide::{
     Bot,
     prelude::*,
     types::{InlineKeyboardButton, InlineKeyboardMarkup, Message, ParseMode},
+    utils::html::escape,
 };
 
-use super::dto::State;
+/// Register a login session on the server and build the single-use link to the funding page,
+/// optionally prefilled with a deposit to move, or to the self-custody page.
+///
+/// A new ephemeral key is only generated when the user has no zkLogin session the server can reuse.
+pub async fn login_url(
+    squad_connect_client: &mut SquadConnect,
+    services: &Services,
+    user_id: String,
+    language: Option<String>,
+    purpose: LoginPurpose,
+    deposit: Option<(u64, String)>,
+) -> AnyhowResult<Url> {
+    // Reuse the ephemeral key and its cached proof until the session nears max_epoch
+    let (randomness, public_key, max_epoch) =
+        match services.zk_login_session(user_id.clone()).await? {
+            Some(session) => (session.randomness, session.public_key, session.max_epoch),
+            None => {
+                let path_str = env::var("KEYSTORE_PATH").expect("PATH env variable is not set");
+
+                let mut path = PathBuf::new();
+                path.push(path_str);
+
+                // Ensure the zkp directory exists
+                std::fs::create_dir_all(&path).unwrap_or_else(|_| {
+                    // Directory might already exist, that's fine
+                });
+
+                squad_connect_client.create_zkp_payload(path).await?;
+
+                squad_connect_client.get_zk_proof_params()
+            }
+        };
+
+    let (amount, coin_type) = deposit.unzip();
+
+    let session = services
+        .create_session(SessionRequest {
+            telegram_id: user_id,
+            public_key,
+            max_epoch,
+            randomness,
+            amount,
+            coin_type,
+            language,
+            purpose,
+        })
+        .await?;
+
+    let host = env::var("HOST").expect("HOST env variable is not set");
+
+    Ok(Url::parse(&format!(
+        "https://{host}/webhook/{}",
+        session.token
+    ))?)
+}
 
 pub async fn handle_fund(
     bot: Bot,
     msg: Message,
     squad_connect_client: SquadConnect,
+    services: Services,
 ) -> AnyhowResult<Message> {
     let current_chat = msg.chat.clone();
     let message: Message;
@@ -39,32 +106,17 @@ pub async fn handle_fund(
     let mut squad_connect_client = squad_connect_client.clone();
 
     if !current_chat.is_group() && !current_chat.is_supergroup() && current_chat.is_private() {
-        let user_id = msg.from.unwrap().id.to_string();
-
-        let path_str = env::var("KEYSTORE_PATH").expect("PATH env variable is not set");
-
-        let mut path = PathBuf::new();
-        path.push(path_str);
-
-        // Ensure the zkp directory exists
-        std::fs::create_dir_all(&path).unwrap_or_else(|_| {
-            // Directory might already exist, that's fine
-        });
-
-        squad_connect_client.create_zkp_payload(path).await?;
-
-        let (randomness, public_key, max_epoch) = squad_connect_client.get_zk_proof_params();
-
-        let state = State::from((user_id.to_string(), max_epoch, public_key, randomness));
-
-        let host = env::var("HOST").expect("HOST env variable is not set");
-        let redirect_url = format!("https://{host}/webhook/token");
-
-        let url_to_build = squad_connect_client
-            .get_url::<State>(redirect_url, Some(state))
-            .await?;
-
-        let url = Url::parse(&url_to_build).unwrap();
+        let user = msg.from.unwrap();
+
+        let url = login_url(
+            &mut squad_connect_client,
+            &services,
+            user.id.to_string(),
+            user.language_code,
+            LoginPurpose::Fund,
+            None,
+        )
+        .await?;
 
         let fund_button = vec![vec![InlineKeyboardButton::new(
             "Fund your account",
@@ -92,11 +144,79 @@ pub async fn handle_fund(
     Ok(message)
 }
 
+pub async fn handle_custody(
+    bot: Bot,
+    msg: Message,
+    squad_connect_client: SquadConnect,
+    services: Services,
+) -> AnyhowResult<Message> {
+    let current_chat = msg.chat.clone();
+
+    if !current_chat.is_private() {
+        return Ok(bot
+            .send_message(
+                current_chat.id,
+                "You only can manage self-custody in private Bot chat",
+            )
+            .await?);
+    }
+
+    let user = msg.from.ok_or_else(|| anyhow::anyhow!("User not found"))?;
+
+    let mut squad_connect_client = squad_connect_client.clone();
+
+    let url = login_url(
+        &mut squad_connect_client,
+        &services,
+        user.id.to_string(),
+        user.language_code,
+        LoginPurpose::Custody,
+        None,
+    )
+    .await?;
+
+    let markup = InlineKeyboardMarkup::new(vec![vec![InlineKeyboardButton::url(
+        "Open self-custody",
+        url,
+    )]]);
+
+    Ok(bot
+        .send_message(
+            current_chat.id,
+            "🔐 <b>Self-custody</b>\n\nRegister your Google zkLogin address as the owner of your Sui Squad account. Once enabled, you can withdraw by signing in with Google, even without the bot's relayer.\n\nIf it is already enabled, the same link lets you withdraw.",
+        )
+        .parse_mode(ParseMode::Html)
+        .reply_markup(markup)
+        .await?)
+}
+
+pub async fn handle_wallet(
+    bot: Bot,
+    msg: Message,
+    squad_connect_client: SquadConnect,
+    services: Services,
+) -> AnyhowResult<Message> {
+    let user = msg
+        .from
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("User not found"))?;
+
+    let text = match wallet_info(user.id, &services, &squad_connect_client).await {
+        Ok(info) => info,
+        Err(e) => format!("❌ Failed to load your wallet: {}", e),
+    };
+
+    Ok(bot
+        .send_message(msg.chat.id, text)
+        .parse_mode(ParseMode::Html)
+        .await?)
+}
+
 pub async fn handle_prompt(
     bot: Bot,
     msg: Message,
     prompt_text: String,
-    responses_client: ResponsesClient,
+    llm: Arc<dyn LlmBackend>,
     squad_connect_client: SquadConnect,
     conversation_cache: ConversationCache,
     db: Db,
@@ -119,6 +239,29 @@ pub async fn handle_prompt(
 
     let username = user.username;
 
+    // Group chats share a quota on top of each member's own
+    let quota_user_id = user_id.0 as i64;
+    let quota_chat_id = (!msg.chat.is_private()).then_some(msg.chat.id.0);
+
+    if let Some((scope, status)) = exceeded_quota(quota_user_id, quota_chat_id, db.clone())? {
+        let whose = match scope {
+            QuotaScope::User => "your",
+            QuotaScope::Chat => "this group's",
+        };
+
+        return Ok(bot
+            .send_message(
+                msg.chat.id,
+                format!(
+                    "⛔ You reached {} daily AI quota ({} of {} tokens). It resets at 00:00 UTC.\n\nCommands like /wallet, /send and /withdraw still work.",
+                    whose,
+                    status.used,
+                    status.limit.unwrap_or_default()
+                ),
+            )
+            .await?);
+    }
+
     // Get cached conversation ID
     let previous_response_id = conversation_cache.get(&user_key).await;
 
@@ -129,94 +272,244 @@ pub async fn handle_prompt(
         println!("🆕 Starting new conversation");
     }
 
-    // Define custom function/tool schemas for the model
-    let schema = get_schema();
+    // Tools offered to the model and what they need to run
+    let registry = ToolRegistry::wallet();
+    let schema = registry.definitions();
 
-    // Call AI with function-calling enabled AND conversation continuity
-    let mut current_response = responses_client
-        .generate_response(
-            Some(&prompt_text),
-            Some(schema.clone()),
-            previous_response_id,
-            None,
-        )
-        .await?;
+    let ctx = ToolContext {
+        user_id,
+        username,
+        services: Services::new(),
+        squad_connect_client,
+        db: db.clone(),
+    };
 
-    let mut iteration = 1;
-    const MAX_ITERATIONS: usize = 5; // Prevent infinite loops
+    // Show the answer while it is produced, starting with a placeholder
+    let reply = StreamingReply::start(&bot, msg.chat.id).await?;
+    let on_text = |text: &str| reply.update(text);
+
+    // Tokens of every call to the model, counted even when a later call fails
+    let mut usage = TokenUsage::default();
+    let mut add_usage = |response: &LlmResponse| {
+        // Backends that report no usage are charged an estimate, or quotas would never apply
+        let used = reported_or_estimated(response.usage);
+        usage.input_tokens += used.input_tokens;
+        usage.output_tokens += used.output_tokens;
+    };
 
-    // Handle function calling loop
-    while !current_response.tool_calls().is_empty() && iteration <= MAX_ITERATIONS {
-        println!(
-            "🔧 Iteration {}: Processing {} tool calls",
-            iteration,
-            current_response.tool_calls().len()
-        );
+    let result = async {
+        // Call AI with function-calling enabled AND conversation continuity
+        let mut current_response = llm
+            .generate_streaming(&prompt_text, &schema, previous_response_id, &on_text)
+            .await?;
+        add_usage(&current_response);
 
-        let mut function_outputs = Vec::new();
+        let mut iteration = 1;
+        const MAX_ITERATIONS: usize = 5; // Prevent infinite loops
 
-        // Process all tool calls
-        for tool_call in current_response.tool_calls() {
-            println!("   📞 Function: {} ({})", tool_call.name, tool_call.call_id);
-            println!("   📋 Arguments: {}", tool_call.arguments);
+        // Handle function calling loop
+        while !current_response.tool_calls.is_empty() && iteration <= MAX_ITERATIONS {
+            println!(
+                "🔧 Iteration {}: Processing {} tool calls",
+                iteration,
+                current_response.tool_calls.len()
+            );
 
-            let username = username.clone();
+            let mut function_outputs = Vec::new();
 
-            // Execute function based on name
-            let result = match tool_call.name.as_str() {
-                "get_balance" => {
-                    handle_get_balance_tool(user_id, squad_connect_client.clone()).await
-                }
-                "withdraw" => {
-                    let args: serde_json::Value = serde_json::from_str(&tool_call.arguments)
-                        .unwrap_or_else(|_| serde_json::json!({}));
-                    handle_withdraw_tool(username, args, Services::new(), db.clone()).await
-                }
-                "send" => {
-                    let args: serde_json::Value = serde_json::from_str(&tool_call.arguments)
-                        .unwrap_or_else(|_| serde_json::json!({}));
-                    handle_send_tool(username, args, Services::new(), db.clone()).await
-                }
-                _ => format!("Unknown function call: {}", tool_call.name),
-            };
+            // Process all tool calls
+            for tool_call in &current_response.tool_calls {
+                println!("   📞 Function: {} ({})", tool_call.name, tool_call.call_id);
+                println!("   📋 Arguments: {}", tool_call.arguments);
+
+                reply.update(registry.progress(&tool_call.name));
+
+                let result = registry.dispatch(tool_call, &ctx).await;
+
+                println!("   ✅ Result: {}", result);
+                function_outputs.push((tool_call.call_id.clone(), result));
+            }
 
-            println!("   ✅ Result: {}", result);
-            function_outputs.push((tool_call.call_id.clone(), result));
+            // Submit tool outputs and get next response
+            current_response = llm
+                .submit_tool_outputs_streaming(
+                    &current_response.id,
+                    function_outputs,
+                    &schema,
+                    &on_text,
+                )
+                .await?;
+            add_usage(&current_response);
+
+            iteration += 1;
         }
 
-        // Submit tool outputs and get next response using unified method
-        current_response = responses_client
-            .generate_response(
-                None,
-                Some(schema.clone()),
-                None,
-                Some((current_response.id().to_string(), function_outputs)),
-            )
-            .await?;
+        if iteration > MAX_ITERATIONS {
+            println!(
+                "⚠️ Stopped after {} iterations to prevent infinite loop",
+                MAX_ITERATIONS
+            );
+        }
 
-        iteration += 1;
+        Ok::<_, CoreError>(current_response)
     }
+    .await;
 
-    if iteration > MAX_ITERATIONS {
-        println!(
-            "⚠️ Stopped after {} iterations to prevent infinite loop",
-            MAX_ITERATIONS
-        );
+    if let Err(e) = record_usage(quota_user_id, quota_chat_id, usage, db) {
+        println!("❌ Failed to record AI usage: {}", e);
     }
 
-    // Update cache with new response ID for next turn
-    conversation_cache
-        .update(user_key, current_response.id().to_string())
-        .await;
+    match result {
+        Ok(response) => {
+            // Update cache with new response ID for next turn
+            conversation_cache
+                .update(user_key, response.id.clone())
+                .await;
 
-    // Send final response
-    let response_text = current_response.output_text();
-    let message = bot
-        .send_message(msg.chat.id, response_text)
-        .parse_mode(ParseMode::Html)
-        .await?;
+            // Replace the streamed text with the formatted final response
+            reply.finish(&response.text).await
+        }
+        Err(e) => {
+            println!("❌ AI request failed: {}", e);
+            reply
+                .finish(&format!("❌ Sorry, I could not answer that: {}", e))
+                .await
+        }
+    }
+}
 
-    Ok(message)
+pub async fn handle_reset(
+    bot: Bot,
+    msg: Message,
+    conversation_cache: ConversationCache,
+) -> AnyhowResult<Message> {
+    let user = msg
+        .from
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("User not found"))?;
+
+    let user_key = (user.id.to_string(), msg.chat.id.to_string());
+
+    let text = match conversation_cache.reset(&user_key).await {
+        Ok(true) => "🧹 Conversation cleared. Your next prompt starts fresh.".to_string(),
+        Ok(false) => {
+            "There is no conversation to clear, your next prompt already starts fresh.".to_string()
+        }
+        Err(e) => format!("❌ Failed to clear the conversation: {}", e),
+    };
+
+    Ok(bot.send_message(msg.chat.id, text).await?)
+}
+
+fn describe_memory(ttl: Duration) -> String {
+    if ttl.is_zero() {
+        "🧠 Conversation memory is off in this chat, every prompt starts fresh.".to_string()
+    } else {
+        format!(
+            "🧠 The AI remembers this chat's conversation for {} minutes after the last message.",
+            ttl.as_secs().div_ceil(60)
+        )
+    }
+}
+
+/// Parse `/memory` arguments: minutes, `off` or `default` (`None`).
+fn parse_memory_ttl(args: &str) -> Result<Option<Duration>, String> {
+    match args.trim().to_lowercase().as_str() {
+        "off" => Ok(Some(Duration::ZERO)),
+        "default" => Ok(None),
+        minutes => minutes
+            .parse::<u64>()
+            .map(|minutes| Some(Duration::from_secs(minutes.saturating_mul(60))))
+            .map_err(|_| "Usage: /memory <minutes>, /memory off or /memory default".to_string()),
+    }
+}
+
+pub async fn handle_memory(
+    bot: Bot,
+    msg: Message,
+    args: String,
+    conversation_cache: ConversationCache,
+) -> AnyhowResult<Message> {
+    let chat_id = msg.chat.id.to_string();
+
+    if args.trim().is_empty() {
+        let ttl = conversation_cache.ttl(&chat_id).await;
+
+        return Ok(bot.send_message(msg.chat.id, describe_memory(ttl)).await?);
+    }
+
+    let user = msg
+        .from
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("User not found"))?;
+
+    // In groups the setting applies to everyone, so only chat admins can change it
+    if !msg.chat.is_private()
+        && !bot
+            .get_chat_member(msg.chat.id, user.id)
+            .await?
+            .is_privileged()
+    {
+        return Ok(bot
+            .send_message(
+                msg.chat.id,
+                "❌ Only chat administrators can change the conversation memory",
+            )
+            .await?);
+    }
+
+    let ttl = match parse_memory_ttl(&args) {
+        Ok(ttl) => ttl,
+        Err(e) => return Ok(bot.send_message(msg.chat.id, format!("❌ {}", e)).await?),
+    };
+
+    // The cache rejects a memory longer than its policy allows
+    let text = match conversation_cache.set_ttl(&chat_id, ttl).await {
+        Ok(ttl) => format!("✅ Updated\n\n{}", describe_memory(ttl)),
+        Err(CoreError::Other(message)) => format!("❌ {}", message),
+        Err(e) => format!("❌ {}", e),
+    };
+
+    Ok(bot.send_message(msg.chat.id, text).await?)
+}
+
+fn describe_quota(label: &str, status: &QuotaStatus) -> String {
+    match (status.limit, status.remaining()) {
+        (Some(limit), Some(remaining)) => format!(
+            "{}: {} of {} tokens left ({} used)",
+            label, remaining, limit, status.used
+        ),
+        _ => format!("{}: unlimited ({} tokens used)", label, status.used),
+    }
+}
+
+pub async fn handle_quota(bot: Bot, msg: Message, db: Db) -> AnyhowResult<Message> {
+    let user = msg
+        .from
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("User not found"))?;
+
+    let mut lines = vec![describe_quota(
+        "👤 You",
+        &quota_status(QuotaScope::User, user.id.0 as i64, db.clone())?,
+    )];
+
+    if !msg.chat.is_private() {
+        lines.push(describe_quota(
+            "👥 This group",
+            &quota_status(QuotaScope::Chat, msg.chat.id.0, db)?,
+        ));
+    }
+
+    Ok(bot
+        .send_message(
+            msg.chat.id,
+            format!(
+                "🎟️ Daily AI quota\n\n{}\n\nQuotas reset at 00:00 UTC.",
+                lines.join("\n")
+            ),
+        )
+        .await?)
 }
 
 pub async fn handle_get_balance_tool(
@@ -290,7 +583,10 @@ pub async fn handle_get_balance_tool(
 
     let objects = objects.unwrap();
 
-    let object_info = objects.data.last();
+    let object_info = objects
+        .data
+        .iter()
+        .find(|field| is_sui_balance_field(&field.name));
 
     if object_info.is_none() {
         return "Object not found".to_string();
@@ -358,189 +654,309 @@ pub async fn handle_get_balance_tool(
     return formatted_balance;
 }
 
-pub async fn handle_send_tool(
+/// Pay `amount` MIST to each target username; returns the explorer links, comma-separated.
+///
+/// When any payment fails the error lists the link or the error of every target, since the
+/// other payments may still have gone through.
+pub async fn send_payment(
     username: Option<String>,
-    args: serde_json::Value,
+    targets: Vec<String>,
+    amount: u64,
     services: Services,
     db: Db,
-) -> String {
-    let sui_explorer_url = env::var("SUI_EXPLORER_URL");
-
-    if sui_explorer_url.is_err() {
-        return "Error: SUI_EXPLORER_URL is not set".to_string();
+) -> AnyhowResult<String> {
+    let sui_explorer_url =
+        env::var("SUI_EXPLORER_URL").map_err(|_| anyhow::anyhow!("SUI_EXPLORER_URL is not set"))?;
+
+    let username = username.ok_or_else(|| anyhow::anyhow!("Username is required"))?;
+
+    let token = get_credentials(&username, db.clone())
+        .ok_or_else(|| anyhow::anyhow!("User not found"))?
+        .jwt;
+
+    let tasks: Vec<_> = targets
+        .into_iter()
+        .map(|target| {
+            let token = token.clone();
+            let services = services.clone();
+            let db = db.clone();
+            let sui_explorer_url = sui_explorer_url.clone();
+
+            tokio::spawn(async move {
+                let user = db
+                    .get(&target)
+                    .ok()
+                    .flatten()
+                    .ok_or_else(|| format!("Target {} not found", target))?;
+
+                let user = serde_json::from_slice::<Credentials>(&user)
+                    .map_err(|_| format!("Failed to parse the id of {}", target))?;
+
+                let request = PaymentRequest {
+                    amount,
+                    receiver_id: user.user_id.to_string(),
+                };
+
+                let payment = services
+                    .payment(token, request)
+                    .await
+                    .map_err(|e| format!("Failed to send payment to {}: {}", target, e))?;
+
+                Ok::<_, String>(format!("{}/txblock/{}", sui_explorer_url, payment.digest))
+            })
+        })
+        .collect();
+
+    let mut results = Vec::new();
+    for task in tasks {
+        results.push(
+            task.await
+                .unwrap_or_else(|_| Err("Payment task failed".to_string())),
+        );
     }
 
-    let sui_explorer_url = sui_explorer_url.unwrap();
-
-    if username.is_none() {
-        return "Error: Username is required".to_string();
+    if results.iter().all(|result| result.is_ok()) {
+        return Ok(results
+            .into_iter()
+            .filter_map(|result| result.ok())
+            .collect::<Vec<_>>()
+            .join(", "));
     }
 
-    let username = username.unwrap();
+    Err(anyhow::anyhow!(
+        results
+            .into_iter()
+            .map(|result| result.unwrap_or_else(|e| e))
+            .collect::<Vec<_>>()
+            .join(", ")
+    ))
+}
 
-    let credentials = get_credentials(&username, db.clone());
+/// Withdraw `amount` MIST to a Sui address, SuiNS name or address book label.
+pub async fn withdraw_funds(
+    username: Option<String>,
+    amount: u64,
+    address: &str,
+    services: Services,
+    db: Db,
+) -> AnyhowResult<String> {
+    let sui_explorer_url =
+        env::var("SUI_EXPLORER_URL").map_err(|_| anyhow::anyhow!("SUI_EXPLORER_URL is not set"))?;
 
-    if credentials.is_none() {
-        return "Error: User not found".to_string();
-    }
+    let username = username.ok_or_else(|| anyhow::anyhow!("Username is required"))?;
 
-    let token = credentials.unwrap().jwt;
-    let targets = args.get("targets");
-    let amount_value = args.get("amount");
+    let credentials =
+        get_credentials(&username, db.clone()).ok_or_else(|| anyhow::anyhow!("User not found"))?;
 
-    if targets.is_none() {
-        return "Error: Target is required".to_string();
-    }
+    let address = resolve_address(&credentials.user_id.to_string(), address, db)?;
 
-    let targets = targets.unwrap();
+    let request = WithdrawRequest { amount, address };
 
-    if amount_value.is_none() {
-        return "Error: Amount is required".to_string();
-    }
+    let withdrawal = services
+        .withdraw(credentials.jwt, request)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to withdraw: {}", e))?;
 
-    println!("amount_value: {:?}", amount_value.unwrap());
+    let destination = match withdrawal.name {
+        Some(name) => format!("{} (<code>{}</code>)", name, withdrawal.address),
+        None => format!("<code>{}</code>", withdrawal.address),
+    };
 
-    let amount_number = amount_value.unwrap().as_f64();
+    Ok(format!(
+        "Withdrawn to {}: {}/txblock/{}",
+        destination, sui_explorer_url, withdrawal.digest
+    ))
+}
 
-    if amount_number.is_none() {
-        return "Error: Amount is required".to_string();
+fn format_limit(limit: Option<u64>) -> String {
+    match limit {
+        Some(limit) => format!("{} SUI", limit as f64 / 1_000_000_000f64),
+        None => "no limit".to_string(),
     }
+}
 
-    // Convert SUI amount to raw format (9 decimal places)
-    let sui_decimals = 1_000_000_000u64; // 10^9
-    let amount = (amount_number.unwrap() * sui_decimals as f64) as u64;
-
-    if targets.is_array() {
-        let targets = targets.as_array().unwrap();
-        let tasks: Vec<_> = targets
-            .iter()
-            .map(|target| {
-                let target = target.as_str().unwrap().to_string();
-                let token = token.clone();
-                let services = services.clone();
-                let db = db.clone();
-                let sui_explorer_url = sui_explorer_url.clone();
-
-                tokio::spawn(async move {
-                    let user = db.get(&target);
-
-                    if user.is_err() {
-                        return "Error: Target not found".to_string();
-                    }
-
-                    let user = user.unwrap();
-
-                    if user.is_none() {
-                        return "Error: Target not found".to_string();
-                    }
-
-                    let user_vec = user.unwrap();
-
-                    let user = serde_json::from_slice::<Credentials>(&user_vec);
-
-                    if user.is_err() {
-                        return "Error: Failed to parse target id".to_string();
-                    }
+fn format_limits(limits: &LimitsResponse) -> String {
+    format!(
+        "📊 <b>Spending limits</b> ({})\n\n\
+         Daily: {} (spent {})\n\
+         Weekly: {} (spent {})\n\
+         Per transaction: {}\n\n\
+         Admin policy: daily {}, weekly {}, per transaction {}\n\n\
+         💡 Lower a limit with /limits daily 5, /limits weekly 20 or /limits tx 2. Use /limits daily off to fall back to the admin policy.",
+        limits.coin_type,
+        format_limit(limits.effective.daily),
+        format_limit(Some(limits.spent_daily)),
+        format_limit(limits.effective.weekly),
+        format_limit(Some(limits.spent_weekly)),
+        format_limit(limits.effective.per_transaction),
+        format_limit(limits.policy.daily),
+        format_limit(limits.policy.weekly),
+        format_limit(limits.policy.per_transaction),
+    )
+}
 
-                    let request = PaymentRequest {
-                        amount,
-                        receiver_id: user.unwrap().user_id.to_string(),
-                    };
+fn parse_limit_update(args: &str, mut limits: SpendingLimits) -> Result<SpendingLimits, String> {
+    let mut parts = args.split_whitespace();
 
-                    let digests = services.payment(token, request).await;
+    let (Some(period), Some(value), None) = (parts.next(), parts.next(), parts.next()) else {
+        return Err("Usage: /limits [daily|weekly|tx] [amount in SUI|off]".to_string());
+    };
 
-                    if digests.is_err() {
-                        return "Error: Failed to send payment".to_string();
-                    }
+    let value = if value.eq_ignore_ascii_case("off") {
+        None
+    } else {
+        let amount = value
+            .parse::<f64>()
+            .ok()
+            .filter(|amount| *amount >= 0.0)
+            .ok_or_else(|| format!("Invalid amount: {}", value))?;
 
-                    let digests = digests.unwrap();
-                    format!("{}/txblock/{}", sui_explorer_url, digests.digest)
-                })
-            })
-            .collect();
+        Some((amount * 1_000_000_000f64) as u64)
+    };
 
-        let mut digests = Vec::new();
-        for task in tasks {
-            match task.await {
-                Ok(result) => digests.push(result),
-                Err(_) => digests.push("Error: Task failed".to_string()),
-            }
+    match period.to_lowercase().as_str() {
+        "daily" | "day" => limits.daily = value,
+        "weekly" | "week" => limits.weekly = value,
+        "tx" | "transaction" | "per_transaction" => limits.per_transaction = value,
+        _ => {
+            return Err(format!(
+                "Unknown limit: {}. Use daily, weekly or tx.",
+                period
+            ));
         }
-
-        return digests.join(", ");
-    } else {
-        return "Error: Targets must be an array".to_string();
     }
+
+    Ok(limits)
 }
 
-pub async fn handle_withdraw_tool(
-    username: Option<String>,
-    args: serde_json::Value,
+pub async fn handle_limits(
+    bot: Bot,
+    msg: Message,
+    args: String,
     services: Services,
     db: Db,
-) -> String {
-    let sui_explorer_url = env::var("SUI_EXPLORER_URL");
-
-    if sui_explorer_url.is_err() {
-        return "Error: SUI_EXPLORER_URL is not set".to_string();
-    }
-
-    let sui_explorer_url = sui_explorer_url.unwrap();
-
-    if username.is_none() {
-        return "Error: Username is required".to_string();
-    }
-
-    let username = username.unwrap();
-
-    let credentials = get_credentials(&username, db.clone());
+) -> AnyhowResult<Message> {
+    let username = msg.from.clone().and_then(|user| user.username);
 
-    if credentials.is_none() {
-        return "Error: User not found".to_string();
-    }
+    let Some(username) = username else {
+        return Ok(bot
+            .send_message(msg.chat.id, "❌ Username is required to manage limits")
+            .await?);
+    };
 
-    let token = credentials.unwrap().jwt;
+    let Some(credentials) = get_credentials(&username, db) else {
+        return Ok(bot
+            .send_message(msg.chat.id, "❌ Please /login first")
+            .await?);
+    };
 
-    let amount_value = args.get("amount");
+    let coin_type = "0x2::sui::SUI".to_string();
 
-    if amount_value.is_none() {
-        return "Error: Amount is required".to_string();
-    }
+    let current = services
+        .get_limits(credentials.jwt.clone(), coin_type.clone())
+        .await;
 
-    let amount_number = amount_value.unwrap().as_f64();
+    let current = match current {
+        Ok(current) => current,
+        Err(e) => {
+            return Ok(bot
+                .send_message(msg.chat.id, format!("❌ Failed to get limits: {}", e))
+                .await?);
+        }
+    };
 
-    if amount_number.is_none() {
-        return "Error: Amount is required".to_string();
+    if args.trim().is_empty() {
+        return Ok(bot
+            .send_message(msg.chat.id, format_limits(&current))
+            .parse_mode(ParseMode::Html)
+            .await?);
     }
 
-    let amount = (amount_number.unwrap() * 1_000_000_000 as f64) as u64;
-
-    let address_value = args.get("address");
+    let limits = match parse_limit_update(&args, current.user_limits) {
+        Ok(limits) => limits,
+        Err(e) => return Ok(bot.send_message(msg.chat.id, format!("❌ {}", e)).await?),
+    };
 
-    if address_value.is_none() {
-        return "Error: Address is required".to_string();
-    }
+    let updated = services
+        .set_limits(credentials.jwt, LimitsRequest { coin_type, limits })
+        .await;
 
-    let address = address_value.unwrap().as_str();
+    let message = match updated {
+        Ok(updated) => {
+            bot.send_message(
+                msg.chat.id,
+                format!("✅ Limits updated\n\n{}", format_limits(&updated)),
+            )
+            .parse_mode(ParseMode::Html)
+            .await?
+        }
+        Err(e) => {
+            bot.send_message(msg.chat.id, format!("❌ Failed to update limits: {}", e))
+                .await?
+        }
+    };
 
-    if address.is_none() {
-        return "Error: Address is required".to_string();
-    }
+    Ok(message)
+}
 
-    let request = WithdrawRequest {
-        amount,
-        address: address.unwrap().to_string(),
+pub async fn handle_address_book(
+    bot: Bot,
+    msg: Message,
+    args: String,
+    db: Db,
+) -> AnyhowResult<Message> {
+    let Some(user) = msg.from.clone() else {
+        return Ok(bot
+            .send_message(msg.chat.id, "❌ Unable to identify user")
+            .await?);
     };
 
-    let digests = services.withdraw(token, request).await;
+    let user_id = user.id.to_string();
+    let parts: Vec<&str> = args.split_whitespace().collect();
 
-    if digests.is_err() {
-        return "Error: Failed to withdraw".to_string();
-    }
+    let text = match parts.as_slice() {
+        [] | ["list"] => match get_address_book(&user_id, db) {
+            Ok(address_book) if address_book.entries.is_empty() => {
+                "📒 Your address book is empty.\n\nAdd an address with /addressbook add cold 0x…"
+                    .to_string()
+            }
+            Ok(address_book) => {
+                let entries = address_book
+                    .entries
+                    .iter()
+                    .map(|(label, address)| {
+                        format!("• <b>{}</b>: <code>{}</code>", escape(label), escape(address))
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+
+                format!("📒 <b>Address book</b>\n\n{}", entries)
+            }
+            Err(e) => format!("❌ Failed to read address book: {}", escape(&e.to_string())),
+        },
+        ["add", label, address] => match add_address(&user_id, label, address, db) {
+            Ok(label) => format!(
+                "✅ Saved <b>{}</b>. You can now say \"withdraw 5 SUI to {}\"",
+                escape(&label),
+                escape(&label)
+            ),
+            Err(e) => format!("❌ {}", escape(&e.to_string())),
+        },
+        ["remove", label] => match remove_address(&user_id, label, db) {
+            Ok(Some(_)) => format!("🗑️ Removed <b>{}</b>", escape(&label.to_lowercase())),
+            Ok(None) => format!("❌ <b>{}</b> is not in your address book", escape(label)),
+            Err(e) => format!("❌ {}", escape(&e.to_string())),
+        },
+        _ => "Usage:\n/addressbook add &lt;label&gt; &lt;address&gt;\n/addressbook list\n/addressbook remove &lt;label&gt;"
+            .to_string(),
+    };
 
-    let digests = digests.unwrap();
+    let message = bot
+        .send_message(msg.chat.id, text)
+        .parse_mode(ParseMode::Html)
+        .await?;
 
-    return format!("{}/txblock/{}", sui_explorer_url, digests.digest);
+    Ok(message)
 }
 
 pub async fn handle_login(bot: Bot, msg: Message, db: Db) -> AnyhowResult<Message> {
@@ -1,4 +1,6 @@
+pub mod admin;
 pub mod answer;
+pub mod dto;
 pub mod handler_tree;
 pub mod handlers;
-pub mod dto;
\ No newline at end of file
+pub mod stream;
@@ -0,0 +1,133 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use teloxide::{
+    Bot,
+    prelude::*,
+    types::{ChatAction, Message, MessageId, ParseMode},
+};
+use tokio::{sync::watch, task::JoinHandle};
+
+/// Telegram rate limits edits, so a streamed answer is shown at most this often.
+const EDIT_INTERVAL: Duration = Duration::from_millis(1200);
+/// The typing action lasts about five seconds and must be renewed.
+const TYPING_INTERVAL: Duration = Duration::from_secs(4);
+const MAX_MESSAGE_CHARS: usize = 4096;
+
+fn truncate(text: &str) -> String {
+    match text.char_indices().nth(MAX_MESSAGE_CHARS - 1) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+/// A reply sent as a placeholder and edited in place while the answer is produced.
+pub struct StreamingReply {
+    bot: Bot,
+    chat_id: ChatId,
+    message: Message,
+    text: watch::Sender<String>,
+    editor: JoinHandle<()>,
+    typing: JoinHandle<()>,
+}
+
+impl StreamingReply {
+    pub async fn start(bot: &Bot, chat_id: ChatId) -> Result<Self> {
+        let message = bot.send_message(chat_id, "💭 Thinking…").await?;
+
+        let typing = tokio::spawn({
+            let bot = bot.clone();
+
+            async move {
+                loop {
+                    bot.send_chat_action(chat_id, ChatAction::Typing).await.ok();
+                    tokio::time::sleep(TYPING_INTERVAL).await;
+                }
+            }
+        });
+
+        let (text, mut updates) = watch::channel(String::new());
+
+        let editor = tokio::spawn({
+            let bot = bot.clone();
+            let message_id = message.id;
+
+            async move {
+                while updates.changed().await.is_ok() {
+                    let text = updates.borrow_and_update().clone();
+
+                    edit_plain(&bot, chat_id, message_id, &text).await;
+                    tokio::time::sleep(EDIT_INTERVAL).await;
+                }
+            }
+        });
+
+        Ok(StreamingReply {
+            bot: bot.clone(),
+            chat_id,
+            message,
+            text,
+            editor,
+            typing,
+        })
+    }
+
+    /// Show `text` in place of the reply; only the latest text is shown when updates come
+    /// faster than edits are allowed.
+    pub fn update(&self, text: &str) {
+        if !text.trim().is_empty() {
+            self.text.send_replace(text.to_string());
+        }
+    }
+
+    /// Stop streaming and show the final answer, formatted as HTML when it is valid.
+    pub async fn finish(self, text: &str) -> Result<Message> {
+        self.editor.abort();
+        self.typing.abort();
+
+        let text = if text.trim().is_empty() {
+            "🤷 I have nothing to add."
+        } else {
+            text
+        };
+
+        let html = self
+            .bot
+            .edit_message_text(self.chat_id, self.message.id, truncate(text))
+            .parse_mode(ParseMode::Html)
+            .await;
+
+        match html {
+            Ok(message) => Ok(message),
+            Err(e) => {
+                println!(
+                    "⚠️ Failed to show the answer as HTML, sending plain text: {}",
+                    e
+                );
+
+                Ok(self
+                    .bot
+                    .edit_message_text(self.chat_id, self.message.id, truncate(text))
+                    .await
+                    .unwrap_or_else(|_| self.message.clone()))
+            }
+        }
+    }
+}
+
+impl Drop for StreamingReply {
+    fn drop(&mut self) {
+        self.editor.abort();
+        self.typing.abort();
+    }
+}
+
+/// Partial answers are shown as plain text, their HTML tags may not be closed yet.
+async fn edit_plain(bot: &Bot, chat_id: ChatId, message_id: MessageId, text: &str) {
+    if let Err(e) = bot
+        .edit_message_text(chat_id, message_id, truncate(text))
+        .await
+    {
+        println!("⚠️ Failed to update the streamed answer: {}", e);
+    }
+}
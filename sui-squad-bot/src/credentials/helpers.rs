@@ -1,5 +1,6 @@
 use anyhow::Result;
 use sled::Db;
+use teloxide::types::UserId;
 
 use crate::credentials::dto::Credentials;
 
@@ -20,3 +21,16 @@ pub fn save_credentials(username: &str, credentials: Credentials, db: Db) -> Res
 
     Ok(())
 }
+
+/// Find the username a Telegram user logged in with.
+pub fn find_username(user_id: UserId, db: Db) -> Option<String> {
+    db.iter()
+        .filter_map(|entry| entry.ok())
+        .find_map(|(key, bytes)| {
+            let credentials = serde_json::from_slice::<Credentials>(&bytes).ok()?;
+
+            (credentials.user_id == user_id)
+                .then(|| String::from_utf8(key.to_vec()).ok())
+                .flatten()
+        })
+}
@@ -0,0 +1,292 @@
+use anyhow::Result;
+use sled::Db;
+use sui_squad_core::commands::wizard::{
+    DialogueState, Wizard, WizardCommand, WizardFlow, WizardStep,
+};
+use teloxide::{
+    Bot,
+    prelude::*,
+    types::{CallbackQuery, Message, ParseMode},
+    utils::html::escape,
+};
+
+use crate::{
+    address_book::helpers::resolve_address,
+    bot_manage::handlers::{send_payment, withdraw_funds},
+    credentials::helpers::get_credentials,
+    services::services::Services,
+};
+
+use super::{
+    BotDialogue,
+    helpers::{
+        CANCEL_CALLBACK, CONFIRM_CALLBACK, TOKEN_CALLBACK_PREFIX, advance, amount_prompt,
+        confirm_keyboard, confirmation_text, flow_name, is_expired, new_wizard, parse_units,
+        recipient_prompt, token_decimals, token_keyboard, validate_amount,
+    },
+};
+
+const TIMED_OUT: &str = "⌛ This flow timed out. Start again with /send or /withdraw.";
+
+pub async fn answer_wizard(
+    bot: Bot,
+    msg: Message,
+    cmd: WizardCommand,
+    dialogue: BotDialogue,
+    state: DialogueState,
+) -> Result<()> {
+    let flow = match cmd {
+        WizardCommand::Send => WizardFlow::Send,
+        WizardCommand::Withdraw => WizardFlow::Withdraw,
+        WizardCommand::Cancel => {
+            let text = match state {
+                DialogueState::Wizard(wizard) => {
+                    dialogue.exit().await?;
+                    format!("❌ The {} flow was cancelled.", flow_name(wizard.flow))
+                }
+                DialogueState::Idle => "There is nothing to cancel.".to_string(),
+            };
+
+            bot.send_message(msg.chat.id, text).await?;
+            return Ok(());
+        }
+    };
+
+    if !msg.chat.is_private() {
+        bot.send_message(
+            msg.chat.id,
+            format!("You only can use /{} in private Bot chat", flow_name(flow)),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    // Starting a flow replaces any flow left unfinished
+    dialogue
+        .update(DialogueState::Wizard(new_wizard(flow)))
+        .await?;
+
+    bot.send_message(msg.chat.id, recipient_prompt(flow))
+        .await?;
+
+    Ok(())
+}
+
+/// Validate the recipient of the flow: a registered username for `/send`, a resolvable
+/// destination for `/withdraw`.
+fn check_recipient(wizard: &Wizard, msg: &Message, text: &str, db: Db) -> Result<String, String> {
+    let user = msg.from.as_ref().ok_or("User not found")?;
+
+    match wizard.flow {
+        WizardFlow::Send => {
+            let username = text.trim().trim_start_matches('@').to_string();
+
+            if user.username.as_deref() == Some(username.as_str()) {
+                return Err("You cannot send tokens to yourself".to_string());
+            }
+
+            if get_credentials(&username, db).is_none() {
+                return Err(format!(
+                    "@{} has not used Sui Squad yet, ask them to /login first",
+                    username
+                ));
+            }
+
+            Ok(username)
+        }
+        WizardFlow::Withdraw => {
+            resolve_address(&user.id.to_string(), text, db).map_err(|e| e.to_string())
+        }
+    }
+}
+
+pub async fn handle_wizard_message(
+    bot: Bot,
+    msg: Message,
+    dialogue: BotDialogue,
+    wizard: Wizard,
+    db: Db,
+) -> Result<()> {
+    let Some(text) = msg.text() else {
+        return Ok(());
+    };
+
+    if is_expired(&wizard) {
+        dialogue.exit().await?;
+        bot.send_message(msg.chat.id, TIMED_OUT).await?;
+        return Ok(());
+    }
+
+    match wizard.step {
+        WizardStep::Recipient => match check_recipient(&wizard, &msg, text, db) {
+            Ok(recipient) => {
+                let mut wizard = advance(wizard, WizardStep::Amount);
+                wizard.recipient = Some(recipient);
+
+                dialogue.update(DialogueState::Wizard(wizard)).await?;
+                bot.send_message(msg.chat.id, amount_prompt()).await?;
+            }
+            Err(e) => {
+                bot.send_message(msg.chat.id, format!("❌ {}", e)).await?;
+            }
+        },
+        WizardStep::Amount => match validate_amount(text) {
+            Ok(amount) => {
+                let mut wizard = advance(wizard, WizardStep::Token);
+                wizard.amount = Some(amount);
+
+                dialogue.update(DialogueState::Wizard(wizard)).await?;
+                bot.send_message(msg.chat.id, "🪙 Which token?")
+                    .reply_markup(token_keyboard())
+                    .await?;
+            }
+            Err(e) => {
+                bot.send_message(msg.chat.id, format!("❌ {}", e)).await?;
+            }
+        },
+        WizardStep::Token => {
+            choose_token(&bot, msg.chat.id, &dialogue, wizard, text).await?;
+        }
+        WizardStep::Confirm => {
+            bot.send_message(
+                msg.chat.id,
+                "Use the buttons above to confirm, or send /cancel to stop.",
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn choose_token(
+    bot: &Bot,
+    chat_id: ChatId,
+    dialogue: &BotDialogue,
+    wizard: Wizard,
+    symbol: &str,
+) -> Result<()> {
+    let Some(decimals) = token_decimals(symbol.trim()) else {
+        bot.send_message(chat_id, "❌ Pick one of the tokens below")
+            .reply_markup(token_keyboard())
+            .await?;
+        return Ok(());
+    };
+
+    // The amount is only checked against the token's decimals once the token is known
+    if let Err(e) = parse_units(wizard.amount.as_deref().unwrap_or_default(), decimals) {
+        let wizard = advance(wizard, WizardStep::Amount);
+
+        dialogue.update(DialogueState::Wizard(wizard)).await?;
+        bot.send_message(chat_id, format!("❌ {}\n\n{}", e, amount_prompt()))
+            .await?;
+        return Ok(());
+    }
+
+    let mut wizard = advance(wizard, WizardStep::Confirm);
+    wizard.token = Some(symbol.trim().to_uppercase());
+
+    bot.send_message(chat_id, confirmation_text(&wizard))
+        .parse_mode(ParseMode::Html)
+        .reply_markup(confirm_keyboard())
+        .await?;
+
+    dialogue.update(DialogueState::Wizard(wizard)).await?;
+
+    Ok(())
+}
+
+async fn execute(
+    username: Option<String>,
+    wizard: &Wizard,
+    services: Services,
+    db: Db,
+) -> Result<String> {
+    let recipient = wizard.recipient.clone().unwrap_or_default();
+    let token = wizard.token.as_deref().unwrap_or_default();
+
+    let decimals =
+        token_decimals(token).ok_or_else(|| anyhow::anyhow!("Unsupported token {}", token))?;
+    let amount = parse_units(wizard.amount.as_deref().unwrap_or_default(), decimals)?;
+
+    match wizard.flow {
+        WizardFlow::Send => send_payment(username, vec![recipient], amount, services, db).await,
+        WizardFlow::Withdraw => withdraw_funds(username, amount, &recipient, services, db).await,
+    }
+}
+
+pub async fn handle_wizard_callback(
+    bot: Bot,
+    q: CallbackQuery,
+    dialogue: BotDialogue,
+    state: DialogueState,
+    services: Services,
+    db: Db,
+) -> Result<()> {
+    bot.answer_callback_query(q.id.clone()).await?;
+
+    let chat_id = dialogue.chat_id();
+    let data = q.data.clone().unwrap_or_default();
+
+    // The buttons are single-use, whatever happens next
+    if let Some(message) = &q.message {
+        bot.edit_message_reply_markup(chat_id, message.id())
+            .await
+            .ok();
+    }
+
+    let DialogueState::Wizard(wizard) = state else {
+        bot.send_message(
+            chat_id,
+            "This flow is no longer active. Start again with /send or /withdraw.",
+        )
+        .await?;
+        return Ok(());
+    };
+
+    if is_expired(&wizard) {
+        dialogue.exit().await?;
+        bot.send_message(chat_id, TIMED_OUT).await?;
+        return Ok(());
+    }
+
+    if data == CANCEL_CALLBACK {
+        dialogue.exit().await?;
+        bot.send_message(
+            chat_id,
+            format!("❌ The {} flow was cancelled.", flow_name(wizard.flow)),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    match (wizard.step, data.strip_prefix(TOKEN_CALLBACK_PREFIX)) {
+        (WizardStep::Token, Some(symbol)) => {
+            choose_token(&bot, chat_id, &dialogue, wizard, symbol).await?;
+        }
+        (WizardStep::Confirm, None) if data == CONFIRM_CALLBACK => {
+            // Leave the flow before executing so a double tap cannot pay twice
+            dialogue.exit().await?;
+
+            bot.send_message(chat_id, "⏳ Processing...").await?;
+
+            let text = match execute(q.from.username.clone(), &wizard, services, db).await {
+                Ok(result) => format!("✅ {}", result),
+                Err(e) => format!("❌ {}", escape(&e.to_string())),
+            };
+
+            bot.send_message(chat_id, text)
+                .parse_mode(ParseMode::Html)
+                .await?;
+        }
+        _ => {
+            bot.send_message(
+                chat_id,
+                "These buttons belong to an earlier step of the flow.",
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
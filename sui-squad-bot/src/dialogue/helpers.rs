@@ -0,0 +1,206 @@
+use std::env;
+
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use sui_squad_core::commands::wizard::{Wizard, WizardFlow, WizardStep};
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+/// Tokens offered by the guided flows: symbol and decimals.
+pub const WIZARD_TOKENS: &[(&str, u8)] = &[("SUI", 9)];
+
+pub const TOKEN_CALLBACK_PREFIX: &str = "wizard:token:";
+pub const CONFIRM_CALLBACK: &str = "wizard:confirm";
+pub const CANCEL_CALLBACK: &str = "wizard:cancel";
+
+/// How long a guided flow waits for the next step, from `WIZARD_TIMEOUT_SECS` (5 minutes by default).
+fn wizard_timeout_millis() -> i64 {
+    env::var("WIZARD_TIMEOUT_SECS")
+        .ok()
+        .and_then(|secs| secs.parse::<i64>().ok())
+        .unwrap_or(300)
+        * 1000
+}
+
+pub fn new_wizard(flow: WizardFlow) -> Wizard {
+    Wizard {
+        flow,
+        step: WizardStep::Recipient,
+        recipient: None,
+        amount: None,
+        token: None,
+        updated_at: Utc::now().timestamp_millis(),
+    }
+}
+
+/// Move the flow to `step`, restarting its timeout.
+pub fn advance(mut wizard: Wizard, step: WizardStep) -> Wizard {
+    wizard.step = step;
+    wizard.updated_at = Utc::now().timestamp_millis();
+    wizard
+}
+
+pub fn is_expired(wizard: &Wizard) -> bool {
+    Utc::now().timestamp_millis() - wizard.updated_at > wizard_timeout_millis()
+}
+
+pub fn token_decimals(symbol: &str) -> Option<u8> {
+    WIZARD_TOKENS
+        .iter()
+        .find(|(token, _)| token.eq_ignore_ascii_case(symbol))
+        .map(|(_, decimals)| *decimals)
+}
+
+/// Check that an amount typed by the user is a positive decimal number.
+pub fn validate_amount(amount: &str) -> Result<String> {
+    let amount = amount.trim();
+
+    let valid = !amount.is_empty()
+        && amount.chars().all(|c| c.is_ascii_digit() || c == '.')
+        && amount.matches('.').count() <= 1
+        && amount.chars().any(|c| c.is_ascii_digit() && c != '0');
+
+    if !valid {
+        return Err(anyhow!(
+            "'{}' is not a valid amount. Reply with a number greater than zero, e.g. 1.5",
+            amount
+        ));
+    }
+
+    Ok(amount.to_string())
+}
+
+/// Convert a decimal amount to the token's smallest unit without going through floats.
+pub fn parse_units(amount: &str, decimals: u8) -> Result<u64> {
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+
+    if fraction.len() > decimals as usize {
+        return Err(anyhow!("At most {} decimal places are allowed", decimals));
+    }
+
+    let digits = format!("{}{:0<width$}", whole, fraction, width = decimals as usize);
+
+    let units = digits
+        .parse::<u64>()
+        .map_err(|_| anyhow!("'{}' is too large", amount))?;
+
+    if units == 0 {
+        return Err(anyhow!("The amount must be greater than zero"));
+    }
+
+    Ok(units)
+}
+
+pub fn flow_name(flow: WizardFlow) -> &'static str {
+    match flow {
+        WizardFlow::Send => "send",
+        WizardFlow::Withdraw => "withdraw",
+    }
+}
+
+pub fn recipient_prompt(flow: WizardFlow) -> &'static str {
+    match flow {
+        WizardFlow::Send => {
+            "👤 Who do you want to send tokens to? Reply with their @username.\n\nSend /cancel to stop."
+        }
+        WizardFlow::Withdraw => {
+            "📍 Where should the tokens go? Reply with a Sui address, a .sui name or an address book label.\n\nSend /cancel to stop."
+        }
+    }
+}
+
+pub fn amount_prompt() -> &'static str {
+    "💰 How much? Reply with the amount, e.g. 1.5"
+}
+
+pub fn token_keyboard() -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![
+        WIZARD_TOKENS
+            .iter()
+            .map(|(symbol, _)| {
+                InlineKeyboardButton::callback(
+                    symbol.to_string(),
+                    format!("{}{}", TOKEN_CALLBACK_PREFIX, symbol),
+                )
+            })
+            .collect::<Vec<_>>(),
+    ])
+}
+
+pub fn confirm_keyboard() -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![vec![
+        InlineKeyboardButton::callback("✅ Confirm", CONFIRM_CALLBACK),
+        InlineKeyboardButton::callback("❌ Cancel", CANCEL_CALLBACK),
+    ]])
+}
+
+pub fn confirmation_text(wizard: &Wizard) -> String {
+    let recipient = wizard.recipient.as_deref().unwrap_or_default();
+    let amount = wizard.amount.as_deref().unwrap_or_default();
+    let token = wizard.token.as_deref().unwrap_or_default();
+
+    match wizard.flow {
+        WizardFlow::Send => format!(
+            "📝 <b>Confirm payment</b>\n\nSend {} {} to @{}?",
+            amount, token, recipient
+        ),
+        WizardFlow::Withdraw => format!(
+            "📝 <b>Confirm withdrawal</b>\n\nWithdraw {} {} to <code>{}</code>?",
+            amount, token, recipient
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_amount_rejects_zero() {
+        assert!(validate_amount("0").is_err());
+        assert!(validate_amount("0.000").is_err());
+        assert!(validate_amount(".0").is_err());
+    }
+
+    #[test]
+    fn validate_amount_rejects_malformed_input() {
+        assert!(validate_amount("").is_err());
+        assert!(validate_amount("   ").is_err());
+        assert!(validate_amount("abc").is_err());
+        assert!(validate_amount("-1").is_err());
+        assert!(validate_amount("1.2.3").is_err());
+        assert!(validate_amount("1e9").is_err());
+    }
+
+    #[test]
+    fn validate_amount_trims_valid_input() {
+        assert_eq!(validate_amount(" 1.5 ").unwrap(), "1.5");
+        assert_eq!(validate_amount("0.000000001").unwrap(), "0.000000001");
+    }
+
+    #[test]
+    fn parse_units_scales_by_decimals() {
+        assert_eq!(parse_units("1.5", 9).unwrap(), 1_500_000_000);
+        assert_eq!(parse_units("2", 9).unwrap(), 2_000_000_000);
+        assert_eq!(parse_units("0.000000001", 9).unwrap(), 1);
+        assert_eq!(parse_units(".25", 2).unwrap(), 25);
+    }
+
+    #[test]
+    fn parse_units_rejects_zero() {
+        assert!(parse_units("0", 9).is_err());
+        assert!(parse_units("0.000000000", 9).is_err());
+    }
+
+    #[test]
+    fn parse_units_rejects_too_many_decimals() {
+        assert!(parse_units("0.0000000001", 9).is_err());
+        assert!(parse_units("1.001", 2).is_err());
+    }
+
+    #[test]
+    fn parse_units_rejects_overflow() {
+        assert!(parse_units("18446744073709551616", 0).is_err());
+        assert!(parse_units("18446744074", 9).is_err());
+        assert_eq!(parse_units("18446744073.709551615", 9).unwrap(), u64::MAX);
+    }
+}
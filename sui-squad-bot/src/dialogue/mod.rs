@@ -0,0 +1,11 @@
+pub mod handlers;
+pub mod helpers;
+pub mod storage;
+
+use sui_squad_core::commands::wizard::DialogueState;
+use teloxide::dispatching::dialogue::Dialogue;
+
+use storage::SledStorage;
+
+pub type DialogueStorage = SledStorage<DialogueState>;
+pub type BotDialogue = Dialogue<DialogueState, DialogueStorage>;
@@ -0,0 +1,84 @@
+use std::{future::Future, marker::PhantomData, pin::Pin, sync::Arc};
+
+use serde::{Serialize, de::DeserializeOwned};
+use sled::{Db, Tree};
+use teloxide::{dispatching::dialogue::Storage, types::ChatId};
+use thiserror::Error;
+
+const DIALOGUES_TREE: &str = "dialogues";
+
+type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+#[derive(Debug, Error)]
+pub enum SledStorageError {
+    #[error("Dialogue storage error: {0}")]
+    Sled(#[from] sled::Error),
+
+    #[error("Dialogue serialization error: {0}")]
+    Serde(#[from] serde_json::Error),
+}
+
+/// Teloxide dialogue storage in a sled tree of the bot database, so flows survive restarts.
+pub struct SledStorage<D> {
+    tree: Tree,
+    dialogue: PhantomData<fn() -> D>,
+}
+
+impl<D> SledStorage<D> {
+    pub fn open(db: &Db) -> Result<Arc<Self>, SledStorageError> {
+        Ok(Arc::new(Self {
+            tree: db.open_tree(DIALOGUES_TREE)?,
+            dialogue: PhantomData,
+        }))
+    }
+}
+
+impl<D> Storage<D> for SledStorage<D>
+where
+    D: Serialize + DeserializeOwned + Send + 'static,
+{
+    type Error = SledStorageError;
+
+    fn remove_dialogue(self: Arc<Self>, chat_id: ChatId) -> BoxFuture<Result<(), Self::Error>>
+    where
+        D: Send + 'static,
+    {
+        Box::pin(async move {
+            self.tree.remove(chat_id.0.to_be_bytes())?;
+            Ok(())
+        })
+    }
+
+    fn update_dialogue(
+        self: Arc<Self>,
+        chat_id: ChatId,
+        dialogue: D,
+    ) -> BoxFuture<Result<(), Self::Error>>
+    where
+        D: Send + 'static,
+    {
+        Box::pin(async move {
+            let bytes = serde_json::to_vec(&dialogue)?;
+            self.tree.insert(chat_id.0.to_be_bytes(), bytes)?;
+            Ok(())
+        })
+    }
+
+    fn get_dialogue(self: Arc<Self>, chat_id: ChatId) -> BoxFuture<Result<Option<D>, Self::Error>> {
+        Box::pin(async move {
+            let Some(bytes) = self.tree.get(chat_id.0.to_be_bytes())? else {
+                return Ok(None);
+            };
+
+            match serde_json::from_slice(&bytes) {
+                Ok(dialogue) => Ok(Some(dialogue)),
+                // A state saved by an older version would block the chat, so start over instead
+                Err(e) => {
+                    println!("⚠️ Dropping unreadable dialogue of chat {}: {}", chat_id, e);
+                    self.tree.remove(chat_id.0.to_be_bytes())?;
+                    Ok(None)
+                }
+            }
+        })
+    }
+}
@@ -1,23 +1,34 @@
+mod address_book;
 mod bot_manage;
 mod credentials;
 mod db;
+mod dialogue;
 mod middleware;
+mod notifications;
 mod services;
 mod tools;
+mod usage;
+mod wallet;
 
 use anyhow::Result;
 use bot_manage::handler_tree::handler_tree;
+use dialogue::DialogueStorage;
 use dotenvy::dotenv;
 use services::services::Services;
 use squad_connect::{client::squad_connect::SquadConnect, service::dtos::Network};
 use std::env;
+use std::sync::Arc;
 use std::time::Duration;
 use sui_sdk::SuiClientBuilder;
 use sui_squad_core::{
-    ai::ResponsesClient, commands::bot_commands::LoginState, config::Config,
-    conversation::ConversationCache,
+    ai::{FallbackBackend, IntentParser, LlmBackend, LlmConfig},
+    config::Config,
+    conversation::{
+        ConversationCache, ConversationPolicy, ConversationStore, InMemoryConversationStore,
+        SledConversationStore,
+    },
 };
-use teloxide::{dispatching::dialogue::InMemStorage, prelude::*, types::BotCommand};
+use teloxide::{prelude::*, types::BotCommand};
 use tracing_subscriber;
 
 #[tokio::main]
@@ -48,8 +59,14 @@ async fn main() -> Result<()> {
 
     let db = db::init_tree();
 
-    // Create conversation cache with 10-minute TTL
-    let conversation_cache = ConversationCache::new(Duration::from_secs(600));
+    let conversation_store: Arc<dyn ConversationStore> =
+        match env::var("CONVERSATION_STORE").as_deref() {
+            Ok("memory") => Arc::new(InMemoryConversationStore::default()),
+            _ => Arc::new(SledConversationStore::new(&db)?),
+        };
+
+    let conversation_cache =
+        ConversationCache::new(conversation_store, ConversationPolicy::from_env());
     let cache_for_cleanup = conversation_cache.clone();
 
     // Spawn cleanup task that runs every minute
@@ -57,17 +74,50 @@ async fn main() -> Result<()> {
         let mut interval = tokio::time::interval(Duration::from_secs(60));
         loop {
             interval.tick().await;
-            cache_for_cleanup.cleanup_expired().await;
-            println!("🧹 Cleaned up expired conversations");
+            match cache_for_cleanup.cleanup_expired().await {
+                Ok(0) => {}
+                Ok(removed) => println!("🧹 Cleaned up {} expired conversations", removed),
+                Err(e) => println!("❌ Failed to clean up conversations: {}", e),
+            }
+        }
+    });
+
+    // Old AI usage is only kept for the stats, prune it every hour
+    let db_for_usage = db.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(60 * 60));
+        loop {
+            interval.tick().await;
+            match usage::helpers::prune_usage(db_for_usage.clone()) {
+                Ok(0) => {}
+                Ok(removed) => println!("🧹 Pruned {} old AI usage records", removed),
+                Err(e) => println!("❌ Failed to prune AI usage: {}", e),
+            }
         }
     });
 
-    println!("✅ Conversation cache initialized with 10-minute TTL");
+    println!(
+        "✅ Conversation memory initialized with {}-minute TTL",
+        conversation_cache.policy().ttl.as_secs() / 60
+    );
 
     tracing_subscriber::fmt::init();
 
     let cfg = Config::from_env();
-    let responses_client = ResponsesClient::new(&cfg)?;
+    // Without a usable model, prompts are still understood by the rule-based parser
+    let llm: Arc<dyn LlmBackend> = match LlmConfig::from_env().and_then(|config| config.load()) {
+        Ok(backend) => {
+            println!("🤖 AI assistant using model {}", backend.model());
+            Arc::new(FallbackBackend::new(backend))
+        }
+        Err(e) => {
+            println!(
+                "⚠️ AI backend unavailable, understanding prompts with rules only: {}",
+                e
+            );
+            Arc::new(IntentParser::new())
+        }
+    };
     let bot = Bot::new(cfg.teloxide_token.clone());
 
     let commands = vec![
@@ -76,19 +126,45 @@ async fn main() -> Result<()> {
         BotCommand::new("p", "Send a prompt to the AI (short alias)."),
         BotCommand::new("promptexamples", "Show prompt examples."),
         BotCommand::new("fund", "Fund your account."),
+        BotCommand::new("wallet", "Show your account, zkLogin address and balances."),
+        BotCommand::new("limits", "Show or lower your spending limits."),
+        BotCommand::new("addressbook", "Manage your saved withdrawal addresses."),
+        BotCommand::new("send", "Send tokens step by step."),
+        BotCommand::new("withdraw", "Withdraw tokens step by step."),
+        BotCommand::new("cancel", "Cancel the current step-by-step flow."),
+        BotCommand::new("custody", "Withdraw with your own Google login."),
+        BotCommand::new("reset", "Start a fresh AI conversation."),
+        BotCommand::new("memory", "Show or set how long the AI remembers this chat."),
+        BotCommand::new("quota", "Show your remaining daily AI quota."),
         BotCommand::new("help", "Display this help message."),
     ];
 
     bot.set_my_commands(commands).await?;
 
+    if env::var("INTERNAL_SECRET").is_ok() {
+        tokio::spawn(notifications::helpers::poll_notifications(
+            bot.clone(),
+            services.clone(),
+            db.clone(),
+            squad_connect_client.clone(),
+        ));
+
+        println!("✅ Notification poller started");
+    } else {
+        println!("⚠️ INTERNAL_SECRET is not set, funding and payment notifications are disabled");
+    }
+
+    let dialogue_storage = DialogueStorage::open(&db)?;
+
     Dispatcher::builder(bot.clone(), handler_tree())
         .dependencies(dptree::deps![
-            responses_client.clone(),
-            InMemStorage::<LoginState>::new(),
+            llm,
+            dialogue_storage,
             squad_connect_client,
             services,
             conversation_cache,
-            db
+            db,
+            cfg
         ])
         .enable_ctrlc_handler()
         .build()
@@ -0,0 +1,10 @@
+use sui_squad_core::{config::Config, permissions::is_admin};
+use teloxide::types::Message;
+
+/// Only operators listed in `ADMIN_TELEGRAM_IDS` may use the admin command group.
+pub fn is_operator(msg: Message, cfg: Config) -> bool {
+    match msg.from {
+        Some(user) => is_admin(user.id.0 as i64, cfg.admin_ids()),
+        None => false,
+    }
+}
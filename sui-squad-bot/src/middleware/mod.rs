@@ -1,2 +1,3 @@
+pub mod admin;
 pub mod auth;
-pub mod user;
\ No newline at end of file
+pub mod user;
@@ -0,0 +1,235 @@
+use std::{env, time::Duration};
+
+use sled::Db;
+use squad_connect::client::squad_connect::SquadConnect;
+use sui_squad_core::helpers::dtos::{LoginPurpose, Notification, NotificationKind};
+use teloxide::{
+    Bot, RequestError,
+    prelude::*,
+    types::{InlineKeyboardButton, InlineKeyboardMarkup, ParseMode, UserId},
+    utils::html::escape,
+};
+
+use crate::{
+    bot_manage::handlers::login_url, credentials::helpers::find_username,
+    services::services::Services,
+};
+
+fn format_amount(amount: u64, coin_type: &str) -> String {
+    if coin_type.ends_with("::sui::SUI") {
+        format!("{} SUI", amount as f64 / 1_000_000_000f64)
+    } else {
+        let symbol = coin_type.rsplit("::").next().unwrap_or(coin_type);
+        format!("{} {}", amount, symbol)
+    }
+}
+
+fn format_sender(sender: &Option<String>, db: &Db) -> String {
+    let Some(sender) = sender else {
+        return "someone".to_string();
+    };
+
+    match sender.parse::<u64>() {
+        Ok(id) => match find_username(UserId(id), db.clone()) {
+            Some(username) => format!("@{}", escape(&username)),
+            None => format!("user {}", id),
+        },
+        Err(_) => format!("<code>{}</code>", escape(sender)),
+    }
+}
+
+fn format_notification(notification: &Notification, db: &Db) -> String {
+    let sui_explorer_url =
+        env::var("SUI_EXPLORER_URL").unwrap_or_else(|_| "https://suiscan.xyz".to_string());
+    let amount = format_amount(notification.amount, &notification.coin_type);
+    let recipient = |fallback: &str| escape(notification.recipient.as_deref().unwrap_or(fallback));
+    let link = format!(
+        "🔗 <a href=\"{}/txblock/{}\">View on Sui Explorer</a>",
+        sui_explorer_url, notification.digest
+    );
+
+    match notification.kind {
+        NotificationKind::FundingCompleted => format!(
+            "💰 <b>Deposit complete</b>\n\n{} was added to your Sui Squad account from {}.\n\n{}",
+            amount,
+            format_sender(&notification.sender, db),
+            link
+        ),
+        NotificationKind::PaymentReceived => format!(
+            "💸 <b>Payment received</b>\n\n{} sent you {}.\n\n{}",
+            format_sender(&notification.sender, db),
+            amount,
+            link
+        ),
+        NotificationKind::WithdrawalExecuted => format!(
+            "📤 <b>Withdrawal sent</b>\n\n{} was sent to <code>{}</code>.\n\n{}",
+            amount,
+            recipient("your wallet"),
+            link
+        ),
+        NotificationKind::DepositDetected => format!(
+            "📥 <b>Deposit detected</b>\n\n{} arrived at your zkLogin address <code>{}</code> but is not in your Sui Squad account yet.",
+            amount,
+            recipient("your wallet"),
+        ),
+        NotificationKind::SelfCustodyEnabled => format!(
+            "🔐 <b>Self-custody enabled</b>\n\n<code>{}</code> can now withdraw from your Sui Squad account with its own zkLogin signature.\n\n{}",
+            recipient("your zkLogin address"),
+            link
+        ),
+        NotificationKind::SessionExpiring => "🔑 <b>Login expiring</b>\n\nYour zkLogin session is about to expire. Sign in again before your next deposit.".to_string(),
+    }
+}
+
+/// Button opening the funding page, prefilled with the detected deposit if any.
+async fn fund_button(
+    squad_connect_client: &SquadConnect,
+    services: &Services,
+    notification: &Notification,
+    label: &str,
+    deposit: Option<(u64, String)>,
+) -> Option<InlineKeyboardMarkup> {
+    let mut squad_connect_client = squad_connect_client.clone();
+
+    let url = login_url(
+        &mut squad_connect_client,
+        services,
+        notification.telegram_id.clone(),
+        None,
+        LoginPurpose::Fund,
+        deposit,
+    )
+    .await
+    .map_err(|e| println!("❌ Failed to build the funding link: {}", e))
+    .ok()?;
+
+    Some(InlineKeyboardMarkup::new(vec![vec![
+        InlineKeyboardButton::url(label, url),
+    ]]))
+}
+
+/// DM the user; returns whether the notification can be removed from the server queue.
+async fn deliver(
+    bot: &Bot,
+    notification: &Notification,
+    db: &Db,
+    squad_connect_client: &SquadConnect,
+    services: &Services,
+) -> bool {
+    let Ok(chat_id) = notification.telegram_id.parse::<i64>() else {
+        println!(
+            "⚠️ Dropping notification {} for invalid Telegram id {}",
+            notification.id, notification.telegram_id
+        );
+        return true;
+    };
+
+    let markup = match notification.kind {
+        NotificationKind::DepositDetected => {
+            let deposit = Some((notification.amount, notification.coin_type.clone()));
+
+            fund_button(
+                squad_connect_client,
+                services,
+                notification,
+                "Move into Squad account",
+                deposit,
+            )
+            .await
+        }
+        // The session is past reuse, so the link comes with a fresh ephemeral key
+        NotificationKind::SessionExpiring => {
+            fund_button(
+                squad_connect_client,
+                services,
+                notification,
+                "Sign in again",
+                None,
+            )
+            .await
+        }
+        _ => None,
+    };
+
+    let mut text = format_notification(notification, db);
+
+    match notification.kind {
+        NotificationKind::DepositDetected => text.push_str(match markup {
+            Some(_) => "\n\nTap below to move it in.",
+            None => "\n\nUse /fund to move it into your account.",
+        }),
+        NotificationKind::SessionExpiring if markup.is_none() => {
+            text.push_str("\n\nUse /fund to sign in again.")
+        }
+        _ => {}
+    }
+
+    let mut request = bot
+        .send_message(ChatId(chat_id), text)
+        .parse_mode(ParseMode::Html);
+
+    if let Some(markup) = markup {
+        request = request.reply_markup(markup);
+    }
+
+    let result = request.await;
+
+    match result {
+        Ok(_) => true,
+        // The user blocked the bot or never started a chat with it; retrying will not help
+        Err(RequestError::Api(e)) => {
+            println!("⚠️ Could not notify {}: {}", notification.telegram_id, e);
+            true
+        }
+        Err(e) => {
+            println!(
+                "❌ Failed to send notification {}, will retry: {}",
+                notification.id, e
+            );
+            false
+        }
+    }
+}
+
+/// Poll the server for funding, payment and withdrawal notifications and DM the users.
+///
+/// Notifications are only acknowledged once delivered, so nothing is lost while the bot is down.
+pub async fn poll_notifications(
+    bot: Bot,
+    services: Services,
+    db: Db,
+    squad_connect_client: SquadConnect,
+) {
+    let poll_secs = env::var("NOTIFY_POLL_SECS")
+        .ok()
+        .and_then(|secs| secs.parse().ok())
+        .unwrap_or(5);
+
+    let mut interval = tokio::time::interval(Duration::from_secs(poll_secs));
+
+    loop {
+        interval.tick().await;
+
+        let notifications = match services.notifications().await {
+            Ok(notifications) => notifications,
+            Err(e) => {
+                println!("❌ Failed to fetch notifications: {}", e);
+                continue;
+            }
+        };
+
+        let mut delivered = Vec::new();
+
+        for notification in &notifications {
+            if deliver(&bot, notification, &db, &squad_connect_client, &services).await {
+                delivered.push(notification.id);
+            }
+        }
+
+        if !delivered.is_empty() {
+            if let Err(e) = services.ack_notifications(delivered).await {
+                println!("❌ Failed to acknowledge notifications: {}", e);
+            }
+        }
+    }
+}
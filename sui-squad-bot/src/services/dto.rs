@@ -4,6 +4,18 @@ pub enum Endpoints {
     User,
     Payment,
     Withdraw,
+    Limits,
+    AdminStats,
+    AdminUser(String),
+    AdminPause,
+    AdminChainPause,
+    AdminFreeze,
+    AdminRelayer,
+    Notifications,
+    NotificationsAck,
+    Sessions,
+    ZkLoginSession(String),
+    ZkLoginAddress(String),
 }
 
 impl fmt::Display for Endpoints {
@@ -15,6 +27,30 @@ impl fmt::Display for Endpoints {
             &Endpoints::User => write!(f, "{}/user", backend_url),
             &Endpoints::Payment => write!(f, "{}/payment", backend_url),
             &Endpoints::Withdraw => write!(f, "{}/withdraw", backend_url),
+            &Endpoints::Limits => write!(f, "{}/limits", backend_url),
+            &Endpoints::AdminStats => write!(f, "{}/admin/stats", backend_url),
+            &Endpoints::AdminUser(ref telegram_id) => {
+                write!(f, "{}/admin/user/{}", backend_url, telegram_id)
+            }
+            &Endpoints::AdminPause => write!(f, "{}/admin/pause", backend_url),
+            &Endpoints::AdminChainPause => write!(f, "{}/admin/chain-pause", backend_url),
+            &Endpoints::AdminFreeze => write!(f, "{}/admin/freeze", backend_url),
+            &Endpoints::AdminRelayer => write!(f, "{}/admin/relayer", backend_url),
+            &Endpoints::Notifications => write!(f, "{}/internal/notifications", backend_url),
+            &Endpoints::NotificationsAck => {
+                write!(f, "{}/internal/notifications/ack", backend_url)
+            }
+            &Endpoints::Sessions => write!(f, "{}/internal/sessions", backend_url),
+            &Endpoints::ZkLoginSession(ref telegram_id) => {
+                write!(f, "{}/internal/zklogin/{}", backend_url, telegram_id)
+            }
+            &Endpoints::ZkLoginAddress(ref telegram_id) => {
+                write!(
+                    f,
+                    "{}/internal/zklogin/{}/address",
+                    backend_url, telegram_id
+                )
+            }
         }
     }
 }
@@ -1,10 +1,23 @@
+use std::env;
+
 use anyhow::{Result, anyhow};
-use reqwest::Client;
-use sui_squad_core::helpers::dtos::{DigestResponse, PaymentRequest, WithdrawRequest};
+use reqwest::{Client, StatusCode};
+use serde::de::DeserializeOwned;
+use sui_squad_core::helpers::dtos::{
+    AdminStats, AdminUserInfo, ChainPauseResponse, DigestResponse, FreezeRequest, FreezeResponse,
+    LimitsRequest, LimitsResponse, Notification, NotificationAck, PauseRequest, PauseResponse,
+    PaymentRequest, RelayerRequest, RelayerResponse, SessionRequest, SessionResponse,
+    WithdrawRequest, WithdrawResponse, ZkLoginAddressInfo, ZkLoginSessionInfo,
+};
 use tracing::{debug, error, info, warn};
 
 use super::dto::Endpoints;
 
+/// Shared secret authenticating the bot on the server's internal endpoints.
+fn internal_secret() -> Result<String> {
+    env::var("INTERNAL_SECRET").map_err(|_| anyhow!("INTERNAL_SECRET is not set"))
+}
+
 #[derive(Clone)]
 pub struct Services {
     client: Client,
@@ -178,7 +191,7 @@ impl Services {
         &self,
         token: String,
         request: WithdrawRequest,
-    ) -> Result<DigestResponse> {
+    ) -> Result<WithdrawResponse> {
         let url = Endpoints::Withdraw.to_string();
         debug!("🌐 Making withdraw service request to: {}", url);
         debug!(
@@ -206,7 +219,7 @@ impl Services {
 
                 if resp.status().is_success() {
                     info!("✅ Withdraw service call successful - Status: {}", status);
-                    let digest = resp.json::<DigestResponse>().await;
+                    let digest = resp.json::<WithdrawResponse>().await;
 
                     if digest.is_err() {
                         error!("❌ Failed to parse withdraw response: {:?}", digest.err());
@@ -243,4 +256,332 @@ impl Services {
             }
         }
     }
+
+    pub async fn get_limits(&self, token: String, coin_type: String) -> Result<LimitsResponse> {
+        let url = Endpoints::Limits.to_string();
+        debug!("🌐 Making get limits service request to: {}", url);
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .query(&[("coin_type", coin_type)])
+            .send()
+            .await;
+
+        self.limits_response(url, response).await
+    }
+
+    pub async fn set_limits(
+        &self,
+        token: String,
+        request: LimitsRequest,
+    ) -> Result<LimitsResponse> {
+        let url = Endpoints::Limits.to_string();
+        debug!("🌐 Making set limits service request to: {}", url);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .json(&request)
+            .send()
+            .await;
+
+        self.limits_response(url, response).await
+    }
+
+    async fn limits_response(
+        &self,
+        url: String,
+        response: reqwest::Result<reqwest::Response>,
+    ) -> Result<LimitsResponse> {
+        match response {
+            Ok(resp) => {
+                let status = resp.status();
+                debug!("📡 Server response status: {}", status);
+
+                if resp.status().is_success() {
+                    info!("✅ Limits service call successful - Status: {}", status);
+                    let limits = resp.json::<LimitsResponse>().await;
+
+                    if limits.is_err() {
+                        error!("❌ Failed to parse limits response: {:?}", limits.err());
+                        Err(anyhow!("Failed to parse limits response"))
+                    } else {
+                        Ok(limits.unwrap())
+                    }
+                } else {
+                    let error_body = resp
+                        .text()
+                        .await
+                        .unwrap_or_else(|_| "Unable to read error body".to_string());
+
+                    error!("❌ Server responded with error status: {}", status);
+                    error!("❌ Server error response body: {}", error_body);
+                    error!("❌ Request URL: {}", url);
+
+                    Err(anyhow!(
+                        "Limits service failed with status {}: {}",
+                        status,
+                        error_body
+                    ))
+                }
+            }
+            Err(network_error) => {
+                error!(
+                    "❌ Network error during limits service call: {:?}",
+                    network_error
+                );
+                error!("❌ Failed to connect to: {}", url);
+
+                Err(anyhow!("Network error: {}", network_error))
+            }
+        }
+    }
+
+    pub async fn admin_stats(&self, token: String) -> Result<AdminStats> {
+        let url = Endpoints::AdminStats.to_string();
+        debug!("🌐 Making admin stats service request to: {}", url);
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .send()
+            .await;
+
+        self.admin_response(url, response).await
+    }
+
+    pub async fn admin_user(&self, token: String, telegram_id: String) -> Result<AdminUserInfo> {
+        let url = Endpoints::AdminUser(telegram_id).to_string();
+        debug!("🌐 Making admin user service request to: {}", url);
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .send()
+            .await;
+
+        self.admin_response(url, response).await
+    }
+
+    pub async fn admin_pause(&self, token: String, paused: bool) -> Result<PauseResponse> {
+        let url = Endpoints::AdminPause.to_string();
+        debug!("🌐 Making admin pause service request to: {}", url);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .json(&PauseRequest { paused })
+            .send()
+            .await;
+
+        self.admin_response(url, response).await
+    }
+
+    pub async fn admin_chain_pause(
+        &self,
+        token: String,
+        paused: bool,
+    ) -> Result<ChainPauseResponse> {
+        let url = Endpoints::AdminChainPause.to_string();
+        debug!("🌐 Making admin chain pause service request to: {}", url);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .json(&PauseRequest { paused })
+            .send()
+            .await;
+
+        self.admin_response(url, response).await
+    }
+
+    pub async fn admin_freeze(
+        &self,
+        token: String,
+        telegram_id: String,
+        frozen: bool,
+    ) -> Result<FreezeResponse> {
+        let url = Endpoints::AdminFreeze.to_string();
+        debug!("🌐 Making admin freeze service request to: {}", url);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .json(&FreezeRequest {
+                telegram_id,
+                frozen,
+            })
+            .send()
+            .await;
+
+        self.admin_response(url, response).await
+    }
+
+    pub async fn admin_relayer(
+        &self,
+        token: String,
+        address: String,
+        authorized: bool,
+    ) -> Result<RelayerResponse> {
+        let url = Endpoints::AdminRelayer.to_string();
+        debug!("🌐 Making admin relayer service request to: {}", url);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .json(&RelayerRequest {
+                address,
+                authorized,
+            })
+            .send()
+            .await;
+
+        self.admin_response(url, response).await
+    }
+
+    pub async fn notifications(&self) -> Result<Vec<Notification>> {
+        let url = Endpoints::Notifications.to_string();
+        debug!("🌐 Making notifications service request to: {}", url);
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", internal_secret()?))
+            .send()
+            .await;
+
+        self.admin_response(url, response).await
+    }
+
+    pub async fn ack_notifications(&self, ids: Vec<u64>) -> Result<NotificationAck> {
+        let url = Endpoints::NotificationsAck.to_string();
+        debug!("🌐 Making notifications ack service request to: {}", url);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", internal_secret()?))
+            .json(&NotificationAck { ids })
+            .send()
+            .await;
+
+        self.admin_response(url, response).await
+    }
+
+    pub async fn create_session(&self, session_request: SessionRequest) -> Result<SessionResponse> {
+        let url = Endpoints::Sessions.to_string();
+        debug!("🌐 Making session service request to: {}", url);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", internal_secret()?))
+            .json(&session_request)
+            .send()
+            .await;
+
+        self.admin_response(url, response).await
+    }
+
+    /// The user's zkLogin session if the server can still reuse it, `None` when a new key is needed.
+    pub async fn zk_login_session(
+        &self,
+        telegram_id: String,
+    ) -> Result<Option<ZkLoginSessionInfo>> {
+        let url = Endpoints::ZkLoginSession(telegram_id).to_string();
+        debug!("🌐 Making zkLogin session service request to: {}", url);
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", internal_secret()?))
+            .send()
+            .await;
+
+        if let Ok(resp) = &response {
+            if resp.status() == StatusCode::NOT_FOUND {
+                return Ok(None);
+            }
+        }
+
+        self.admin_response(url, response).await.map(Some)
+    }
+
+    /// The zkLogin address the user last logged in with, `None` if they never completed a login.
+    pub async fn zk_login_address(&self, telegram_id: String) -> Result<Option<String>> {
+        let url = Endpoints::ZkLoginAddress(telegram_id).to_string();
+        debug!("🌐 Making zkLogin address service request to: {}", url);
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", internal_secret()?))
+            .send()
+            .await;
+
+        if let Ok(resp) = &response {
+            if resp.status() == StatusCode::NOT_FOUND {
+                return Ok(None);
+            }
+        }
+
+        self.admin_response::<ZkLoginAddressInfo>(url, response)
+            .await
+            .map(|info| Some(info.address))
+    }
+
+    async fn admin_response<T: DeserializeOwned>(
+        &self,
+        url: String,
+        response: reqwest::Result<reqwest::Response>,
+    ) -> Result<T> {
+        match response {
+            Ok(resp) => {
+                let status = resp.status();
+                debug!("📡 Server response status: {}", status);
+
+                if resp.status().is_success() {
+                    info!("✅ Admin service call successful - Status: {}", status);
+
+                    resp.json::<T>().await.map_err(|e| {
+                        error!("❌ Failed to parse admin response: {:?}", e);
+                        anyhow!("Failed to parse admin response")
+                    })
+                } else {
+                    let error_body = resp
+                        .text()
+                        .await
+                        .unwrap_or_else(|_| "Unable to read error body".to_string());
+
+                    error!("❌ Server responded with error status: {}", status);
+                    error!("❌ Server error response body: {}", error_body);
+                    error!("❌ Request URL: {}", url);
+
+                    Err(anyhow!(
+                        "Admin service failed with status {}: {}",
+                        status,
+                        error_body
+                    ))
+                }
+            }
+            Err(network_error) => {
+                error!(
+                    "❌ Network error during admin service call: {:?}",
+                    network_error
+                );
+                error!("❌ Failed to connect to: {}", url);
+
+                Err(anyhow!("Network error: {}", network_error))
+            }
+        }
+    }
 }
@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::bot_manage::handlers::handle_get_balance_tool;
+
+use super::{Tool, ToolContext};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetBalanceArgs {}
+
+pub struct GetBalanceTool;
+
+#[async_trait]
+impl Tool for GetBalanceTool {
+    type Args = GetBalanceArgs;
+
+    fn name(&self) -> &'static str {
+        "get_balance"
+    }
+
+    fn description(&self) -> &'static str {
+        "Get the user's balance for all tokens or a specific token"
+    }
+
+    fn progress(&self) -> &'static str {
+        "🔍 Checking balance…"
+    }
+
+    async fn execute(&self, _args: GetBalanceArgs, ctx: &ToolContext) -> String {
+        handle_get_balance_tool(ctx.user_id, ctx.squad_connect_client.clone()).await
+    }
+}
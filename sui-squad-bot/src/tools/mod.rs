@@ -1 +1,57 @@
-pub mod schema;
+pub mod balance;
+pub mod registry;
+pub mod send;
+pub mod wallet;
+pub mod withdraw;
+
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use sled::Db;
+use squad_connect::client::squad_connect::SquadConnect;
+use teloxide::types::UserId;
+
+use crate::services::services::Services;
+
+pub use registry::ToolRegistry;
+
+/// Everything a tool may need about the prompt that triggered it.
+#[derive(Clone)]
+pub struct ToolContext {
+    pub user_id: UserId,
+    pub username: Option<String>,
+    pub services: Services,
+    pub squad_connect_client: SquadConnect,
+    pub db: Db,
+}
+
+/// A function the model can call, with arguments checked against `Args` before it runs.
+#[async_trait]
+pub trait Tool: Send + Sync + 'static {
+    /// Arguments as the model sends them; their JSON schema is what the model is shown.
+    type Args: DeserializeOwned + JsonSchema + Send + 'static;
+
+    fn name(&self) -> &'static str;
+
+    fn description(&self) -> &'static str;
+
+    /// Shown to the user while the tool runs.
+    fn progress(&self) -> &'static str {
+        "⏳ Working on it…"
+    }
+
+    /// Run the tool; the returned text is handed back to the model, `Error: ...` on failure.
+    async fn execute(&self, args: Self::Args, ctx: &ToolContext) -> String;
+}
+
+/// Convert a SUI amount chosen by the model to MIST.
+pub fn sui_to_mist(amount: f64) -> Result<u64, String> {
+    if !amount.is_finite() || amount <= 0.0 {
+        return Err(format!(
+            "Error: amount must be a positive number, got {}",
+            amount
+        ));
+    }
+
+    Ok((amount * 1_000_000_000f64) as u64)
+}
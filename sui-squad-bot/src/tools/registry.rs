@@ -0,0 +1,264 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use schemars::r#gen::SchemaSettings;
+use serde_json::Value;
+use sui_squad_core::ai::{ToolCall, ToolDefinition};
+
+use super::{
+    Tool, ToolContext, balance::GetBalanceTool, send::SendTool, wallet::GetWalletTool,
+    withdraw::WithdrawTool,
+};
+
+/// JSON schema of the arguments, inlined and without the draft metadata models do not expect.
+fn parameters_schema<T: Tool>() -> Value {
+    let generator = SchemaSettings::draft07()
+        .with(|settings| {
+            settings.inline_subschemas = true;
+            settings.meta_schema = None;
+        })
+        .into_generator();
+
+    let mut schema = serde_json::to_value(generator.into_root_schema_for::<T::Args>())
+        .unwrap_or_else(|_| serde_json::json!({ "type": "object" }));
+
+    if let Some(object) = schema.as_object_mut() {
+        object.remove("title");
+        object.remove("definitions");
+        object
+            .entry("properties")
+            .or_insert_with(|| serde_json::json!({}));
+    }
+
+    schema
+}
+
+/// A tool call whose arguments passed validation, ready to run.
+#[async_trait]
+trait BoundCall: Send {
+    async fn run(self: Box<Self>, ctx: &ToolContext) -> String;
+}
+
+struct Bound<T: Tool> {
+    tool: Arc<T>,
+    args: T::Args,
+}
+
+#[async_trait]
+impl<T: Tool> BoundCall for Bound<T> {
+    async fn run(self: Box<Self>, ctx: &ToolContext) -> String {
+        self.tool.execute(self.args, ctx).await
+    }
+}
+
+/// Object-safe view of a [`Tool`], taking raw JSON arguments.
+trait RegisteredTool: Send + Sync {
+    fn tool_name(&self) -> &'static str;
+
+    fn progress(&self) -> &'static str;
+
+    fn definition(&self) -> ToolDefinition;
+
+    /// Check `arguments` against the tool's schema; the error is meant for the model.
+    fn bind(self: Arc<Self>, arguments: &str) -> Result<Box<dyn BoundCall>, String>;
+}
+
+impl<T: Tool> RegisteredTool for T {
+    fn tool_name(&self) -> &'static str {
+        self.name()
+    }
+
+    fn progress(&self) -> &'static str {
+        Tool::progress(self)
+    }
+
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: self.name().to_string(),
+            description: self.description().to_string(),
+            parameters: Some(parameters_schema::<T>()),
+        }
+    }
+
+    fn bind(self: Arc<Self>, arguments: &str) -> Result<Box<dyn BoundCall>, String> {
+        // Tools without arguments are sometimes called with an empty string
+        let arguments = if arguments.trim().is_empty() {
+            "{}"
+        } else {
+            arguments
+        };
+
+        match serde_json::from_str::<T::Args>(arguments) {
+            Ok(args) => Ok(Box::new(Bound { tool: self, args })),
+            Err(e) => Err(format!(
+                "Error: invalid arguments for {}: {}. Call it again with arguments matching its schema.",
+                self.name(),
+                e
+            )),
+        }
+    }
+}
+
+/// The tools offered to the model, by name.
+#[derive(Clone, Default)]
+pub struct ToolRegistry {
+    tools: Vec<Arc<dyn RegisteredTool>>,
+}
+
+impl ToolRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The wallet tools available to `/prompt`.
+    pub fn wallet() -> Self {
+        ToolRegistry::new()
+            .register(GetWalletTool)
+            .register(GetBalanceTool)
+            .register(WithdrawTool)
+            .register(SendTool)
+    }
+
+    pub fn register<T: Tool>(mut self, tool: T) -> Self {
+        self.tools.push(Arc::new(tool));
+        self
+    }
+
+    pub fn definitions(&self) -> Vec<ToolDefinition> {
+        self.tools.iter().map(|tool| tool.definition()).collect()
+    }
+
+    /// What to show the user while `name` runs.
+    pub fn progress(&self, name: &str) -> &'static str {
+        self.tools
+            .iter()
+            .find(|tool| tool.tool_name() == name)
+            .map(|tool| tool.progress())
+            .unwrap_or("⏳ Working on it…")
+    }
+
+    /// Find the tool the model asked for and check its arguments, without running it.
+    fn bind(&self, tool_call: &ToolCall) -> Result<Box<dyn BoundCall>, String> {
+        self.tools
+            .iter()
+            .find(|tool| tool.tool_name() == tool_call.name)
+            .ok_or_else(|| format!("Error: unknown function {}", tool_call.name))?
+            .clone()
+            .bind(&tool_call.arguments)
+    }
+
+    /// Run the tool the model asked for; unknown tools and bad arguments are reported back to it.
+    pub async fn dispatch(&self, tool_call: &ToolCall, ctx: &ToolContext) -> String {
+        match self.bind(tool_call) {
+            Ok(call) => call.run(ctx).await,
+            Err(e) => e,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool_call(name: &str, arguments: &str) -> ToolCall {
+        ToolCall {
+            name: name.to_string(),
+            arguments: arguments.to_string(),
+            call_id: "call-1".to_string(),
+        }
+    }
+
+    fn bind_error(name: &str, arguments: &str) -> String {
+        match ToolRegistry::wallet().bind(&tool_call(name, arguments)) {
+            Ok(_) => panic!("{} was accepted with {}", name, arguments),
+            Err(e) => e,
+        }
+    }
+
+    #[test]
+    fn unknown_tools_are_reported() {
+        assert_eq!(
+            bind_error("transfer_all", "{}"),
+            "Error: unknown function transfer_all"
+        );
+    }
+
+    #[test]
+    fn malformed_arguments_are_reported() {
+        let error = bind_error("send", r#"{"targets": ["bob"], "amount": "#);
+
+        assert!(error.starts_with("Error: invalid arguments for send:"));
+        assert!(error.ends_with("Call it again with arguments matching its schema."));
+    }
+
+    #[test]
+    fn missing_and_mistyped_arguments_are_reported() {
+        assert!(bind_error("send", r#"{"targets": ["bob"]}"#).contains("amount"));
+        assert!(
+            bind_error("send", r#"{"targets": "bob", "amount": 2}"#)
+                .starts_with("Error: invalid arguments for send:")
+        );
+    }
+
+    #[test]
+    fn extra_arguments_are_reported() {
+        let error = bind_error(
+            "withdraw",
+            r#"{"amount": 1, "address": "alice.sui", "coin": "USDC"}"#,
+        );
+
+        assert!(error.starts_with("Error: invalid arguments for withdraw:"));
+        assert!(error.contains("coin"));
+
+        assert!(bind_error("get_wallet", r#"{"user": "bob"}"#).contains("user"));
+    }
+
+    #[test]
+    fn tools_without_arguments_accept_an_empty_string() {
+        let registry = ToolRegistry::wallet();
+
+        for arguments in ["", "  ", "{}"] {
+            assert!(registry.bind(&tool_call("get_wallet", arguments)).is_ok());
+            assert!(registry.bind(&tool_call("get_balance", arguments)).is_ok());
+        }
+    }
+
+    #[test]
+    fn valid_arguments_are_accepted() {
+        let registry = ToolRegistry::wallet();
+
+        assert!(
+            registry
+                .bind(&tool_call("send", r#"{"targets": ["bob"], "amount": 1.5}"#))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn definitions_are_object_schemas() {
+        let definitions = ToolRegistry::wallet().definitions();
+
+        assert_eq!(
+            definitions
+                .iter()
+                .map(|definition| definition.name.as_str())
+                .collect::<Vec<_>>(),
+            ["get_wallet", "get_balance", "withdraw", "send"]
+        );
+
+        for definition in definitions {
+            let parameters = definition.parameters.unwrap();
+
+            assert_eq!(parameters["type"], "object", "{}", definition.name);
+            assert!(parameters["properties"].is_object(), "{}", definition.name);
+            assert!(parameters.get("$schema").is_none(), "{}", definition.name);
+            assert!(parameters.get("title").is_none(), "{}", definition.name);
+        }
+
+        let send = &ToolRegistry::wallet().definitions()[3];
+        let properties = send.parameters.as_ref().unwrap()["properties"].clone();
+
+        assert!(properties["targets"].is_object());
+        assert!(properties["amount"].is_object());
+    }
+}
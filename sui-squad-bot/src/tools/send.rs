@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::bot_manage::handlers::send_payment;
+
+use super::{Tool, ToolContext, sui_to_mist};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SendArgs {
+    /// telegram usernames without @ for example ['mytestuser', 'mytestuser2']
+    pub targets: Vec<String>,
+    /// amount of SUI to send (e.g., 1.5 for 1.5 SUI)
+    pub amount: f64,
+}
+
+pub struct SendTool;
+
+#[async_trait]
+impl Tool for SendTool {
+    type Args = SendArgs;
+
+    fn name(&self) -> &'static str {
+        "send"
+    }
+
+    fn description(&self) -> &'static str {
+        "Send a specified amount of a coin to a Telegram ID or everyone in the group"
+    }
+
+    fn progress(&self) -> &'static str {
+        "💸 Sending payment…"
+    }
+
+    async fn execute(&self, args: SendArgs, ctx: &ToolContext) -> String {
+        if args.targets.is_empty() {
+            return "Error: Target is required".to_string();
+        }
+
+        let amount = match sui_to_mist(args.amount) {
+            Ok(amount) => amount,
+            Err(e) => return e,
+        };
+
+        send_payment(
+            ctx.username.clone(),
+            args.targets,
+            amount,
+            ctx.services.clone(),
+            ctx.db.clone(),
+        )
+        .await
+        .unwrap_or_else(|e| format!("Error: {}", e))
+    }
+}
@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::wallet::helpers::wallet_info;
+
+use super::{Tool, ToolContext};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetWalletArgs {}
+
+pub struct GetWalletTool;
+
+#[async_trait]
+impl Tool for GetWalletTool {
+    type Args = GetWalletArgs;
+
+    fn name(&self) -> &'static str {
+        "get_wallet"
+    }
+
+    fn description(&self) -> &'static str {
+        "Get the user's Squad account id, zkLogin address, balances of every coin and explorer links"
+    }
+
+    fn progress(&self) -> &'static str {
+        "👛 Looking up your wallet…"
+    }
+
+    async fn execute(&self, _args: GetWalletArgs, ctx: &ToolContext) -> String {
+        match wallet_info(ctx.user_id, &ctx.services, &ctx.squad_connect_client).await {
+            Ok(info) => info,
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+}
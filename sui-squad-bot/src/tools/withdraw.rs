@@ -0,0 +1,52 @@
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::bot_manage::handlers::withdraw_funds;
+
+use super::{Tool, ToolContext, sui_to_mist};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct WithdrawArgs {
+    /// amount of SUI to withdraw (e.g., 1.5 for 1.5 SUI)
+    pub amount: f64,
+    /// Sui address (0x followed by 64 hex characters), a SuiNS name (e.g. 'alice.sui') or a label from the user's address book (e.g. 'cold') to withdraw to
+    pub address: String,
+}
+
+pub struct WithdrawTool;
+
+#[async_trait]
+impl Tool for WithdrawTool {
+    type Args = WithdrawArgs;
+
+    fn name(&self) -> &'static str {
+        "withdraw"
+    }
+
+    fn description(&self) -> &'static str {
+        "Withdraw a specified amount of a coin from the user's account"
+    }
+
+    fn progress(&self) -> &'static str {
+        "🏧 Withdrawing…"
+    }
+
+    async fn execute(&self, args: WithdrawArgs, ctx: &ToolContext) -> String {
+        let amount = match sui_to_mist(args.amount) {
+            Ok(amount) => amount,
+            Err(e) => return e,
+        };
+
+        withdraw_funds(
+            ctx.username.clone(),
+            amount,
+            &args.address,
+            ctx.services.clone(),
+            ctx.db.clone(),
+        )
+        .await
+        .unwrap_or_else(|e| format!("Error: {}", e))
+    }
+}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+/// AI tokens and prompts counted for one user or chat on one day.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct UsageRecord {
+    pub tokens: u64,
+    pub requests: u64,
+}
+
+/// Daily quota set by an operator, replacing the configured default.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct StoredQuota {
+    /// `None` for unlimited.
+    pub tokens: Option<u64>,
+}
+
+/// Today's usage of a user or chat against its daily quota.
+#[derive(Debug, Clone, Copy)]
+pub struct QuotaStatus {
+    pub used: u64,
+    /// `None` for unlimited.
+    pub limit: Option<u64>,
+}
+
+impl QuotaStatus {
+    pub fn remaining(&self) -> Option<u64> {
+        self.limit.map(|limit| limit.saturating_sub(self.used))
+    }
+
+    pub fn exceeded(&self) -> bool {
+        self.limit.is_some_and(|limit| self.used >= limit)
+    }
+}
+
+/// AI usage of all users and chats, for the operator stats.
+#[derive(Debug, Default)]
+pub struct UsageSummary {
+    pub today: UsageRecord,
+    pub week_tokens: u64,
+    pub top_users: Vec<(String, UsageRecord)>,
+    pub top_chats: Vec<(String, UsageRecord)>,
+}
@@ -0,0 +1,386 @@
+use std::{collections::HashMap, env};
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use sled::Db;
+use sui_squad_core::{
+    ai::TokenUsage,
+    commands::admin::{QuotaOverride, QuotaScope},
+};
+
+use super::dto::{QuotaStatus, StoredQuota, UsageRecord, UsageSummary};
+
+const USAGE_TREE: &str = "ai_usage";
+const QUOTA_TREE: &str = "ai_quotas";
+const DEFAULT_USER_TOKENS: u64 = 50_000;
+const DEFAULT_CHAT_TOKENS: u64 = 200_000;
+const DEFAULT_UNREPORTED_CALL_TOKENS: u64 = 2_000;
+/// Days of usage kept for the stats; older records are pruned.
+const RETENTION_DAYS: i64 = 30;
+
+fn scope_name(scope: QuotaScope) -> &'static str {
+    match scope {
+        QuotaScope::User => "user",
+        QuotaScope::Chat => "chat",
+    }
+}
+
+/// Quotas reset at 00:00 UTC.
+fn day(days_ago: i64) -> String {
+    (Utc::now() - Duration::days(days_ago))
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+/// Keys are `<day>:<scope>:<id>` so they sort by day.
+fn usage_key(day: &str, scope: QuotaScope, id: i64) -> String {
+    format!("{}:{}:{}", day, scope_name(scope), id)
+}
+
+fn quota_key(scope: QuotaScope, id: i64) -> String {
+    format!("{}:{}", scope_name(scope), id)
+}
+
+/// `AI_DAILY_USER_TOKENS` or `AI_DAILY_CHAT_TOKENS`, where 0 means unlimited.
+pub fn default_quota(scope: QuotaScope) -> Option<u64> {
+    let (name, default) = match scope {
+        QuotaScope::User => ("AI_DAILY_USER_TOKENS", DEFAULT_USER_TOKENS),
+        QuotaScope::Chat => ("AI_DAILY_CHAT_TOKENS", DEFAULT_CHAT_TOKENS),
+    };
+
+    let tokens = env::var(name)
+        .ok()
+        .and_then(|tokens| tokens.trim().parse::<u64>().ok())
+        .unwrap_or(default);
+
+    (tokens > 0).then_some(tokens)
+}
+
+/// `AI_UNREPORTED_CALL_TOKENS`, counted for each call to a model that reports no usage.
+pub fn unreported_call_tokens() -> u64 {
+    env::var("AI_UNREPORTED_CALL_TOKENS")
+        .ok()
+        .and_then(|tokens| tokens.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_UNREPORTED_CALL_TOKENS)
+}
+
+/// The tokens reported by the backend, or the configured estimate so quotas still apply.
+pub fn reported_or_estimated(usage: Option<TokenUsage>) -> TokenUsage {
+    usage.unwrap_or(TokenUsage {
+        input_tokens: unreported_call_tokens(),
+        output_tokens: 0,
+    })
+}
+
+fn get_usage(scope: QuotaScope, id: i64, db: Db) -> Result<UsageRecord> {
+    let tree = db.open_tree(USAGE_TREE)?;
+
+    match tree.get(usage_key(&day(0), scope, id))? {
+        Some(bytes) => Ok(serde_json::from_slice(&bytes)?),
+        None => Ok(UsageRecord::default()),
+    }
+}
+
+/// Add one prompt and its tokens to today's usage of the user and, outside private chats, the chat.
+pub fn record_usage(user_id: i64, chat_id: Option<i64>, usage: TokenUsage, db: Db) -> Result<()> {
+    let tree = db.open_tree(USAGE_TREE)?;
+    let today = day(0);
+
+    let scopes = std::iter::once((QuotaScope::User, user_id))
+        .chain(chat_id.map(|chat_id| (QuotaScope::Chat, chat_id)));
+
+    for (scope, id) in scopes {
+        tree.update_and_fetch(usage_key(&today, scope, id), |bytes| {
+            let mut record: UsageRecord = bytes
+                .and_then(|bytes| serde_json::from_slice(bytes).ok())
+                .unwrap_or_default();
+
+            record.tokens += usage.total();
+            record.requests += 1;
+
+            serde_json::to_vec(&record).ok()
+        })?;
+    }
+
+    Ok(())
+}
+
+/// The operator override when set, the configured default otherwise.
+pub fn quota_limit(scope: QuotaScope, id: i64, db: Db) -> Result<Option<u64>> {
+    let tree = db.open_tree(QUOTA_TREE)?;
+
+    match tree.get(quota_key(scope, id))? {
+        Some(bytes) => Ok(serde_json::from_slice::<StoredQuota>(&bytes)?.tokens),
+        None => Ok(default_quota(scope)),
+    }
+}
+
+pub fn quota_status(scope: QuotaScope, id: i64, db: Db) -> Result<QuotaStatus> {
+    Ok(QuotaStatus {
+        used: get_usage(scope, id, db.clone())?.tokens,
+        limit: quota_limit(scope, id, db)?,
+    })
+}
+
+/// The first exhausted quota among the user's and, outside private chats, the chat's.
+pub fn exceeded_quota(
+    user_id: i64,
+    chat_id: Option<i64>,
+    db: Db,
+) -> Result<Option<(QuotaScope, QuotaStatus)>> {
+    let scopes = std::iter::once((QuotaScope::User, user_id))
+        .chain(chat_id.map(|chat_id| (QuotaScope::Chat, chat_id)));
+
+    for (scope, id) in scopes {
+        let status = quota_status(scope, id, db.clone())?;
+
+        if status.exceeded() {
+            return Ok(Some((scope, status)));
+        }
+    }
+
+    Ok(None)
+}
+
+pub fn set_quota_override(scope: QuotaScope, id: i64, quota: QuotaOverride, db: Db) -> Result<()> {
+    let tree = db.open_tree(QUOTA_TREE)?;
+    let key = quota_key(scope, id);
+
+    let tokens = match quota {
+        QuotaOverride::Tokens(tokens) => Some(tokens),
+        QuotaOverride::Unlimited => None,
+        QuotaOverride::Default => {
+            tree.remove(key)?;
+            return Ok(());
+        }
+    };
+
+    tree.insert(key, serde_json::to_vec(&StoredQuota { tokens })?)?;
+
+    Ok(())
+}
+
+fn top(usage: HashMap<String, UsageRecord>, count: usize) -> Vec<(String, UsageRecord)> {
+    let mut usage: Vec<_> = usage.into_iter().collect();
+    usage.sort_by(|a, b| b.1.tokens.cmp(&a.1.tokens));
+    usage.truncate(count);
+    usage
+}
+
+/// Today's usage with its `count` heaviest users and chats, and the tokens of the last 7 days.
+pub fn usage_summary(count: usize, db: Db) -> Result<UsageSummary> {
+    let tree = db.open_tree(USAGE_TREE)?;
+    let today = day(0);
+    let week_start = day(6);
+
+    let mut summary = UsageSummary::default();
+    let mut users = HashMap::new();
+    let mut chats = HashMap::new();
+
+    for entry in tree.range(week_start.as_str()..) {
+        let (key, bytes) = entry?;
+        let key = String::from_utf8_lossy(&key);
+
+        let mut parts = key.splitn(3, ':');
+        let (Some(day), Some(scope), Some(id)) = (parts.next(), parts.next(), parts.next()) else {
+            continue;
+        };
+
+        let Ok(record) = serde_json::from_slice::<UsageRecord>(&bytes) else {
+            continue;
+        };
+
+        match scope {
+            // Chat usage is also counted in its users' usage
+            "user" => {
+                summary.week_tokens += record.tokens;
+
+                if day == today {
+                    summary.today.tokens += record.tokens;
+                    summary.today.requests += record.requests;
+                    users.insert(id.to_string(), record);
+                }
+            }
+            "chat" if day == today => {
+                chats.insert(id.to_string(), record);
+            }
+            _ => {}
+        }
+    }
+
+    summary.top_users = top(users, count);
+    summary.top_chats = top(chats, count);
+
+    Ok(summary)
+}
+
+/// Remove the usage older than the retention period, returning how many records were removed.
+pub fn prune_usage(db: Db) -> Result<usize> {
+    let tree = db.open_tree(USAGE_TREE)?;
+    let cutoff = day(RETENTION_DAYS);
+    let mut removed = 0;
+
+    for key in tree.range(..cutoff.as_str()).keys() {
+        tree.remove(key?)?;
+        removed += 1;
+    }
+
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temporary_db() -> Db {
+        sled::Config::new().temporary(true).open().unwrap()
+    }
+
+    fn tokens(input_tokens: u64, output_tokens: u64) -> TokenUsage {
+        TokenUsage {
+            input_tokens,
+            output_tokens,
+        }
+    }
+
+    #[test]
+    fn records_tokens_and_requests_for_user_and_chat() {
+        let db = temporary_db();
+
+        record_usage(1, Some(-100), tokens(100, 50), db.clone()).unwrap();
+        record_usage(1, None, tokens(10, 0), db.clone()).unwrap();
+
+        let user = get_usage(QuotaScope::User, 1, db.clone()).unwrap();
+        assert_eq!((user.tokens, user.requests), (160, 2));
+
+        let chat = get_usage(QuotaScope::Chat, -100, db.clone()).unwrap();
+        assert_eq!((chat.tokens, chat.requests), (150, 1));
+
+        let other = get_usage(QuotaScope::User, 2, db).unwrap();
+        assert_eq!((other.tokens, other.requests), (0, 0));
+    }
+
+    #[test]
+    fn token_override_is_enforced_for_user_then_chat() {
+        let db = temporary_db();
+        set_quota_override(QuotaScope::User, 1, QuotaOverride::Tokens(100), db.clone()).unwrap();
+        set_quota_override(
+            QuotaScope::Chat,
+            -100,
+            QuotaOverride::Tokens(150),
+            db.clone(),
+        )
+        .unwrap();
+
+        record_usage(1, Some(-100), tokens(60, 0), db.clone()).unwrap();
+        assert!(exceeded_quota(1, Some(-100), db.clone()).unwrap().is_none());
+
+        record_usage(1, Some(-100), tokens(40, 0), db.clone()).unwrap();
+        let (scope, status) = exceeded_quota(1, Some(-100), db.clone()).unwrap().unwrap();
+        assert_eq!(scope, QuotaScope::User);
+        assert_eq!((status.used, status.limit), (100, Some(100)));
+
+        // Another member is stopped by the chat quota once the chat has used it up
+        record_usage(2, Some(-100), tokens(50, 0), db.clone()).unwrap();
+        let (scope, status) = exceeded_quota(2, Some(-100), db.clone()).unwrap().unwrap();
+        assert_eq!(scope, QuotaScope::Chat);
+        assert_eq!((status.used, status.limit), (150, Some(150)));
+
+        // Outside the chat the same user is still within quota
+        assert!(exceeded_quota(2, None, db).unwrap().is_none());
+    }
+
+    #[test]
+    fn unlimited_and_default_overrides() {
+        let db = temporary_db();
+
+        set_quota_override(QuotaScope::User, 1, QuotaOverride::Unlimited, db.clone()).unwrap();
+        assert_eq!(quota_limit(QuotaScope::User, 1, db.clone()).unwrap(), None);
+
+        record_usage(1, None, tokens(u32::MAX as u64, 0), db.clone()).unwrap();
+        assert!(exceeded_quota(1, None, db.clone()).unwrap().is_none());
+
+        set_quota_override(QuotaScope::User, 1, QuotaOverride::Tokens(10), db.clone()).unwrap();
+        assert_eq!(
+            quota_limit(QuotaScope::User, 1, db.clone()).unwrap(),
+            Some(10)
+        );
+
+        set_quota_override(QuotaScope::User, 1, QuotaOverride::Default, db.clone()).unwrap();
+        assert_eq!(
+            quota_limit(QuotaScope::User, 1, db).unwrap(),
+            default_quota(QuotaScope::User)
+        );
+    }
+
+    #[test]
+    fn summary_counts_users_once_and_ranks_them() {
+        let db = temporary_db();
+
+        record_usage(1, Some(-100), tokens(100, 0), db.clone()).unwrap();
+        record_usage(2, Some(-100), tokens(300, 0), db.clone()).unwrap();
+        record_usage(3, None, tokens(200, 0), db.clone()).unwrap();
+
+        // Two days ago: part of the week but not of today
+        let tree = db.open_tree(USAGE_TREE).unwrap();
+        let old = UsageRecord {
+            tokens: 1_000,
+            requests: 1,
+        };
+        tree.insert(
+            usage_key(&day(2), QuotaScope::User, 1),
+            serde_json::to_vec(&old).unwrap(),
+        )
+        .unwrap();
+
+        let summary = usage_summary(2, db).unwrap();
+
+        // Chat usage is not added on top of its users'
+        assert_eq!(summary.today.tokens, 600);
+        assert_eq!(summary.today.requests, 3);
+        assert_eq!(summary.week_tokens, 1_600);
+
+        let top_users: Vec<_> = summary
+            .top_users
+            .iter()
+            .map(|(id, _)| id.as_str())
+            .collect();
+        assert_eq!(top_users, ["2", "3"]);
+
+        assert_eq!(summary.top_chats.len(), 1);
+        assert_eq!(summary.top_chats[0].0, "-100");
+        assert_eq!(summary.top_chats[0].1.tokens, 400);
+    }
+
+    #[test]
+    fn prunes_only_records_past_retention() {
+        let db = temporary_db();
+        record_usage(1, None, tokens(100, 0), db.clone()).unwrap();
+
+        let tree = db.open_tree(USAGE_TREE).unwrap();
+        let old = serde_json::to_vec(&UsageRecord::default()).unwrap();
+        tree.insert(
+            usage_key(&day(RETENTION_DAYS + 1), QuotaScope::User, 1),
+            old.clone(),
+        )
+        .unwrap();
+        tree.insert(
+            usage_key(&day(RETENTION_DAYS + 5), QuotaScope::Chat, -100),
+            old,
+        )
+        .unwrap();
+
+        assert_eq!(prune_usage(db.clone()).unwrap(), 2);
+        assert_eq!(prune_usage(db.clone()).unwrap(), 0);
+        assert_eq!(get_usage(QuotaScope::User, 1, db).unwrap().tokens, 100);
+    }
+
+    #[test]
+    fn unreported_usage_is_estimated() {
+        assert_eq!(reported_or_estimated(Some(tokens(3, 4))), tokens(3, 4));
+        assert_eq!(
+            reported_or_estimated(None).total(),
+            unreported_call_tokens()
+        );
+    }
+}
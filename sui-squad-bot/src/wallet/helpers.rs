@@ -0,0 +1,226 @@
+use std::env;
+
+use anyhow::{Result, anyhow};
+use squad_connect::client::squad_connect::SquadConnect;
+use sui_sdk::{SuiClient, rpc_types::EventFilter, types::base_types::ObjectID};
+use sui_squad_core::package::{dto::Event, helpers::balance_coin_type};
+use teloxide::types::UserId;
+
+use crate::{bot_manage::dto::BalanceObject, services::services::Services};
+
+/// Balance of one coin held by a Squad account.
+pub struct CoinBalance {
+    pub symbol: String,
+    pub decimals: u8,
+    pub amount: u64,
+}
+
+/// Object id of the Squad `Account` created for the Telegram user.
+pub async fn find_account_id(node: &SuiClient, user_id: UserId) -> Result<Option<ObjectID>> {
+    let filter = EventFilter::MoveEventType(Event::AccountEvent.to_string().parse()?);
+    let telegram_id = user_id.to_string();
+    let mut cursor = None;
+
+    loop {
+        let page = node
+            .event_api()
+            .query_events(filter.clone(), cursor, None, false)
+            .await?;
+
+        let account_id = page.data.iter().find_map(|event| {
+            if event.parsed_json.get("telegram_id")?.as_str()? != telegram_id {
+                return None;
+            }
+
+            event.parsed_json.get("account_id")?.as_str()
+        });
+
+        if let Some(account_id) = account_id {
+            return Ok(Some(ObjectID::from_hex_literal(account_id)?));
+        }
+
+        if !page.has_next_page {
+            return Ok(None);
+        }
+
+        cursor = page.next_cursor;
+    }
+}
+
+/// Every coin balance of the account, with the symbol and decimals of its coin metadata.
+pub async fn account_balances(node: &SuiClient, account_id: ObjectID) -> Result<Vec<CoinBalance>> {
+    let mut fields = Vec::new();
+    let mut cursor = None;
+
+    loop {
+        let page = node
+            .read_api()
+            .get_dynamic_fields(account_id, cursor, None)
+            .await?;
+
+        fields.extend(page.data);
+
+        if !page.has_next_page {
+            break;
+        }
+
+        cursor = page.next_cursor;
+    }
+
+    let mut balances = Vec::new();
+
+    for field in fields {
+        let Some(coin_type) = balance_coin_type(&field.name) else {
+            continue;
+        };
+
+        let object = node
+            .read_api()
+            .get_dynamic_field_object(account_id, field.name.clone())
+            .await?;
+
+        let content = object
+            .data
+            .and_then(|data| data.content)
+            .ok_or_else(|| anyhow!("Balance of {} not found", coin_type))?;
+
+        let balance_object: BalanceObject = serde_json::from_value(serde_json::to_value(content)?)?;
+        let amount = balance_object.fields.value.fields.balance.parse::<u64>()?;
+
+        let metadata = node
+            .coin_read_api()
+            .get_coin_metadata(coin_type.clone())
+            .await
+            .ok()
+            .flatten();
+
+        let (symbol, decimals) = match metadata {
+            Some(metadata) => (metadata.symbol, metadata.decimals),
+            None => (
+                coin_type
+                    .rsplit("::")
+                    .next()
+                    .unwrap_or(&coin_type)
+                    .to_string(),
+                9,
+            ),
+        };
+
+        balances.push(CoinBalance {
+            symbol,
+            decimals,
+            amount,
+        });
+    }
+
+    Ok(balances)
+}
+
+/// Format raw units with `decimals`, dropping trailing zeros (`1500000000`, 9 → `1.5`).
+pub fn format_units(amount: u64, decimals: u8) -> String {
+    // Past 10^38 the scale no longer fits, but any u64 is then below one whole unit anyway
+    let scale = 10u128.checked_pow(decimals as u32).unwrap_or(u128::MAX);
+    let whole = amount as u128 / scale;
+    let fraction = amount as u128 % scale;
+
+    if fraction == 0 {
+        return whole.to_string();
+    }
+
+    let fraction = format!("{:0width$}", fraction, width = decimals as usize);
+
+    format!("{}.{}", whole, fraction.trim_end_matches('0'))
+}
+
+fn explorer_link(path: &str) -> String {
+    match env::var("SUI_EXPLORER_URL") {
+        Ok(sui_explorer_url) => format!(
+            "\n🔗 <a href=\"{}/{}\">View on explorer</a>",
+            sui_explorer_url.trim_end_matches('/'),
+            path
+        ),
+        Err(_) => String::new(),
+    }
+}
+
+/// HTML summary of the user's Squad account, zkLogin address and balances.
+pub async fn wallet_info(
+    user_id: UserId,
+    services: &Services,
+    squad_connect_client: &SquadConnect,
+) -> Result<String> {
+    let node = squad_connect_client.get_node();
+
+    let account_id = find_account_id(&node, user_id)
+        .await?
+        .ok_or_else(|| anyhow!("Account not found, use /login to create it"))?;
+
+    let balances = account_balances(&node, account_id).await?;
+
+    let zk_login_address = match services.zk_login_address(user_id.to_string()).await {
+        Ok(address) => address,
+        Err(e) => {
+            println!("Failed to get the zkLogin address of {}: {}", user_id, e);
+            None
+        }
+    };
+
+    let mut text = format!(
+        "👛 <b>Your Squad wallet</b>\n\n🏦 Account: <code>{}</code>{}",
+        account_id,
+        explorer_link(&format!("object/{}", account_id))
+    );
+
+    match zk_login_address {
+        Some(address) => text.push_str(&format!(
+            "\n\n🔐 zkLogin address: <code>{}</code>{}",
+            address,
+            explorer_link(&format!("account/{}", address))
+        )),
+        None => {
+            text.push_str("\n\n🔐 zkLogin address: not known yet, log in with /fund to link it")
+        }
+    }
+
+    text.push_str("\n\n💰 Balances:");
+
+    if balances.iter().all(|balance| balance.amount == 0) {
+        text.push_str("\n• No funds yet, use /fund to deposit");
+    }
+
+    for balance in balances.iter().filter(|balance| balance.amount > 0) {
+        text.push_str(&format!(
+            "\n• {} {}",
+            format_units(balance.amount, balance.decimals),
+            balance.symbol
+        ));
+    }
+
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_units_drops_trailing_zeros() {
+        assert_eq!(format_units(1_500_000_000, 9), "1.5");
+        assert_eq!(format_units(2_000_000_000, 9), "2");
+        assert_eq!(format_units(1_230_000, 6), "1.23");
+    }
+
+    #[test]
+    fn format_units_pads_small_fractions() {
+        assert_eq!(format_units(1, 9), "0.000000001");
+        assert_eq!(format_units(50, 3), "0.05");
+    }
+
+    #[test]
+    fn format_units_handles_edge_values() {
+        assert_eq!(format_units(0, 9), "0");
+        assert_eq!(format_units(42, 0), "42");
+        assert_eq!(format_units(u64::MAX, 9), "18446744073.709551615");
+        assert_eq!(format_units(1, 40), format!("0.{}1", "0".repeat(39)));
+    }
+}
@@ -0,0 +1,517 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use reqwest::Client;
+use serde_json::{Value, json};
+use tokio::sync::Mutex;
+
+use crate::error::CoreError;
+
+use super::{
+    LlmBackend, LlmConfig, LlmResponse, TextSink, TokenUsage, ToolCall, ToolDefinition,
+    sse::read_events,
+};
+
+/// Conversations remembered at most; the oldest are forgotten beyond it.
+const MAX_TRANSCRIPTS: usize = 1000;
+/// Messages kept per conversation besides the system prompt.
+const MAX_MESSAGES: usize = 40;
+
+/// Messages exchanged so far, by the id of the turn that ended them.
+#[derive(Default)]
+struct Transcripts {
+    messages: HashMap<String, Vec<Value>>,
+    order: VecDeque<String>,
+}
+
+impl Transcripts {
+    fn insert(&mut self, id: String, messages: Vec<Value>) {
+        self.order.push_back(id.clone());
+        self.messages.insert(id, messages);
+
+        while self.order.len() > MAX_TRANSCRIPTS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.messages.remove(&oldest);
+            }
+        }
+    }
+
+    fn remove(&mut self, id: &str) {
+        if self.messages.remove(id).is_some() {
+            self.order.retain(|known| known != id);
+        }
+    }
+}
+
+/// Client for any server speaking OpenAI's `/chat/completions` API, such as a self-hosted model.
+///
+/// The API is stateless, so conversations are replayed from transcripts kept in memory and
+/// start over after a restart.
+#[derive(Clone)]
+pub struct ChatCompletionsClient {
+    http: Client,
+    url: String,
+    api_key: Option<String>,
+    model: String,
+    temperature: Option<f32>,
+    system_prompt: String,
+    transcripts: Arc<Mutex<Transcripts>>,
+    next_id: Arc<AtomicU64>,
+}
+
+fn to_function(definition: &ToolDefinition) -> Value {
+    json!({
+        "type": "function",
+        "function": {
+            "name": definition.name,
+            "description": definition.description,
+            "parameters": definition
+                .parameters
+                .clone()
+                .unwrap_or_else(|| json!({ "type": "object", "properties": {} })),
+        }
+    })
+}
+
+/// Rebuild the assistant message from the deltas of a streamed completion.
+fn apply_delta(message: &mut Value, delta: &Value) {
+    if let Some(content) = delta["content"].as_str() {
+        let text = format!(
+            "{}{}",
+            message["content"].as_str().unwrap_or_default(),
+            content
+        );
+        message["content"] = Value::String(text);
+    }
+
+    let Some(calls) = delta["tool_calls"].as_array() else {
+        return;
+    };
+
+    if !message["tool_calls"].is_array() {
+        message["tool_calls"] = json!([]);
+    }
+
+    for call in calls {
+        let index = call["index"].as_u64().unwrap_or_default() as usize;
+        let known = message["tool_calls"]
+            .as_array_mut()
+            .expect("tool_calls is an array");
+
+        while known.len() <= index {
+            known.push(json!({ "type": "function", "function": { "name": "", "arguments": "" } }));
+        }
+
+        let known = &mut known[index];
+
+        if let Some(id) = call["id"].as_str() {
+            known["id"] = json!(id);
+        }
+
+        for key in ["name", "arguments"] {
+            if let Some(part) = call["function"][key].as_str() {
+                let joined = format!(
+                    "{}{}",
+                    known["function"][key].as_str().unwrap_or_default(),
+                    part
+                );
+                known["function"][key] = Value::String(joined);
+            }
+        }
+    }
+}
+
+/// Token counts of a completion, `None` when the server did not report them.
+fn parse_usage(usage: &Value) -> Option<TokenUsage> {
+    Some(TokenUsage {
+        input_tokens: usage["prompt_tokens"].as_u64()?,
+        output_tokens: usage["completion_tokens"].as_u64()?,
+    })
+}
+
+/// Drop the oldest messages, keeping the system prompt and starting again at a user message
+/// so no tool output loses the call it answers.
+fn trim(messages: &mut Vec<Value>) {
+    if messages.len() <= MAX_MESSAGES + 1 {
+        return;
+    }
+
+    let mut start = messages.len() - MAX_MESSAGES;
+
+    while start < messages.len() && messages[start]["role"] != "user" {
+        start += 1;
+    }
+
+    if start < messages.len() {
+        messages.drain(1..start);
+    }
+}
+
+impl ChatCompletionsClient {
+    pub fn new(config: &LlmConfig, base_url: &str) -> Result<Self, CoreError> {
+        let http = Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .map_err(|e| {
+                CoreError::ConfigurationError(format!(
+                    "Failed to create chat completions client: {}",
+                    e
+                ))
+            })?;
+
+        Ok(ChatCompletionsClient {
+            http,
+            url: format!("{}/chat/completions", base_url.trim_end_matches('/')),
+            api_key: config.api_key.clone(),
+            model: config.model.clone(),
+            temperature: config.temperature,
+            system_prompt: config.system_prompt.clone(),
+            transcripts: Arc::new(Mutex::new(Transcripts::default())),
+            next_id: Arc::new(AtomicU64::new(0)),
+        })
+    }
+
+    async fn transcript(&self, id: &str) -> Option<Vec<Value>> {
+        self.transcripts.lock().await.messages.get(id).cloned()
+    }
+
+    /// Messages of a new prompt, after the transcript of `previous_response_id` when known.
+    async fn prompt_messages(&self, input: &str, previous_response_id: Option<&str>) -> Vec<Value> {
+        let previous = match previous_response_id {
+            Some(id) => self.transcript(id).await,
+            None => None,
+        };
+
+        let mut messages = previous
+            .unwrap_or_else(|| vec![json!({ "role": "system", "content": self.system_prompt })]);
+
+        messages.push(json!({ "role": "user", "content": input }));
+        messages
+    }
+
+    /// Messages answering the tool calls made in `response_id`.
+    async fn output_messages(
+        &self,
+        response_id: &str,
+        outputs: Vec<(String, String)>,
+    ) -> Result<Vec<Value>, CoreError> {
+        let mut messages = self
+            .transcript(response_id)
+            .await
+            .ok_or_else(|| CoreError::Other(format!("Unknown chat completion {}", response_id)))?;
+
+        for (call_id, output) in outputs {
+            messages.push(json!({
+                "role": "tool",
+                "tool_call_id": call_id,
+                "content": output,
+            }));
+        }
+
+        Ok(messages)
+    }
+
+    /// Ask the model to continue `messages`, streaming its text to `on_text` if given, then
+    /// remember them under a new id.
+    async fn complete(
+        &self,
+        mut messages: Vec<Value>,
+        tools: &[ToolDefinition],
+        previous_id: Option<&str>,
+        on_text: Option<&TextSink>,
+    ) -> Result<LlmResponse, CoreError> {
+        let mut body = json!({
+            "model": self.model,
+            "messages": messages,
+        });
+
+        if !tools.is_empty() {
+            body["tools"] = Value::Array(tools.iter().map(to_function).collect());
+            body["tool_choice"] = json!("auto");
+        }
+
+        if let Some(temperature) = self.temperature {
+            body["temperature"] = json!(temperature);
+        }
+
+        if on_text.is_some() {
+            body["stream"] = json!(true);
+            // The last chunk then carries the usage of the whole completion
+            body["stream_options"] = json!({ "include_usage": true });
+        }
+
+        let mut request = self.http.post(&self.url).json(&body);
+
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| CoreError::Other(format!("Failed to reach {}: {}", self.url, e)))?;
+
+        let status = response.status();
+
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(CoreError::Other(format!(
+                "Chat completion failed with {}: {}",
+                status, text
+            )));
+        }
+
+        let (message, usage) = match on_text {
+            Some(on_text) => {
+                let mut message = json!({ "role": "assistant", "content": null });
+                let mut usage = None;
+
+                read_events(response, |chunk| {
+                    if let Some(reported) = parse_usage(&chunk["usage"]) {
+                        usage = Some(reported);
+                    }
+
+                    let delta = &chunk["choices"][0]["delta"];
+
+                    apply_delta(&mut message, delta);
+
+                    if delta["content"].is_string() {
+                        on_text(message["content"].as_str().unwrap_or_default());
+                    }
+
+                    Ok(())
+                })
+                .await?;
+
+                (message, usage)
+            }
+            None => {
+                let completion: Value = response
+                    .json()
+                    .await
+                    .map_err(|e| CoreError::Other(format!("Invalid chat completion: {}", e)))?;
+
+                let message = completion["choices"][0]["message"].clone();
+
+                if message.is_null() {
+                    return Err(CoreError::Other(format!(
+                        "Chat completion without a message: {}",
+                        completion
+                    )));
+                }
+
+                (message, parse_usage(&completion["usage"]))
+            }
+        };
+
+        let tool_calls = message["tool_calls"]
+            .as_array()
+            .map(|calls| {
+                calls
+                    .iter()
+                    .map(|call| ToolCall {
+                        name: call["function"]["name"]
+                            .as_str()
+                            .unwrap_or_default()
+                            .to_string(),
+                        arguments: call["function"]["arguments"]
+                            .as_str()
+                            .unwrap_or("{}")
+                            .to_string(),
+                        call_id: call["id"].as_str().unwrap_or_default().to_string(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let text = message["content"].as_str().unwrap_or_default().to_string();
+
+        messages.push(message);
+        trim(&mut messages);
+
+        let id = format!(
+            "chat-{}-{}",
+            Utc::now().timestamp_millis(),
+            self.next_id.fetch_add(1, Ordering::Relaxed)
+        );
+
+        let mut transcripts = self.transcripts.lock().await;
+
+        // Each turn extends the previous transcript, which is no longer needed
+        if let Some(previous_id) = previous_id {
+            transcripts.remove(previous_id);
+        }
+
+        transcripts.insert(id.clone(), messages);
+
+        Ok(LlmResponse {
+            id,
+            text,
+            tool_calls,
+            usage,
+        })
+    }
+}
+
+#[async_trait]
+impl LlmBackend for ChatCompletionsClient {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn generate(
+        &self,
+        input: &str,
+        tools: &[ToolDefinition],
+        previous_response_id: Option<String>,
+    ) -> Result<LlmResponse, CoreError> {
+        let previous_response_id = previous_response_id.as_deref();
+        let messages = self.prompt_messages(input, previous_response_id).await;
+
+        self.complete(messages, tools, previous_response_id, None)
+            .await
+    }
+
+    async fn submit_tool_outputs(
+        &self,
+        response_id: &str,
+        outputs: Vec<(String, String)>,
+        tools: &[ToolDefinition],
+    ) -> Result<LlmResponse, CoreError> {
+        let messages = self.output_messages(response_id, outputs).await?;
+
+        self.complete(messages, tools, Some(response_id), None)
+            .await
+    }
+
+    async fn generate_streaming(
+        &self,
+        input: &str,
+        tools: &[ToolDefinition],
+        previous_response_id: Option<String>,
+        on_text: &TextSink,
+    ) -> Result<LlmResponse, CoreError> {
+        let previous_response_id = previous_response_id.as_deref();
+        let messages = self.prompt_messages(input, previous_response_id).await;
+
+        self.complete(messages, tools, previous_response_id, Some(on_text))
+            .await
+    }
+
+    async fn submit_tool_outputs_streaming(
+        &self,
+        response_id: &str,
+        outputs: Vec<(String, String)>,
+        tools: &[ToolDefinition],
+        on_text: &TextSink,
+    ) -> Result<LlmResponse, CoreError> {
+        let messages = self.output_messages(response_id, outputs).await?;
+
+        self.complete(messages, tools, Some(response_id), Some(on_text))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assistant() -> Value {
+        json!({ "role": "assistant", "content": null })
+    }
+
+    #[test]
+    fn apply_delta_joins_content() {
+        let mut message = assistant();
+
+        apply_delta(
+            &mut message,
+            &json!({ "role": "assistant", "content": "Hel" }),
+        );
+        apply_delta(&mut message, &json!({ "content": "lo" }));
+
+        assert_eq!(message["content"], json!("Hello"));
+        assert!(message.get("tool_calls").is_none());
+    }
+
+    #[test]
+    fn apply_delta_joins_fragmented_tool_call_arguments() {
+        let mut message = assistant();
+
+        for delta in [
+            json!({ "tool_calls": [{
+                "index": 0,
+                "id": "call_1",
+                "type": "function",
+                "function": { "name": "send", "arguments": "" }
+            }] }),
+            json!({ "tool_calls": [{ "index": 0, "function": { "arguments": "{\"targets\":[\"bo" } }] }),
+            json!({ "tool_calls": [{ "index": 0, "function": { "arguments": "b\"],\"amount\"" } }] }),
+            json!({ "tool_calls": [{ "index": 0, "function": { "arguments": ":2}" } }] }),
+        ] {
+            apply_delta(&mut message, &delta);
+        }
+
+        let calls = message["tool_calls"].as_array().unwrap();
+
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0]["id"], json!("call_1"));
+        assert_eq!(calls[0]["function"]["name"], json!("send"));
+
+        let arguments: Value =
+            serde_json::from_str(calls[0]["function"]["arguments"].as_str().unwrap()).unwrap();
+
+        assert_eq!(arguments, json!({ "targets": ["bob"], "amount": 2 }));
+    }
+
+    #[test]
+    fn apply_delta_keeps_parallel_tool_calls_apart() {
+        let mut message = assistant();
+
+        for delta in [
+            json!({ "tool_calls": [{ "index": 0, "id": "call_1", "function": { "name": "get_wallet" } }] }),
+            json!({ "tool_calls": [{ "index": 1, "id": "call_2", "function": { "name": "get_bal" } }] }),
+            json!({ "tool_calls": [
+                { "index": 0, "function": { "arguments": "{}" } },
+                { "index": 1, "function": { "name": "ance", "arguments": "{" } }
+            ] }),
+            json!({ "tool_calls": [{ "index": 1, "function": { "arguments": "}" } }] }),
+        ] {
+            apply_delta(&mut message, &delta);
+        }
+
+        assert_eq!(
+            message["tool_calls"],
+            json!([
+                {
+                    "id": "call_1",
+                    "type": "function",
+                    "function": { "name": "get_wallet", "arguments": "{}" }
+                },
+                {
+                    "id": "call_2",
+                    "type": "function",
+                    "function": { "name": "get_balance", "arguments": "{}" }
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn apply_delta_ignores_chunks_without_a_delta() {
+        let mut message = assistant();
+
+        // The usage chunk at the end of a stream has no choices
+        apply_delta(&mut message, &Value::Null);
+        apply_delta(&mut message, &json!({}));
+
+        assert_eq!(message, assistant());
+    }
+}
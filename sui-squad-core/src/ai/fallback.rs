@@ -0,0 +1,154 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::error::CoreError;
+
+use super::{
+    LlmBackend, LlmResponse, TextSink, ToolDefinition,
+    intent::{INTENT_ID_PREFIX, IntentParser},
+};
+
+/// A model backed by the rule-based [`IntentParser`] whenever a call to it fails.
+pub struct FallbackBackend {
+    primary: Arc<dyn LlmBackend>,
+    rules: IntentParser,
+}
+
+impl FallbackBackend {
+    pub fn new(primary: Arc<dyn LlmBackend>) -> Self {
+        FallbackBackend {
+            primary,
+            rules: IntentParser::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl LlmBackend for FallbackBackend {
+    fn model(&self) -> &str {
+        self.primary.model()
+    }
+
+    async fn generate(
+        &self,
+        input: &str,
+        tools: &[ToolDefinition],
+        previous_response_id: Option<String>,
+    ) -> Result<LlmResponse, CoreError> {
+        // Turns answered by the rules are unknown to the model
+        let previous_response_id =
+            previous_response_id.filter(|id| !id.starts_with(INTENT_ID_PREFIX));
+
+        match self
+            .primary
+            .generate(input, tools, previous_response_id)
+            .await
+        {
+            Ok(response) => Ok(response),
+            Err(e) => {
+                println!(
+                    "⚠️ {} failed, falling back to rules: {}",
+                    self.primary.model(),
+                    e
+                );
+                self.rules.generate(input, tools, None).await
+            }
+        }
+    }
+
+    async fn submit_tool_outputs(
+        &self,
+        response_id: &str,
+        outputs: Vec<(String, String)>,
+        tools: &[ToolDefinition],
+    ) -> Result<LlmResponse, CoreError> {
+        if response_id.starts_with(INTENT_ID_PREFIX) {
+            return self
+                .rules
+                .submit_tool_outputs(response_id, outputs, tools)
+                .await;
+        }
+
+        match self
+            .primary
+            .submit_tool_outputs(response_id, outputs.clone(), tools)
+            .await
+        {
+            Ok(response) => Ok(response),
+            Err(e) => {
+                // The tools already ran, so their outputs are still worth showing
+                println!(
+                    "⚠️ {} failed, replying with tool outputs: {}",
+                    self.primary.model(),
+                    e
+                );
+                self.rules
+                    .submit_tool_outputs(response_id, outputs, tools)
+                    .await
+            }
+        }
+    }
+
+    async fn generate_streaming(
+        &self,
+        input: &str,
+        tools: &[ToolDefinition],
+        previous_response_id: Option<String>,
+        on_text: &TextSink,
+    ) -> Result<LlmResponse, CoreError> {
+        let previous_response_id =
+            previous_response_id.filter(|id| !id.starts_with(INTENT_ID_PREFIX));
+
+        match self
+            .primary
+            .generate_streaming(input, tools, previous_response_id, on_text)
+            .await
+        {
+            Ok(response) => Ok(response),
+            Err(e) => {
+                println!(
+                    "⚠️ {} failed, falling back to rules: {}",
+                    self.primary.model(),
+                    e
+                );
+                self.rules
+                    .generate_streaming(input, tools, None, on_text)
+                    .await
+            }
+        }
+    }
+
+    async fn submit_tool_outputs_streaming(
+        &self,
+        response_id: &str,
+        outputs: Vec<(String, String)>,
+        tools: &[ToolDefinition],
+        on_text: &TextSink,
+    ) -> Result<LlmResponse, CoreError> {
+        if response_id.starts_with(INTENT_ID_PREFIX) {
+            return self
+                .rules
+                .submit_tool_outputs_streaming(response_id, outputs, tools, on_text)
+                .await;
+        }
+
+        match self
+            .primary
+            .submit_tool_outputs_streaming(response_id, outputs.clone(), tools, on_text)
+            .await
+        {
+            Ok(response) => Ok(response),
+            Err(e) => {
+                println!(
+                    "⚠️ {} failed, replying with tool outputs: {}",
+                    self.primary.model(),
+                    e
+                );
+                self.rules
+                    .submit_tool_outputs_streaming(response_id, outputs, tools, on_text)
+                    .await
+            }
+        }
+    }
+}
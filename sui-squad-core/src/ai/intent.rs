@@ -0,0 +1,452 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::{Value, json};
+
+use crate::error::CoreError;
+
+use super::{LlmBackend, LlmResponse, ToolCall, ToolDefinition};
+
+/// Prefix of the response ids produced by [`IntentParser`].
+pub const INTENT_ID_PREFIX: &str = "intent-";
+
+const SEND_VERBS: &[&str] = &["send", "pay", "tip", "give", "transfer"];
+const WITHDRAW_VERBS: &[&str] = &["withdraw", "cashout"];
+const NEGATIONS: &[&str] = &[
+    "not", "no", "never", "cannot", "cancel", "stop", "without", "dont", "doesnt", "didnt", "wont",
+    "cant", "shouldnt", "wouldnt",
+];
+
+const EXAMPLES: &str = "Try one of these:
+- <code>wallet</code>
+- <code>balance</code>
+- <code>send 2 SUI to @bob and @carol</code>
+- <code>withdraw 1.5 SUI to 0x…</code>";
+
+/// A wallet action recognised in a prompt, matching the tools offered to the model.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Intent {
+    Wallet,
+    Balance,
+    Send { targets: Vec<String>, amount: f64 },
+    Withdraw { amount: f64, address: String },
+}
+
+impl Intent {
+    pub fn tool_name(&self) -> &'static str {
+        match self {
+            Intent::Wallet => "get_wallet",
+            Intent::Balance => "get_balance",
+            Intent::Send { .. } => "send",
+            Intent::Withdraw { .. } => "withdraw",
+        }
+    }
+
+    pub fn arguments(&self) -> Value {
+        match self {
+            Intent::Wallet | Intent::Balance => json!({}),
+            Intent::Send { targets, amount } => json!({ "targets": targets, "amount": amount }),
+            Intent::Withdraw { amount, address } => {
+                json!({ "amount": amount, "address": address })
+            }
+        }
+    }
+}
+
+fn is_sui_destination(word: &str) -> bool {
+    word.starts_with("0x") || word.to_lowercase().ends_with(".sui")
+}
+
+/// A plain decimal number such as `2`, `1.5` or `.5`; exponents, signs and `inf` are not amounts.
+fn parse_decimal(word: &str) -> Option<f64> {
+    let decimal = word.chars().any(|c| c.is_ascii_digit())
+        && word.chars().all(|c| c.is_ascii_digit() || c == '.')
+        && word.matches('.').count() <= 1;
+
+    if !decimal {
+        return None;
+    }
+
+    word.parse::<f64>().ok()
+}
+
+/// Every number in `words`, also written glued to the token (`2sui`).
+fn find_amounts(words: &[&str]) -> Vec<(usize, f64)> {
+    words
+        .iter()
+        .enumerate()
+        .filter_map(|(index, word)| {
+            let lower = word.to_lowercase();
+            let number = lower.strip_suffix("sui").unwrap_or(&lower);
+
+            parse_decimal(number).map(|amount| (index, amount))
+        })
+        .collect()
+}
+
+/// Only SUI can be moved for now; a symbol right after the amount must name it.
+fn check_token(words: &[&str], amount_index: usize) -> Result<(), String> {
+    match words.get(amount_index + 1) {
+        Some(word)
+            if word.chars().all(|c| c.is_ascii_alphabetic())
+                && !word.eq_ignore_ascii_case("to")
+                && !word.eq_ignore_ascii_case("sui") =>
+        {
+            Err(format!(
+                "Only SUI is supported, not {}",
+                word.to_uppercase()
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Questions and negated requests ("don't send…", "should I pay…?") must never move funds.
+fn is_plain_instruction(input: &str, lower: &[String]) -> bool {
+    !input.contains('?')
+        && !lower.iter().any(|word| {
+            word.ends_with("n't") || word.ends_with("n’t") || NEGATIONS.contains(&word.as_str())
+        })
+}
+
+/// Recognise the common wallet requests without a model: `wallet`, `balance`,
+/// `send 2 SUI to @bob and @carol`, `withdraw 1.5 SUI to 0x…`.
+///
+/// Returns a hint for the user when the prompt is not understood.
+pub fn parse_intent(input: &str) -> Result<Intent, String> {
+    let words: Vec<&str> = input
+        .split(|c: char| c.is_whitespace() || c == ',')
+        // Only trailing dots are punctuation, `.5` is an amount
+        .map(|word| {
+            word.trim_matches('"')
+                .trim_end_matches(|c: char| matches!(c, '.' | '!' | '?' | ';' | ':'))
+        })
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    let lower: Vec<String> = words.iter().map(|word| word.to_lowercase()).collect();
+    let has = |candidates: &[&str]| lower.iter().any(|word| candidates.contains(&word.as_str()));
+
+    let destination = lower
+        .iter()
+        .rposition(|word| word == "to")
+        .and_then(|index| words.get(index + 1))
+        .map(|word| word.to_string());
+
+    if has(SEND_VERBS) || has(WITHDRAW_VERBS) {
+        // Only an instruction starting with the verb moves funds, not a verb quoted or
+        // explained somewhere in the prompt
+        let starts_with_verb = lower.first().is_some_and(|word| {
+            SEND_VERBS.contains(&word.as_str()) || WITHDRAW_VERBS.contains(&word.as_str())
+        });
+
+        if !starts_with_verb || !is_plain_instruction(input, &lower) {
+            return Err(format!(
+                "I only move funds on a plain instruction starting with what to do, not a question or a negation.\n\n{}",
+                EXAMPLES
+            ));
+        }
+
+        let (amount_index, amount) = match find_amounts(&words)[..] {
+            [(index, amount)] if amount > 0.0 => (index, amount),
+            [] | [_] => return Err(format!("How much should I move?\n\n{}", EXAMPLES)),
+            _ => {
+                return Err(format!(
+                    "Please move one amount at a time, to one or more people.\n\n{}",
+                    EXAMPLES
+                ));
+            }
+        };
+
+        check_token(&words, amount_index)?;
+
+        let targets: Vec<String> = words
+            .iter()
+            .filter(|word| word.starts_with('@') && word.len() > 1 && !word.contains('.'))
+            .map(|word| word.trim_start_matches('@').to_string())
+            .collect();
+
+        // Sending to an address rather than to users is a withdrawal
+        let withdraw = has(WITHDRAW_VERBS)
+            || (targets.is_empty() && destination.as_deref().is_some_and(is_sui_destination));
+
+        if withdraw {
+            let address = destination
+                .ok_or_else(|| format!("Where should I withdraw to?\n\n{}", EXAMPLES))?;
+
+            return Ok(Intent::Withdraw { amount, address });
+        }
+
+        if targets.is_empty() {
+            return Err(format!("Who should I send to?\n\n{}", EXAMPLES));
+        }
+
+        return Ok(Intent::Send { targets, amount });
+    }
+
+    if has(&["balance", "balances", "funds"]) || input.to_lowercase().contains("how much") {
+        return Ok(Intent::Balance);
+    }
+
+    if has(&["wallet", "address", "account"]) {
+        return Ok(Intent::Wallet);
+    }
+
+    Err(format!(
+        "I can only handle simple wallet requests right now. {}",
+        EXAMPLES
+    ))
+}
+
+/// Rule-based stand-in for a model, turning prompts into the tool calls a model would make.
+#[derive(Default)]
+pub struct IntentParser {
+    next_id: AtomicU64,
+}
+
+impl IntentParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn next_id(&self) -> String {
+        format!(
+            "{}{}-{}",
+            INTENT_ID_PREFIX,
+            Utc::now().timestamp_millis(),
+            self.next_id.fetch_add(1, Ordering::Relaxed)
+        )
+    }
+}
+
+#[async_trait]
+impl LlmBackend for IntentParser {
+    fn model(&self) -> &str {
+        "rules"
+    }
+
+    async fn generate(
+        &self,
+        input: &str,
+        tools: &[ToolDefinition],
+        _previous_response_id: Option<String>,
+    ) -> Result<LlmResponse, CoreError> {
+        let id = self.next_id();
+
+        let intent = parse_intent(input).and_then(|intent| {
+            if tools.iter().any(|tool| tool.name == intent.tool_name()) {
+                Ok(intent)
+            } else {
+                Err(format!("{} is not available here", intent.tool_name()))
+            }
+        });
+
+        Ok(match intent {
+            Ok(intent) => LlmResponse {
+                tool_calls: vec![ToolCall {
+                    name: intent.tool_name().to_string(),
+                    arguments: intent.arguments().to_string(),
+                    call_id: format!("{}-call", id),
+                }],
+                id,
+                text: String::new(),
+                usage: None,
+            },
+            Err(hint) => LlmResponse {
+                id,
+                text: hint,
+                tool_calls: vec![],
+                usage: None,
+            },
+        })
+    }
+
+    /// Without a model to phrase them, the tool outputs are the answer.
+    async fn submit_tool_outputs(
+        &self,
+        _response_id: &str,
+        outputs: Vec<(String, String)>,
+        _tools: &[ToolDefinition],
+    ) -> Result<LlmResponse, CoreError> {
+        Ok(LlmResponse {
+            id: self.next_id(),
+            text: outputs
+                .into_iter()
+                .map(|(_, output)| output)
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+            tool_calls: vec![],
+            usage: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "0x7d20dcdb2bca4f508ea9613994683eb4e76e9c4ed371169677c1be02aaf0b58e";
+
+    #[test]
+    fn parses_wallet_and_balance() {
+        assert_eq!(parse_intent("wallet"), Ok(Intent::Wallet));
+        assert_eq!(parse_intent("show my address"), Ok(Intent::Wallet));
+        assert_eq!(parse_intent("balance"), Ok(Intent::Balance));
+        assert_eq!(parse_intent("how much do I have?"), Ok(Intent::Balance));
+    }
+
+    #[test]
+    fn parses_send_to_several_users() {
+        assert_eq!(
+            parse_intent("send 2 SUI to @bob and @carol"),
+            Ok(Intent::Send {
+                targets: vec!["bob".to_string(), "carol".to_string()],
+                amount: 2.0,
+            })
+        );
+    }
+
+    #[test]
+    fn parses_withdraw_to_address() {
+        assert_eq!(
+            parse_intent(&format!("withdraw 1.5 SUI to {}", ADDRESS)),
+            Ok(Intent::Withdraw {
+                amount: 1.5,
+                address: ADDRESS.to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn sending_to_an_address_is_a_withdrawal() {
+        assert_eq!(
+            parse_intent("send 3 SUI to alice.sui"),
+            Ok(Intent::Withdraw {
+                amount: 3.0,
+                address: "alice.sui".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn parses_amount_glued_to_token() {
+        assert_eq!(
+            parse_intent("pay 2sui to @bob"),
+            Ok(Intent::Send {
+                targets: vec!["bob".to_string()],
+                amount: 2.0,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_other_tokens() {
+        let hint = parse_intent("send 2 USDC to @bob").unwrap_err();
+
+        assert!(hint.contains("Only SUI is supported, not USDC"));
+    }
+
+    #[test]
+    fn asks_for_missing_details() {
+        assert!(
+            parse_intent("send 2 SUI")
+                .unwrap_err()
+                .starts_with("Who should I send to?")
+        );
+        assert!(
+            parse_intent("withdraw 2 SUI")
+                .unwrap_err()
+                .starts_with("Where should I withdraw to?")
+        );
+        assert!(
+            parse_intent("send SUI to @bob")
+                .unwrap_err()
+                .starts_with("How much should I move?")
+        );
+    }
+
+    #[test]
+    fn rejects_negated_requests() {
+        for prompt in [
+            "don't send 2 SUI to @bob",
+            "don’t send 2 SUI to @bob",
+            "do not withdraw 1 SUI to alice.sui",
+            "never pay 5 SUI to @carol",
+        ] {
+            assert!(parse_intent(prompt).is_err(), "{}", prompt);
+        }
+    }
+
+    #[test]
+    fn rejects_verbs_that_do_not_start_the_prompt() {
+        for prompt in [
+            "explain how to send 5 SUI to @bob",
+            "Alice said she'd tip 5 SUI to @bob",
+            "please send 2 SUI to @bob",
+            "I want to withdraw 1 SUI to alice.sui",
+        ] {
+            assert!(parse_intent(prompt).is_err(), "{}", prompt);
+        }
+    }
+
+    #[test]
+    fn rejects_several_amounts() {
+        let hint = parse_intent("send 2 SUI to @bob and 3 SUI to @carol").unwrap_err();
+
+        assert!(hint.starts_with("Please move one amount at a time"));
+        assert!(parse_intent("withdraw 1 SUI to alice.sui 2").is_err());
+    }
+
+    #[test]
+    fn parses_decimal_amounts_only() {
+        assert_eq!(
+            parse_intent("send .5 SUI to @bob"),
+            Ok(Intent::Send {
+                targets: vec!["bob".to_string()],
+                amount: 0.5,
+            })
+        );
+
+        for prompt in [
+            "send 1e3 SUI to @bob",
+            "send 1E3 SUI to @bob",
+            "send inf SUI to @bob",
+            "send NaN SUI to @bob",
+            "send -2 SUI to @bob",
+            "send +2 SUI to @bob",
+            "send 0 SUI to @bob",
+            "send 1.2.3 SUI to @bob",
+        ] {
+            assert!(parse_intent(prompt).is_err(), "{}", prompt);
+        }
+    }
+
+    #[test]
+    fn rejects_questions() {
+        for prompt in [
+            "should I pay 5 SUI to @carol?",
+            "send 2 SUI to @bob?",
+            "can you withdraw 1 SUI to alice.sui?",
+        ] {
+            assert!(parse_intent(prompt).is_err(), "{}", prompt);
+        }
+    }
+
+    #[tokio::test]
+    async fn generate_emits_no_tool_call_for_questions() {
+        let tools = vec![ToolDefinition {
+            name: "send".to_string(),
+            description: String::new(),
+            parameters: None,
+        }];
+
+        let response = IntentParser::new()
+            .generate("should I pay 5 SUI to @carol?", &tools, None)
+            .await
+            .unwrap();
+
+        assert!(response.tool_calls.is_empty());
+        assert!(response.id.starts_with(INTENT_ID_PREFIX));
+    }
+}
@@ -1,8 +1,31 @@
+pub mod chat_completions;
+pub mod fallback;
+pub mod intent;
 pub mod responses_client;
+pub mod sse;
 
+pub use chat_completions::ChatCompletionsClient;
+pub use fallback::FallbackBackend;
+pub use intent::{Intent, IntentParser, parse_intent};
 pub use responses_client::ResponsesClient;
 
+use std::{env, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
 use open_ai_rust_responses_by_sshift::types::{Response as OAIResponse, ResponseItem};
+use serde_json::Value;
+
+use crate::error::CoreError;
+
+/// Default system prompt for all interactions, replaced with `LLM_SYSTEM_PROMPT`
+pub const SYSTEM_PROMPT: &str = "You are SUI Squad Bot, a Sui blockchain wallet assistant for Telegram groups! 🚀 Be enthusiastic, friendly, and engaging with light use of emojis. Make genuine connections with users and mirror their communication style and energy level. Respond conversationally and provide helpful wallet information.
+
+When tools are available, ONLY use them when users specifically ask for wallet actions (balance, address, send, withdraw). DO NOT call tools for greetings or casual conversation. Match tools exactly: get_wallet for addresses, get_balance for balances, send for transfers, withdraw for withdrawals.
+
+CRITICAL: When processing function results, you MUST preserve HTML formatting EXACTLY as provided. Do NOT convert <code></code> tags to backticks or any other format. Do NOT escape or modify HTML tags. Output function results with their HTML intact and add your own enthusiastic response with emojis around them.";
+
+const DEFAULT_OPENAI_MODEL: &str = "gpt-4.1-mini";
+const DEFAULT_OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
 
 // Extension trait to extract tool calls from response
 pub trait ResponseExt {
@@ -18,17 +41,203 @@ pub struct ToolCall {
 
 impl ResponseExt for OAIResponse {
     fn tool_calls(&self) -> Vec<ToolCall> {
-        self.output.iter().filter_map(|item| {
-            match item {
-                ResponseItem::FunctionCall { name, arguments, call_id, .. } => {
-                    Some(ToolCall {
-                        name: name.clone(),
-                        arguments: arguments.clone(),
-                        call_id: call_id.clone(),
-                    })
-                },
+        self.output
+            .iter()
+            .filter_map(|item| match item {
+                ResponseItem::FunctionCall {
+                    name,
+                    arguments,
+                    call_id,
+                    ..
+                } => Some(ToolCall {
+                    name: name.clone(),
+                    arguments: arguments.clone(),
+                    call_id: call_id.clone(),
+                }),
                 _ => None,
+            })
+            .collect()
+    }
+}
+
+/// A function the model may call, described independently of any vendor.
+#[derive(Debug, Clone)]
+pub struct ToolDefinition {
+    pub name: String,
+    pub description: String,
+    /// JSON schema of the arguments, `None` for functions without arguments.
+    pub parameters: Option<Value>,
+}
+
+/// Tokens billed for one call to the model.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+impl TokenUsage {
+    pub fn total(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+}
+
+/// One turn of the model: its text and the tools it wants called.
+#[derive(Debug, Clone)]
+pub struct LlmResponse {
+    /// Identifies the conversation up to this turn, to continue it later.
+    pub id: String,
+    pub text: String,
+    pub tool_calls: Vec<ToolCall>,
+    /// Tokens used by this turn, `None` when the backend does not report them.
+    pub usage: Option<TokenUsage>,
+}
+
+/// Receives the text of a response, as much as was produced so far, while it streams.
+pub type TextSink = dyn Fn(&str) + Send + Sync;
+
+/// A model able to answer prompts and call tools.
+#[async_trait]
+pub trait LlmBackend: Send + Sync + 'static {
+    fn model(&self) -> &str;
+
+    /// Answer `input`, continuing the conversation ending at `previous_response_id` if any.
+    async fn generate(
+        &self,
+        input: &str,
+        tools: &[ToolDefinition],
+        previous_response_id: Option<String>,
+    ) -> Result<LlmResponse, CoreError>;
+
+    /// Hand the `(call_id, output)` pairs of the tool calls made in `response_id` back to the model.
+    async fn submit_tool_outputs(
+        &self,
+        response_id: &str,
+        outputs: Vec<(String, String)>,
+        tools: &[ToolDefinition],
+    ) -> Result<LlmResponse, CoreError>;
+
+    /// [`generate`](Self::generate), reporting the text to `on_text` while it is produced.
+    /// Backends that cannot stream report it once, complete.
+    async fn generate_streaming(
+        &self,
+        input: &str,
+        tools: &[ToolDefinition],
+        previous_response_id: Option<String>,
+        on_text: &TextSink,
+    ) -> Result<LlmResponse, CoreError> {
+        let response = self.generate(input, tools, previous_response_id).await?;
+        on_text(&response.text);
+        Ok(response)
+    }
+
+    /// [`submit_tool_outputs`](Self::submit_tool_outputs), reporting the text to `on_text`
+    /// while it is produced.
+    async fn submit_tool_outputs_streaming(
+        &self,
+        response_id: &str,
+        outputs: Vec<(String, String)>,
+        tools: &[ToolDefinition],
+        on_text: &TextSink,
+    ) -> Result<LlmResponse, CoreError> {
+        let response = self
+            .submit_tool_outputs(response_id, outputs, tools)
+            .await?;
+        on_text(&response.text);
+        Ok(response)
+    }
+}
+
+/// Which API the model is reached through, selected with the `LLM_BACKEND` environment variable.
+#[derive(Debug, Clone)]
+pub enum LlmProvider {
+    /// `LLM_BACKEND=openai` (default): OpenAI's Responses API at `LLM_BASE_URL`, or OpenAI's.
+    OpenAi { base_url: String },
+    /// `LLM_BACKEND=chat`: any OpenAI-compatible `/chat/completions` server at `LLM_BASE_URL`.
+    ChatCompletions { base_url: String },
+}
+
+/// Backend and generation settings of the assistant.
+#[derive(Debug, Clone)]
+pub struct LlmConfig {
+    pub provider: LlmProvider,
+    /// `LLM_API_KEY`, falling back to `OPENAI_API_KEY`.
+    pub api_key: Option<String>,
+    /// `LLM_MODEL`, `gpt-4.1-mini` by default on OpenAI.
+    pub model: String,
+    /// `LLM_TEMPERATURE`, the server default when unset.
+    pub temperature: Option<f32>,
+    /// `LLM_TIMEOUT_SECS` for each call to the model (60 by default).
+    pub timeout: Duration,
+    pub system_prompt: String,
+}
+
+fn non_empty_var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.trim().is_empty())
+}
+
+impl LlmConfig {
+    pub fn from_env() -> Result<Self, CoreError> {
+        let backend = non_empty_var("LLM_BACKEND").unwrap_or_else(|| "openai".to_string());
+
+        let provider = match backend.as_str() {
+            "openai" => LlmProvider::OpenAi {
+                base_url: non_empty_var("LLM_BASE_URL")
+                    .unwrap_or_else(|| DEFAULT_OPENAI_BASE_URL.to_string()),
+            },
+            "chat" => LlmProvider::ChatCompletions {
+                base_url: non_empty_var("LLM_BASE_URL").ok_or_else(|| {
+                    CoreError::ConfigurationError(
+                        "LLM_BASE_URL must be set when LLM_BACKEND is chat".to_string(),
+                    )
+                })?,
+            },
+            other => {
+                return Err(CoreError::ConfigurationError(format!(
+                    "Unknown LLM_BACKEND {}, expected openai or chat",
+                    other
+                )));
+            }
+        };
+
+        let model = match (&provider, non_empty_var("LLM_MODEL")) {
+            (_, Some(model)) => model,
+            (LlmProvider::OpenAi { .. }, None) => DEFAULT_OPENAI_MODEL.to_string(),
+            (LlmProvider::ChatCompletions { .. }, None) => {
+                return Err(CoreError::ConfigurationError(
+                    "LLM_MODEL must be set when LLM_BACKEND is chat".to_string(),
+                ));
+            }
+        };
+
+        let temperature = match non_empty_var("LLM_TEMPERATURE") {
+            Some(value) => Some(value.parse::<f32>().map_err(|_| {
+                CoreError::ConfigurationError(format!("Invalid LLM_TEMPERATURE {}", value))
+            })?),
+            None => None,
+        };
+
+        let timeout_secs = non_empty_var("LLM_TIMEOUT_SECS")
+            .and_then(|secs| secs.parse::<u64>().ok())
+            .unwrap_or(60);
+
+        Ok(LlmConfig {
+            provider,
+            api_key: non_empty_var("LLM_API_KEY").or_else(|| non_empty_var("OPENAI_API_KEY")),
+            model,
+            temperature,
+            timeout: Duration::from_secs(timeout_secs),
+            system_prompt: non_empty_var("LLM_SYSTEM_PROMPT")
+                .unwrap_or_else(|| SYSTEM_PROMPT.to_string()),
+        })
+    }
+
+    pub fn load(&self) -> Result<Arc<dyn LlmBackend>, CoreError> {
+        match &self.provider {
+            LlmProvider::OpenAi { base_url } => Ok(Arc::new(ResponsesClient::new(self, base_url)?)),
+            LlmProvider::ChatCompletions { base_url } => {
+                Ok(Arc::new(ChatCompletionsClient::new(self, base_url)?))
             }
-        }).collect()
+        }
     }
-} 
\ No newline at end of file
+}
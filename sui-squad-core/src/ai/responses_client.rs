@@ -1,36 +1,226 @@
-use crate::config::Config;
-use crate::error::CoreError;
-use open_ai_rust_responses_by_sshift::{Client as OAIClient, Request, Model};
-use open_ai_rust_responses_by_sshift::types::{
-    Tool, Response as OAIResponse, ToolChoice,
-};
-
-/// Unified system prompt for all interactions
-const SYSTEM_PROMPT: &str = "You are SUI Squad Bot, a Sui blockchain wallet assistant for Telegram groups! 🚀 Be enthusiastic, friendly, and engaging with light use of emojis. Make genuine connections with users and mirror their communication style and energy level. Respond conversationally and provide helpful wallet information.
-
-When tools are available, ONLY use them when users specifically ask for wallet actions (balance, address, send, withdraw). DO NOT call tools for greetings or casual conversation. Match tools exactly: get_wallet for addresses, get_balance for balances, send for transfers, withdraw for withdrawals.
+use std::{fmt::Display, future::Future, time::Duration};
 
-CRITICAL: When processing function results, you MUST preserve HTML formatting EXACTLY as provided. Do NOT convert <code></code> tags to backticks or any other format. Do NOT escape or modify HTML tags. Output function results with their HTML intact and add your own enthusiastic response with emojis around them.";
+use crate::ai::{
+    LlmBackend, LlmConfig, LlmResponse, ResponseExt, TextSink, TokenUsage, ToolDefinition,
+    sse::read_events,
+};
+use crate::error::CoreError;
+use async_trait::async_trait;
+use open_ai_rust_responses_by_sshift::types::{Response as OAIResponse, Tool, ToolChoice};
+use open_ai_rust_responses_by_sshift::{Client as OAIClient, Config as OAIConfig, Model, Request};
+use serde_json::Value;
 
 /// Client for OpenAI-based responses using open_ai_rust_responses_by_sshift SDK.
 #[derive(Clone)]
 pub struct ResponsesClient {
     client: OAIClient,
+    http: reqwest::Client,
+    /// Endpoint streamed responses are read from; the SDK only streams text deltas.
+    url: String,
+    api_key: String,
+    model: String,
+    temperature: Option<f32>,
+    timeout: Duration,
+    system_prompt: String,
+}
+
+/// Describe a tool the way the Responses API expects it.
+fn to_tool(definition: &ToolDefinition) -> Tool {
+    Tool {
+        tool_type: "function".to_string(),
+        name: Some(definition.name.clone()),
+        description: Some(definition.description.clone()),
+        parameters: definition.parameters.clone(),
+        function: None,
+        vector_store_ids: Some(vec![]),
+        container: None,
+        server_label: None,
+        server_url: None,
+        headers: None,
+        partial_images: None,
+        require_approval: None,
+    }
+}
+
+impl From<OAIResponse> for LlmResponse {
+    fn from(response: OAIResponse) -> Self {
+        LlmResponse {
+            id: response.id().to_string(),
+            text: response.output_text(),
+            tool_calls: ResponseExt::tool_calls(&response),
+            usage: response.usage.as_ref().map(|usage| TokenUsage {
+                input_tokens: u64::from(usage.input_tokens),
+                output_tokens: u64::from(usage.output_tokens),
+            }),
+        }
+    }
 }
 
 impl ResponsesClient {
-    /// Creates a new ResponsesClient with the given Config.
-    pub fn new(config: &Config) -> Result<Self, CoreError> {
+    /// Creates a new ResponsesClient with the given LlmConfig, talking to the API at `base_url`.
+    pub fn new(config: &LlmConfig, base_url: &str) -> Result<Self, CoreError> {
         let api_key = config
-            .openai_api_key()
+            .api_key
+            .clone()
             .ok_or_else(|| CoreError::ConfigurationError("OpenAI API key not found".to_string()))?;
-        let client = OAIClient::new(&api_key).map_err(|e| {
+        let sdk_config = OAIConfig::new(&api_key).with_base_url(base_url);
+        let client = OAIClient::new_with_config(sdk_config).map_err(|e| {
             CoreError::ConfigurationError(format!(
                 "Failed to create OpenAI Responses client: {}",
                 e
             ))
         })?;
-        Ok(ResponsesClient { client })
+        Ok(ResponsesClient {
+            client,
+            http: reqwest::Client::new(),
+            url: format!("{}/responses", base_url.trim_end_matches('/')),
+            api_key,
+            model: config.model.clone(),
+            temperature: config.temperature,
+            timeout: config.timeout,
+            system_prompt: config.system_prompt.clone(),
+        })
+    }
+
+    fn timeout_error(&self) -> CoreError {
+        CoreError::Other(format!(
+            "No response from {} after {}s",
+            self.model,
+            self.timeout.as_secs()
+        ))
+    }
+
+    /// Send a request, giving up after the configured timeout.
+    async fn create<F, E>(&self, request: F) -> Result<OAIResponse, CoreError>
+    where
+        F: Future<Output = Result<OAIResponse, E>>,
+        E: Display,
+    {
+        tokio::time::timeout(self.timeout, request)
+            .await
+            .map_err(|_| self.timeout_error())?
+            .map_err(|e| CoreError::Other(format!("Failed to create response: {}", e)))
+    }
+
+    /// Build the request for a prompt, or for the outputs of the tool calls of `response_id`.
+    fn request(
+        &self,
+        user_input: Option<&str>,
+        tools: Option<Vec<Tool>>,
+        previous_response_id: Option<String>,
+        function_outputs: Option<(String, Vec<(String, String)>)>, // (response_id, outputs)
+    ) -> Result<Request, CoreError> {
+        let mut request_builder = Request::builder().model(Model::Custom(self.model.clone()));
+
+        match function_outputs {
+            // Submit tool outputs and continue conversation using the exact pattern from demo
+            Some((response_id, outputs)) => {
+                request_builder = request_builder
+                    .with_function_outputs(response_id, outputs)
+                    .instructions(self.system_prompt.clone()) // CRITICAL: Include system instructions for function output processing
+                    .tools(tools.unwrap_or_default()); // Keep tools available for potential follow-ups
+            }
+            // Regular response generation (with or without tools and continuity)
+            None => {
+                let user_input = user_input.ok_or_else(|| {
+                    CoreError::Other(
+                        "user_input is required when not submitting function outputs".to_string(),
+                    )
+                })?;
+
+                request_builder = request_builder
+                    .input(user_input)
+                    .instructions(self.system_prompt.clone());
+
+                // Add tools if provided
+                if let Some(tools_vec) = tools {
+                    request_builder = request_builder
+                        .tools(tools_vec)
+                        .tool_choice(ToolChoice::auto());
+                }
+
+                // Add conversation continuity if we have a previous conversation
+                if let Some(prev_id) = previous_response_id {
+                    request_builder = request_builder.previous_response_id(prev_id);
+                }
+            }
+        }
+
+        if let Some(temperature) = self.temperature {
+            request_builder = request_builder.temperature(temperature);
+        }
+
+        Ok(request_builder.build())
+    }
+
+    /// Stream a request, reporting the output text to `on_text` as it grows, and return the
+    /// completed response.
+    pub async fn stream_response(
+        &self,
+        request: Request,
+        on_text: &TextSink,
+    ) -> Result<OAIResponse, CoreError> {
+        let mut body = serde_json::to_value(&request)
+            .map_err(|e| CoreError::Other(format!("Invalid request: {}", e)))?;
+        body["stream"] = Value::Bool(true);
+
+        let stream = async {
+            let response = self
+                .http
+                .post(&self.url)
+                .bearer_auth(&self.api_key)
+                .json(&body)
+                .send()
+                .await
+                .map_err(|e| CoreError::Other(format!("Failed to create response: {}", e)))?;
+
+            let status = response.status();
+
+            if !status.is_success() {
+                let text = response.text().await.unwrap_or_default();
+                return Err(CoreError::Other(format!(
+                    "Failed to create response: {} {}",
+                    status, text
+                )));
+            }
+
+            let mut text = String::new();
+            let mut completed = None;
+
+            read_events(response, |event| {
+                match event["type"].as_str().unwrap_or_default() {
+                    "response.output_text.delta" => {
+                        text.push_str(event["delta"].as_str().unwrap_or_default());
+                        on_text(&text);
+                    }
+                    "response.completed" => {
+                        completed = Some(
+                            serde_json::from_value::<OAIResponse>(event["response"].clone())
+                                .map_err(|e| {
+                                    CoreError::Other(format!("Invalid response: {}", e))
+                                })?,
+                        );
+                    }
+                    "response.failed" | "response.incomplete" | "error" => {
+                        return Err(CoreError::Other(format!("Response failed: {}", event)));
+                    }
+                    _ => {}
+                }
+
+                Ok(())
+            })
+            .await?;
+
+            completed.ok_or_else(|| {
+                CoreError::Other("The response stream ended before completing".to_string())
+            })
+        };
+
+        let response = tokio::time::timeout(self.timeout, stream)
+            .await
+            .map_err(|_| self.timeout_error())??;
+
+        Ok(response)
     }
 
     /// Unified method to generate responses with support for:
@@ -38,45 +228,45 @@ impl ResponsesClient {
     /// - Tool/function calling (with tools)
     /// - Conversation continuity (with previous_response_id)
     /// - Function output submission (with response_id and function_outputs)
-    /// 
+    ///
     /// # Usage Examples:
-    /// 
+    ///
     /// Basic text response:
     /// ```ignore
     /// let response = client.generate_response(
-    ///     Some("Hello"), 
-    ///     None, 
-    ///     None, 
+    ///     Some("Hello"),
+    ///     None,
+    ///     None,
     ///     None
     /// ).await?;
     /// ```
-    /// 
+    ///
     /// With tools (function calling):
     /// ```ignore
     /// let response = client.generate_response(
-    ///     Some("What's my balance?"), 
-    ///     Some(tools), 
-    ///     None, 
+    ///     Some("What's my balance?"),
+    ///     Some(tools),
+    ///     None,
     ///     None
     /// ).await?;
     /// ```
-    /// 
+    ///
     /// With conversation continuity:
     /// ```ignore
     /// let response = client.generate_response(
-    ///     Some("Continue our chat"), 
-    ///     Some(tools), 
-    ///     Some("prev_response_id".to_string()), 
+    ///     Some("Continue our chat"),
+    ///     Some(tools),
+    ///     Some("prev_response_id".to_string()),
     ///     None
     /// ).await?;
     /// ```
-    /// 
+    ///
     /// Submit function outputs:
     /// ```ignore
     /// let response = client.generate_response(
-    ///     None, 
-    ///     Some(tools), 
-    ///     None, 
+    ///     None,
+    ///     Some(tools),
+    ///     None,
     ///     Some(("response_id".to_string(), vec![("call_id".to_string(), "output".to_string())]))
     /// ).await?;
     /// ```
@@ -87,77 +277,17 @@ impl ResponsesClient {
         previous_response_id: Option<String>,
         function_outputs: Option<(String, Vec<(String, String)>)>, // (response_id, outputs)
     ) -> Result<OAIResponse, CoreError> {
-        
-        // Handle function output submission case
-        if let Some((response_id, outputs)) = function_outputs {
-            return self.submit_function_outputs(response_id, outputs, tools.unwrap_or_default()).await;
-        }
-
-        // Regular response generation (with or without tools and continuity)
-        let user_input = user_input.ok_or_else(|| 
-            CoreError::Other("user_input is required when not submitting function outputs".to_string())
-        )?;
-
-        let mut request_builder = Request::builder()
-            .model(Model::GPT41Mini)
-            .input(user_input)
-            .instructions(SYSTEM_PROMPT);
+        let request = self.request(user_input, tools, previous_response_id, function_outputs)?;
 
-        // Add tools if provided
-        if let Some(tools_vec) = tools {
-            request_builder = request_builder
-                .tools(tools_vec)
-                .tool_choice(ToolChoice::auto());
-        }
-
-        // Add conversation continuity if we have a previous conversation
-        if let Some(prev_id) = previous_response_id {
-            request_builder = request_builder.previous_response_id(prev_id);
-        }
-
-        let request = request_builder.build();
-
-        let response = self
-            .client
-            .responses
-            .create(request)
-            .await
-            .map_err(|e| CoreError::Other(format!("Failed to create response: {}", e)))?;
+        let response = self.create(self.client.responses.create(request)).await?;
 
         println!("generate_response result: {:?}", response);
 
         Ok(response)
     }
 
-    /// Internal helper method for submitting function outputs
-    async fn submit_function_outputs(
-        &self,
-        response_id: String,
-        function_outputs: Vec<(String, String)>, // (call_id, output)
-        tools: Vec<Tool>,
-    ) -> Result<OAIResponse, CoreError> {
-        // Submit tool outputs and continue conversation using the exact pattern from demo
-        let continuation_request = Request::builder()
-            .model(Model::GPT41Mini)
-            .with_function_outputs(response_id, function_outputs)
-            .instructions(SYSTEM_PROMPT) // CRITICAL: Include system instructions for function output processing
-            .tools(tools) // Keep tools available for potential follow-ups
-            .build();
-
-        let response = self
-            .client
-            .responses
-            .create(continuation_request)
-            .await
-            .map_err(|e| CoreError::Other(format!("Failed to create response: {}", e)))?;
-
-        println!("submit_function_outputs response: {:?}", response);
-
-        Ok(response)
-    }
-
     // DEPRECATED: These methods remain for backward compatibility but delegate to generate_response()
-    
+
     /// DEPRECATED: Use generate_response() instead
     #[deprecated(note = "Use generate_response() with appropriate parameters instead")]
     pub async fn generate_with_tools(
@@ -165,7 +295,8 @@ impl ResponsesClient {
         user_input: &str,
         tools: Vec<Tool>,
     ) -> Result<OAIResponse, CoreError> {
-        self.generate_response(Some(user_input), Some(tools), None, None).await
+        self.generate_response(Some(user_input), Some(tools), None, None)
+            .await
     }
 
     /// DEPRECATED: Use generate_response() instead
@@ -176,7 +307,8 @@ impl ResponsesClient {
         tools: Vec<Tool>,
         previous_response_id: Option<String>,
     ) -> Result<OAIResponse, CoreError> {
-        self.generate_response(Some(user_input), Some(tools), previous_response_id, None).await
+        self.generate_response(Some(user_input), Some(tools), previous_response_id, None)
+            .await
     }
 
     /// DEPRECATED: Use generate_response() instead
@@ -187,38 +319,127 @@ impl ResponsesClient {
         function_outputs: Vec<(String, String)>, // (call_id, output)
         tools: Vec<Tool>,
     ) -> Result<OAIResponse, CoreError> {
-        self.generate_response(None, Some(tools), None, Some((response_id, function_outputs))).await
+        self.generate_response(
+            None,
+            Some(tools),
+            None,
+            Some((response_id, function_outputs)),
+        )
+        .await
     }
 
     // CONVENIENCE METHODS - These make common use cases easier to call
-    
+
     /// Simple text-only response (no tools, no continuity)
     pub async fn simple_response(&self, user_input: &str) -> Result<OAIResponse, CoreError> {
-        self.generate_response(Some(user_input), None, None, None).await
+        self.generate_response(Some(user_input), None, None, None)
+            .await
     }
-    
+
     /// Response with tools enabled (for function calling)
-    pub async fn with_tools(&self, user_input: &str, tools: Vec<Tool>) -> Result<OAIResponse, CoreError> {
-        self.generate_response(Some(user_input), Some(tools), None, None).await
+    pub async fn with_tools(
+        &self,
+        user_input: &str,
+        tools: Vec<Tool>,
+    ) -> Result<OAIResponse, CoreError> {
+        self.generate_response(Some(user_input), Some(tools), None, None)
+            .await
     }
-    
+
     /// Continue conversation with tools
     pub async fn continue_conversation(
-        &self, 
-        user_input: &str, 
-        tools: Vec<Tool>, 
-        previous_response_id: String
+        &self,
+        user_input: &str,
+        tools: Vec<Tool>,
+        previous_response_id: String,
     ) -> Result<OAIResponse, CoreError> {
-        self.generate_response(Some(user_input), Some(tools), Some(previous_response_id), None).await
+        self.generate_response(
+            Some(user_input),
+            Some(tools),
+            Some(previous_response_id),
+            None,
+        )
+        .await
     }
-    
+
     /// Submit function outputs and continue
     pub async fn submit_outputs(
-        &self, 
-        response_id: String, 
-        outputs: Vec<(String, String)>, 
-        tools: Vec<Tool>
+        &self,
+        response_id: String,
+        outputs: Vec<(String, String)>,
+        tools: Vec<Tool>,
     ) -> Result<OAIResponse, CoreError> {
-        self.generate_response(None, Some(tools), None, Some((response_id, outputs))).await
+        self.generate_response(None, Some(tools), None, Some((response_id, outputs)))
+            .await
+    }
+}
+
+#[async_trait]
+impl LlmBackend for ResponsesClient {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn generate(
+        &self,
+        input: &str,
+        tools: &[ToolDefinition],
+        previous_response_id: Option<String>,
+    ) -> Result<LlmResponse, CoreError> {
+        let tools = (!tools.is_empty()).then(|| tools.iter().map(to_tool).collect());
+
+        self.generate_response(Some(input), tools, previous_response_id, None)
+            .await
+            .map(LlmResponse::from)
+    }
+
+    async fn submit_tool_outputs(
+        &self,
+        response_id: &str,
+        outputs: Vec<(String, String)>,
+        tools: &[ToolDefinition],
+    ) -> Result<LlmResponse, CoreError> {
+        self.generate_response(
+            None,
+            Some(tools.iter().map(to_tool).collect()),
+            None,
+            Some((response_id.to_string(), outputs)),
+        )
+        .await
+        .map(LlmResponse::from)
+    }
+
+    async fn generate_streaming(
+        &self,
+        input: &str,
+        tools: &[ToolDefinition],
+        previous_response_id: Option<String>,
+        on_text: &TextSink,
+    ) -> Result<LlmResponse, CoreError> {
+        let tools = (!tools.is_empty()).then(|| tools.iter().map(to_tool).collect());
+        let request = self.request(Some(input), tools, previous_response_id, None)?;
+
+        self.stream_response(request, on_text)
+            .await
+            .map(LlmResponse::from)
+    }
+
+    async fn submit_tool_outputs_streaming(
+        &self,
+        response_id: &str,
+        outputs: Vec<(String, String)>,
+        tools: &[ToolDefinition],
+        on_text: &TextSink,
+    ) -> Result<LlmResponse, CoreError> {
+        let request = self.request(
+            None,
+            Some(tools.iter().map(to_tool).collect()),
+            None,
+            Some((response_id.to_string(), outputs)),
+        )?;
+
+        self.stream_response(request, on_text)
+            .await
+            .map(LlmResponse::from)
     }
 }
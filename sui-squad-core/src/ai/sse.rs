@@ -0,0 +1,153 @@
+use reqwest::Response;
+use serde_json::Value;
+
+use crate::error::CoreError;
+
+/// JSON `data` of one server-sent event, `None` for comments, keep-alives and `[DONE]`.
+fn event_data(event: &[u8]) -> Result<Option<Value>, CoreError> {
+    let event = String::from_utf8_lossy(event);
+
+    let data = event
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(|data| data.trim_start())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if data.is_empty() || data == "[DONE]" {
+        return Ok(None);
+    }
+
+    serde_json::from_str(&data)
+        .map(Some)
+        .map_err(|e| CoreError::Other(format!("Invalid stream event {}: {}", data, e)))
+}
+
+/// Splits a byte stream into server-sent events, wherever its chunks happen to be cut.
+#[derive(Default)]
+struct EventBuffer {
+    buffer: Vec<u8>,
+}
+
+impl EventBuffer {
+    /// Add a chunk, returning the data of the events it completes.
+    fn push(&mut self, chunk: &[u8]) -> Result<Vec<Value>, CoreError> {
+        self.buffer
+            .extend(chunk.iter().filter(|byte| **byte != b'\r'));
+
+        let mut events = Vec::new();
+
+        while let Some(end) = self.buffer.windows(2).position(|window| window == b"\n\n") {
+            let event: Vec<u8> = self.buffer.drain(..end + 2).collect();
+
+            if let Some(data) = event_data(&event)? {
+                events.push(data);
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Data of the last event, when the stream ends without a blank line after it.
+    fn finish(self) -> Result<Option<Value>, CoreError> {
+        event_data(&self.buffer)
+    }
+}
+
+/// Read a streamed response body, handing the JSON data of each event to `on_event` in order.
+pub async fn read_events(
+    mut response: Response,
+    mut on_event: impl FnMut(Value) -> Result<(), CoreError> + Send,
+) -> Result<(), CoreError> {
+    let mut events = EventBuffer::default();
+
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| CoreError::Other(format!("Stream interrupted: {}", e)))?
+    {
+        for data in events.push(&chunk)? {
+            on_event(data)?;
+        }
+    }
+
+    if let Some(data) = events.finish()? {
+        on_event(data)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    /// Feed `chunks` in order and collect every event, including a trailing unterminated one.
+    fn read_chunks(chunks: &[&str]) -> Result<Vec<Value>, CoreError> {
+        let mut events = EventBuffer::default();
+        let mut data = Vec::new();
+
+        for chunk in chunks {
+            data.extend(events.push(chunk.as_bytes())?);
+        }
+
+        data.extend(events.finish()?);
+
+        Ok(data)
+    }
+
+    #[test]
+    fn reads_events_in_order() {
+        let events = read_chunks(&["data: {\"n\":1}\n\ndata: {\"n\":2}\n\n"]).unwrap();
+
+        assert_eq!(events, vec![json!({"n": 1}), json!({"n": 2})]);
+    }
+
+    #[test]
+    fn joins_events_split_across_chunks() {
+        let events =
+            read_chunks(&["da", "ta: {\"te", "xt\":\"hi\"}\n", "\ndata: {}", "\n\n"]).unwrap();
+
+        assert_eq!(events, vec![json!({"text": "hi"}), json!({})]);
+    }
+
+    #[test]
+    fn accepts_crlf_line_endings() {
+        let events = read_chunks(&[
+            "event: delta\r\ndata: {\"n\":1}\r",
+            "\n\r\ndata: {\"n\":2}\r\n\r\n",
+        ])
+        .unwrap();
+
+        assert_eq!(events, vec![json!({"n": 1}), json!({"n": 2})]);
+    }
+
+    #[test]
+    fn skips_done_comments_and_keep_alives() {
+        let events =
+            read_chunks(&[": keep-alive\n\ndata: {\"n\":1}\n\n\n\ndata: [DONE]\n\n"]).unwrap();
+
+        assert_eq!(events, vec![json!({"n": 1})]);
+    }
+
+    #[test]
+    fn joins_multi_line_data() {
+        let events = read_chunks(&["data: {\"a\":\n", "data: 1,\ndata: \"b\": 2}\n\n"]).unwrap();
+
+        assert_eq!(events, vec![json!({"a": 1, "b": 2})]);
+    }
+
+    #[test]
+    fn reads_an_unterminated_last_event() {
+        let events = read_chunks(&["data: {\"n\":1}\n\ndata: {\"n\":2}"]).unwrap();
+
+        assert_eq!(events, vec![json!({"n": 1}), json!({"n": 2})]);
+    }
+
+    #[test]
+    fn rejects_invalid_json() {
+        assert!(read_chunks(&["data: {not json}\n\n"]).is_err());
+    }
+}
@@ -1,11 +1,138 @@
-use crate::sui_gateway::SuiGateway;
-
-/// Handle admin-specific commands, calling gateway methods as needed.
-pub async fn handle_admin_command<G: SuiGateway>(
-    _command: &str,
-    _gateway: G,
-    // TODO: add parameters for command context
-) -> String {
-    // TODO: implement admin command logic, e.g., create pool, reward top users
-    String::new()
-} 
\ No newline at end of file
+use std::str::FromStr;
+
+use teloxide::macros::BotCommands;
+
+/// Operator-only commands, dispatched through their own branch of the bot handler tree.
+#[derive(BotCommands, Clone, Debug)]
+#[command(rename_rule = "lowercase", description = "Operator commands:")]
+pub enum AdminCommand {
+    #[command(
+        description = "Operator commands: stats, user <telegram id>, pause, resume, emergency, unemergency, freeze <telegram id>, unfreeze <telegram id>, relayer add|remove <address>, quota user|chat <id> <tokens|unlimited|default>, broadcast <message>."
+    )]
+    Admin(String),
+}
+
+/// Whose AI usage a quota applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaScope {
+    User,
+    Chat,
+}
+
+/// Daily AI token quota set by an operator for one user or chat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaOverride {
+    Tokens(u64),
+    Unlimited,
+    /// Back to the configured default.
+    Default,
+}
+
+const QUOTA_USAGE: &str = "Usage: /admin quota user|chat [id] [tokens|unlimited|default]";
+
+fn parse_quota(args: &str) -> Result<AdminAction, String> {
+    let parts: Vec<&str> = args.split_whitespace().collect();
+
+    let [scope, id, quota] = parts.as_slice() else {
+        return Err(QUOTA_USAGE.to_string());
+    };
+
+    let scope = match scope.to_lowercase().as_str() {
+        "user" => QuotaScope::User,
+        "chat" | "group" => QuotaScope::Chat,
+        _ => return Err(QUOTA_USAGE.to_string()),
+    };
+
+    let id = id
+        .parse::<i64>()
+        .map_err(|_| format!("Invalid id: {}", id))?;
+
+    let quota = match quota.to_lowercase().as_str() {
+        "unlimited" => QuotaOverride::Unlimited,
+        "default" => QuotaOverride::Default,
+        tokens => QuotaOverride::Tokens(
+            tokens
+                .parse::<u64>()
+                .map_err(|_| format!("Invalid quota: {}", quota))?,
+        ),
+    };
+
+    Ok(AdminAction::Quota { scope, id, quota })
+}
+
+/// Action requested through `/admin <action> [args]`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AdminAction {
+    Stats,
+    User(String),
+    Pause,
+    Resume,
+    ChainPause,
+    ChainResume,
+    Freeze(String),
+    Unfreeze(String),
+    AddRelayer(String),
+    RemoveRelayer(String),
+    Quota {
+        scope: QuotaScope,
+        id: i64,
+        quota: QuotaOverride,
+    },
+    Broadcast(String),
+    Help,
+}
+
+impl FromStr for AdminAction {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+        let (action, args) = match input.split_once(char::is_whitespace) {
+            Some((action, args)) => (action, args.trim()),
+            None => (input, ""),
+        };
+
+        match action.to_lowercase().as_str() {
+            "" | "help" => Ok(AdminAction::Help),
+            "stats" => Ok(AdminAction::Stats),
+            "pause" => Ok(AdminAction::Pause),
+            "resume" | "unpause" => Ok(AdminAction::Resume),
+            "emergency" => Ok(AdminAction::ChainPause),
+            "unemergency" => Ok(AdminAction::ChainResume),
+            "freeze" if !args.is_empty() => Ok(AdminAction::Freeze(args.to_string())),
+            "freeze" => Err("Usage: /admin freeze [telegram id]".to_string()),
+            "unfreeze" if !args.is_empty() => Ok(AdminAction::Unfreeze(args.to_string())),
+            "unfreeze" => Err("Usage: /admin unfreeze [telegram id]".to_string()),
+            "user" if !args.is_empty() => Ok(AdminAction::User(args.to_string())),
+            "user" => Err("Usage: /admin user [telegram id]".to_string()),
+            "relayer" => match args.split_once(char::is_whitespace) {
+                Some(("add", address)) => Ok(AdminAction::AddRelayer(address.trim().to_string())),
+                Some(("remove", address)) => {
+                    Ok(AdminAction::RemoveRelayer(address.trim().to_string()))
+                }
+                _ => Err("Usage: /admin relayer add|remove [address]".to_string()),
+            },
+            "quota" => parse_quota(args),
+            "broadcast" if !args.is_empty() => Ok(AdminAction::Broadcast(args.to_string())),
+            "broadcast" => Err("Usage: /admin broadcast [message]".to_string()),
+            other => Err(format!("Unknown admin command: {}", other)),
+        }
+    }
+}
+
+/// Help text listing the operator commands.
+pub fn admin_help() -> String {
+    "🛠️ Operator commands:\n\n\
+     /admin stats - Operational statistics\n\
+     /admin user [telegram id] - Account, balance and limits of a user\n\
+     /admin pause - Stop the server from signing transactions\n\
+     /admin resume - Resume signing transactions\n\
+     /admin emergency - Pause the contract on-chain (payments, withdrawals, new accounts)\n\
+     /admin unemergency - Lift the on-chain emergency pause\n\
+     /admin freeze [telegram id] - Freeze a user's account on-chain\n\
+     /admin unfreeze [telegram id] - Unfreeze a user's account\n\
+     /admin relayer add|remove [address] - Authorize or revoke a relayer key on-chain\n\
+     /admin quota user|chat [id] [tokens|unlimited|default] - Override a daily AI token quota\n\
+     /admin broadcast [message] - Send a message to every known user"
+        .to_string()
+}
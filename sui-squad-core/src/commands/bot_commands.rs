@@ -18,10 +18,22 @@ pub enum Command {
     Help,
     #[command(description = "Fund your account.")]
     Fund,
-}
-
-#[derive(Debug, Clone, Default)]
-pub enum LoginState {
-    #[default]
-    Login,
+    #[command(description = "Show your Squad account, zkLogin address and balances.")]
+    Wallet,
+    #[command(description = "Show or lower your spending limits, e.g. /limits daily 5.")]
+    Limits(String),
+    #[command(
+        description = "Manage saved withdrawal addresses: add <label> <address>, list, remove <label>."
+    )]
+    AddressBook(String),
+    #[command(description = "Enable self-custody or withdraw with your own Google login.")]
+    Custody,
+    #[command(description = "Forget the current AI conversation and start fresh.")]
+    Reset,
+    #[command(
+        description = "Show or set how long the AI remembers this chat: <minutes>, off or default."
+    )]
+    Memory(String),
+    #[command(description = "Show how much of your daily AI quota is left.")]
+    Quota,
 }
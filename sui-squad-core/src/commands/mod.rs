@@ -1,5 +1,6 @@
 pub mod admin;
-pub mod user;
 pub mod bot_commands;
+pub mod user;
+pub mod wizard;
 
-// Add shared command enums or parsing helpers here. 
\ No newline at end of file
+// Add shared command enums or parsing helpers here.
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use teloxide::macros::BotCommands;
+
+/// Commands driving the guided multi-step flows, dispatched through their own dialogue branch.
+#[derive(BotCommands, Clone, Debug)]
+#[command(rename_rule = "lowercase", description = "Guided flows:")]
+pub enum WizardCommand {
+    #[command(description = "Send tokens to another user step by step.")]
+    Send,
+    #[command(description = "Withdraw tokens to a Sui address step by step.")]
+    Withdraw,
+    #[command(description = "Cancel the current guided flow.")]
+    Cancel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum WizardFlow {
+    Send,
+    Withdraw,
+}
+
+/// Step a guided flow is waiting on; recipient → amount → token → confirm.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum WizardStep {
+    Recipient,
+    Amount,
+    Token,
+    Confirm,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Wizard {
+    pub flow: WizardFlow,
+    pub step: WizardStep,
+    /// Username for `/send`, resolved address for `/withdraw`.
+    pub recipient: Option<String>,
+    /// Amount as typed by the user, converted with the token's decimals once it is chosen.
+    pub amount: Option<String>,
+    /// Symbol of the chosen token.
+    pub token: Option<String>,
+    /// Unix time in milliseconds of the last step, used to time the flow out.
+    pub updated_at: i64,
+}
+
+/// Per-chat dialogue state, persisted between updates and restarts.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub enum DialogueState {
+    #[default]
+    Idle,
+    Wizard(Wizard),
+}
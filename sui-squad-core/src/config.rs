@@ -1,10 +1,13 @@
 use dotenvy::dotenv;
 use std::env;
 
+use crate::permissions::admin_ids_from_env;
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub teloxide_token: String,
     pub openai_api_key: Option<String>,
+    pub admin_ids: Vec<i64>,
 }
 
 impl Config {
@@ -12,13 +15,19 @@ impl Config {
         dotenv().ok();
         let teloxide_token = env::var("TELOXIDE_TOKEN").expect("TELOXIDE_TOKEN must be set");
         let openai_api_key = env::var("OPENAI_API_KEY").ok();
+        let admin_ids = admin_ids_from_env();
         Config {
             teloxide_token,
             openai_api_key,
+            admin_ids,
         }
     }
 
     pub fn openai_api_key(&self) -> Option<String> {
         self.openai_api_key.clone()
     }
+
+    pub fn admin_ids(&self) -> &[i64] {
+        &self.admin_ids
+    }
 }
@@ -1,46 +1,286 @@
-use std::collections::HashMap;
-use std::time::{Duration, Instant};
-use tokio::sync::RwLock;
-use std::sync::Arc;
+use std::{env, sync::Arc, time::Duration};
 
-#[derive(Clone)]
-pub struct ConversationEntry {
-    pub response_id: String,
-    pub last_activity: Instant,
+use chrono::Utc;
+
+use crate::error::CoreError;
+
+use super::{ConversationEntry, ConversationKey, ConversationStore};
+
+/// Bounds of the conversation memory, loaded from `CONVERSATION_TTL_SECS`,
+/// `CONVERSATION_MAX_TTL_SECS` and `CONVERSATION_MAX_ENTRIES`.
+#[derive(Debug, Clone)]
+pub struct ConversationPolicy {
+    /// How long a thread is continued after its last turn, unless the chat overrides it.
+    pub ttl: Duration,
+    /// Longest TTL a chat can configure.
+    pub max_ttl: Duration,
+    /// Threads kept at most; the least recently used are evicted beyond it.
+    pub max_entries: usize,
+}
+
+fn env_number(name: &str, default: u64) -> u64 {
+    env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+impl ConversationPolicy {
+    pub fn from_env() -> Self {
+        Self {
+            ttl: Duration::from_secs(env_number("CONVERSATION_TTL_SECS", 600)),
+            max_ttl: Duration::from_secs(env_number("CONVERSATION_MAX_TTL_SECS", 7 * 24 * 3600)),
+            max_entries: env_number("CONVERSATION_MAX_ENTRIES", 10_000) as usize,
+        }
+    }
 }
 
+/// Tracks the last response of each `(user_id, chat_id)` thread so prompts continue the
+/// conversation until it sits idle for longer than the chat's TTL.
+///
+/// Storage failures are logged and treated as a fresh conversation rather than failing the prompt.
 #[derive(Clone)]
 pub struct ConversationCache {
-    cache: Arc<RwLock<HashMap<(String, String), ConversationEntry>>>,
-    ttl: Duration,
+    store: Arc<dyn ConversationStore>,
+    policy: ConversationPolicy,
 }
 
 impl ConversationCache {
-    pub fn new(ttl: Duration) -> Self {
-        Self {
-            cache: Arc::new(RwLock::new(HashMap::new())),
-            ttl,
+    pub fn new(store: Arc<dyn ConversationStore>, policy: ConversationPolicy) -> Self {
+        Self { store, policy }
+    }
+
+    pub fn policy(&self) -> &ConversationPolicy {
+        &self.policy
+    }
+
+    /// TTL of a chat: its own setting, or the default. A zero TTL disables conversation memory.
+    pub async fn ttl(&self, chat_id: &str) -> Duration {
+        match self.store.chat_ttl(chat_id).await {
+            Ok(Some(ttl_secs)) => Duration::from_secs(ttl_secs).min(self.policy.max_ttl),
+            Ok(None) => self.policy.ttl,
+            Err(e) => {
+                println!(
+                    "❌ Failed to read the conversation TTL of {}: {}",
+                    chat_id, e
+                );
+                self.policy.ttl
+            }
         }
     }
 
-    pub async fn get(&self, key: &(String, String)) -> Option<String> {
-        let cache = self.cache.read().await;
-        cache.get(key)
-            .filter(|entry| entry.last_activity.elapsed() < self.ttl)
-            .map(|entry| entry.response_id.clone())
+    /// Override the TTL of a chat, or go back to the default with `None`.
+    pub async fn set_ttl(
+        &self,
+        chat_id: &str,
+        ttl: Option<Duration>,
+    ) -> Result<Duration, CoreError> {
+        if let Some(ttl) = ttl {
+            if ttl > self.policy.max_ttl {
+                return Err(CoreError::Other(format!(
+                    "The conversation memory can last at most {} minutes",
+                    self.policy.max_ttl.as_secs() / 60
+                )));
+            }
+        }
+
+        self.store
+            .set_chat_ttl(chat_id, ttl.map(|ttl| ttl.as_secs()))
+            .await?;
+
+        Ok(ttl.unwrap_or(self.policy.ttl))
+    }
+
+    fn is_expired(entry: &ConversationEntry, ttl: Duration, now: i64) -> bool {
+        now - entry.last_activity >= ttl.as_millis() as i64
     }
 
-    pub async fn update(&self, key: (String, String), response_id: String) {
-        let mut cache = self.cache.write().await;
-        cache.insert(key, ConversationEntry {
+    pub async fn get(&self, key: &ConversationKey) -> Option<String> {
+        let ttl = self.ttl(&key.1).await;
+
+        let entry = match self.store.get(key).await {
+            Ok(entry) => entry?,
+            Err(e) => {
+                println!("❌ Failed to load conversation: {}", e);
+                return None;
+            }
+        };
+
+        if Self::is_expired(&entry, ttl, Utc::now().timestamp_millis()) {
+            return None;
+        }
+
+        Some(entry.response_id)
+    }
+
+    pub async fn update(&self, key: ConversationKey, response_id: String) {
+        if self.ttl(&key.1).await.is_zero() {
+            return;
+        }
+
+        let entry = ConversationEntry {
             response_id,
-            last_activity: Instant::now(),
-        });
+            last_activity: Utc::now().timestamp_millis(),
+        };
+
+        if let Err(e) = self.store.insert(key, entry).await {
+            println!("❌ Failed to save conversation: {}", e);
+        }
     }
 
-    pub async fn cleanup_expired(&self) {
-        let mut cache = self.cache.write().await;
-        let now = Instant::now();
-        cache.retain(|_, entry| now.duration_since(entry.last_activity) < self.ttl);
+    /// Forget a thread so the next prompt starts a new conversation; returns whether one was active.
+    pub async fn reset(&self, key: &ConversationKey) -> Result<bool, CoreError> {
+        let active = self.get(key).await.is_some();
+
+        self.store.remove(key).await?;
+
+        Ok(active)
     }
-} 
\ No newline at end of file
+
+    /// Drop expired threads, then the least recently used ones beyond `max_entries`.
+    ///
+    /// Returns how many threads were removed.
+    pub async fn cleanup_expired(&self) -> Result<usize, CoreError> {
+        let now = Utc::now().timestamp_millis();
+        let mut entries = Vec::new();
+        let mut removed = 0;
+
+        for (key, entry) in self.store.entries().await? {
+            let ttl = self.ttl(&key.1).await;
+
+            if Self::is_expired(&entry, ttl, now) {
+                self.store.remove(&key).await?;
+                removed += 1;
+            } else {
+                entries.push((key, entry));
+            }
+        }
+
+        if entries.len() > self.policy.max_entries {
+            entries.sort_by_key(|(_, entry)| entry.last_activity);
+
+            let excess = entries.len() - self.policy.max_entries;
+
+            for (key, _) in entries.iter().take(excess) {
+                self.store.remove(key).await?;
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conversation::InMemoryConversationStore;
+
+    use super::*;
+
+    fn cache(max_entries: usize) -> (ConversationCache, Arc<InMemoryConversationStore>) {
+        let store = Arc::new(InMemoryConversationStore::default());
+
+        let policy = ConversationPolicy {
+            ttl: Duration::from_secs(60),
+            max_ttl: Duration::from_secs(3600),
+            max_entries,
+        };
+
+        (ConversationCache::new(store.clone(), policy), store)
+    }
+
+    fn key(user_id: &str) -> ConversationKey {
+        (user_id.to_string(), "chat".to_string())
+    }
+
+    async fn insert_idle(store: &InMemoryConversationStore, user_id: &str, idle_secs: i64) {
+        let entry = ConversationEntry {
+            response_id: format!("resp_{}", user_id),
+            last_activity: Utc::now().timestamp_millis() - idle_secs * 1000,
+        };
+
+        store.insert(key(user_id), entry).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn continues_threads_until_the_ttl_expires() {
+        let (cache, store) = cache(10);
+
+        insert_idle(&store, "active", 30).await;
+        insert_idle(&store, "idle", 61).await;
+
+        assert_eq!(
+            cache.get(&key("active")).await.as_deref(),
+            Some("resp_active")
+        );
+        assert_eq!(cache.get(&key("idle")).await, None);
+
+        assert_eq!(cache.cleanup_expired().await.unwrap(), 1);
+        assert!(store.get(&key("idle")).await.unwrap().is_none());
+        assert!(store.get(&key("active")).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn evicts_the_least_recently_used_threads_beyond_max_entries() {
+        let (cache, store) = cache(2);
+
+        insert_idle(&store, "oldest", 30).await;
+        insert_idle(&store, "older", 20).await;
+        insert_idle(&store, "newest", 10).await;
+
+        assert_eq!(cache.cleanup_expired().await.unwrap(), 1);
+
+        assert!(store.get(&key("oldest")).await.unwrap().is_none());
+        assert!(store.get(&key("older")).await.unwrap().is_some());
+        assert!(store.get(&key("newest")).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn memory_off_stops_remembering_the_chat() {
+        let (cache, _) = cache(10);
+
+        let ttl = cache.set_ttl("chat", Some(Duration::ZERO)).await.unwrap();
+        assert!(ttl.is_zero());
+
+        cache.update(key("user"), "resp_1".to_string()).await;
+
+        assert_eq!(cache.get(&key("user")).await, None);
+    }
+
+    #[tokio::test]
+    async fn memory_default_restores_the_default_ttl() {
+        let (cache, _) = cache(10);
+
+        cache.set_ttl("chat", Some(Duration::ZERO)).await.unwrap();
+
+        let ttl = cache.set_ttl("chat", None).await.unwrap();
+        assert_eq!(ttl, Duration::from_secs(60));
+        assert_eq!(cache.ttl("chat").await, Duration::from_secs(60));
+
+        cache.update(key("user"), "resp_1".to_string()).await;
+
+        assert_eq!(cache.get(&key("user")).await.as_deref(), Some("resp_1"));
+    }
+
+    #[tokio::test]
+    async fn chat_ttls_apply_per_chat_and_are_capped() {
+        let (cache, store) = cache(10);
+
+        cache
+            .set_ttl("chat", Some(Duration::from_secs(120)))
+            .await
+            .unwrap();
+
+        insert_idle(&store, "user", 90).await;
+
+        assert_eq!(cache.get(&key("user")).await.as_deref(), Some("resp_user"));
+        assert_eq!(cache.ttl("other").await, Duration::from_secs(60));
+
+        assert!(
+            cache
+                .set_ttl("chat", Some(Duration::from_secs(7200)))
+                .await
+                .is_err()
+        );
+    }
+}
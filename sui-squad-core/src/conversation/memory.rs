@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+
+use crate::error::CoreError;
+
+use super::{ConversationEntry, ConversationKey, ConversationStore};
+
+/// Conversations kept in process memory; everything is forgotten on restart.
+#[derive(Default)]
+pub struct InMemoryConversationStore {
+    entries: RwLock<HashMap<ConversationKey, ConversationEntry>>,
+    chat_ttls: RwLock<HashMap<String, u64>>,
+}
+
+#[async_trait]
+impl ConversationStore for InMemoryConversationStore {
+    async fn get(&self, key: &ConversationKey) -> Result<Option<ConversationEntry>, CoreError> {
+        Ok(self.entries.read().await.get(key).cloned())
+    }
+
+    async fn insert(
+        &self,
+        key: ConversationKey,
+        entry: ConversationEntry,
+    ) -> Result<(), CoreError> {
+        self.entries.write().await.insert(key, entry);
+        Ok(())
+    }
+
+    async fn remove(&self, key: &ConversationKey) -> Result<bool, CoreError> {
+        Ok(self.entries.write().await.remove(key).is_some())
+    }
+
+    async fn entries(&self) -> Result<Vec<(ConversationKey, ConversationEntry)>, CoreError> {
+        Ok(self
+            .entries
+            .read()
+            .await
+            .iter()
+            .map(|(key, entry)| (key.clone(), entry.clone()))
+            .collect())
+    }
+
+    async fn chat_ttl(&self, chat_id: &str) -> Result<Option<u64>, CoreError> {
+        Ok(self.chat_ttls.read().await.get(chat_id).copied())
+    }
+
+    async fn set_chat_ttl(&self, chat_id: &str, ttl_secs: Option<u64>) -> Result<(), CoreError> {
+        let mut chat_ttls = self.chat_ttls.write().await;
+
+        match ttl_secs {
+            Some(ttl_secs) => chat_ttls.insert(chat_id.to_string(), ttl_secs),
+            None => chat_ttls.remove(chat_id),
+        };
+
+        Ok(())
+    }
+}
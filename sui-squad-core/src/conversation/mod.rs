@@ -1,2 +1,43 @@
 pub mod cache;
-pub use cache::ConversationCache; 
\ No newline at end of file
+pub mod memory;
+pub mod sled_store;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::error::CoreError;
+
+pub use cache::{ConversationCache, ConversationPolicy};
+pub use memory::InMemoryConversationStore;
+pub use sled_store::SledConversationStore;
+
+/// `(user_id, chat_id)` of an AI conversation thread.
+pub type ConversationKey = (String, String);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationEntry {
+    /// Id of the last response, sent back as `previous_response_id` to continue the thread.
+    pub response_id: String,
+    /// Unix time in milliseconds of the last turn.
+    pub last_activity: i64,
+}
+
+/// Where conversation threads and the per-chat TTL overrides are kept.
+#[async_trait]
+pub trait ConversationStore: Send + Sync + 'static {
+    async fn get(&self, key: &ConversationKey) -> Result<Option<ConversationEntry>, CoreError>;
+
+    async fn insert(&self, key: ConversationKey, entry: ConversationEntry)
+    -> Result<(), CoreError>;
+
+    /// Drop a thread; returns whether there was one.
+    async fn remove(&self, key: &ConversationKey) -> Result<bool, CoreError>;
+
+    /// Every stored thread, used to expire and evict them.
+    async fn entries(&self) -> Result<Vec<(ConversationKey, ConversationEntry)>, CoreError>;
+
+    /// TTL in seconds configured for a chat, if it overrides the default.
+    async fn chat_ttl(&self, chat_id: &str) -> Result<Option<u64>, CoreError>;
+
+    async fn set_chat_ttl(&self, chat_id: &str, ttl_secs: Option<u64>) -> Result<(), CoreError>;
+}
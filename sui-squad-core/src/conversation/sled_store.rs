@@ -0,0 +1,117 @@
+use async_trait::async_trait;
+use sled::{Db, Tree};
+
+use crate::error::CoreError;
+
+use super::{ConversationEntry, ConversationKey, ConversationStore};
+
+const CONVERSATIONS_TREE: &str = "conversations";
+const CHAT_TTL_TREE: &str = "conversation_ttl";
+
+fn storage_error(e: impl ToString) -> CoreError {
+    CoreError::Other(format!("Conversation storage error: {}", e.to_string()))
+}
+
+fn encode_key((user_id, chat_id): &ConversationKey) -> String {
+    format!("{}:{}", user_id, chat_id)
+}
+
+fn decode_key(key: &[u8]) -> Option<ConversationKey> {
+    let key = std::str::from_utf8(key).ok()?;
+    let (user_id, chat_id) = key.split_once(':')?;
+
+    Some((user_id.to_string(), chat_id.to_string()))
+}
+
+/// Conversations kept in sled trees of the bot database, so threads survive restarts.
+pub struct SledConversationStore {
+    conversations: Tree,
+    chat_ttls: Tree,
+}
+
+impl SledConversationStore {
+    pub fn new(db: &Db) -> Result<Self, CoreError> {
+        Ok(Self {
+            conversations: db.open_tree(CONVERSATIONS_TREE).map_err(storage_error)?,
+            chat_ttls: db.open_tree(CHAT_TTL_TREE).map_err(storage_error)?,
+        })
+    }
+}
+
+#[async_trait]
+impl ConversationStore for SledConversationStore {
+    async fn get(&self, key: &ConversationKey) -> Result<Option<ConversationEntry>, CoreError> {
+        match self
+            .conversations
+            .get(encode_key(key))
+            .map_err(storage_error)?
+        {
+            Some(bytes) => serde_json::from_slice(&bytes)
+                .map(Some)
+                .map_err(storage_error),
+            None => Ok(None),
+        }
+    }
+
+    async fn insert(
+        &self,
+        key: ConversationKey,
+        entry: ConversationEntry,
+    ) -> Result<(), CoreError> {
+        let bytes = serde_json::to_vec(&entry).map_err(storage_error)?;
+
+        self.conversations
+            .insert(encode_key(&key), bytes)
+            .map_err(storage_error)?;
+
+        Ok(())
+    }
+
+    async fn remove(&self, key: &ConversationKey) -> Result<bool, CoreError> {
+        Ok(self
+            .conversations
+            .remove(encode_key(key))
+            .map_err(storage_error)?
+            .is_some())
+    }
+
+    async fn entries(&self) -> Result<Vec<(ConversationKey, ConversationEntry)>, CoreError> {
+        let mut entries = Vec::new();
+
+        for entry in self.conversations.iter() {
+            let (key, bytes) = entry.map_err(storage_error)?;
+
+            let parsed =
+                decode_key(&key).zip(serde_json::from_slice::<ConversationEntry>(&bytes).ok());
+
+            match parsed {
+                Some(entry) => entries.push(entry),
+                // Unreadable records would never expire, so they are dropped here
+                None => {
+                    self.conversations.remove(&key).map_err(storage_error)?;
+                }
+            }
+        }
+
+        Ok(entries)
+    }
+
+    async fn chat_ttl(&self, chat_id: &str) -> Result<Option<u64>, CoreError> {
+        Ok(self
+            .chat_ttls
+            .get(chat_id)
+            .map_err(storage_error)?
+            .and_then(|bytes| bytes.as_ref().try_into().ok().map(u64::from_be_bytes)))
+    }
+
+    async fn set_chat_ttl(&self, chat_id: &str, ttl_secs: Option<u64>) -> Result<(), CoreError> {
+        match ttl_secs {
+            Some(ttl_secs) => self
+                .chat_ttls
+                .insert(chat_id, ttl_secs.to_be_bytes().to_vec())
+                .map(|_| ()),
+            None => self.chat_ttls.remove(chat_id).map(|_| ()),
+        }
+        .map_err(storage_error)
+    }
+}
@@ -0,0 +1,26 @@
+/// Length of a Sui address in hex characters, without the `0x` prefix.
+const SUI_ADDRESS_HEX_LENGTH: usize = 64;
+
+/// Strictly validate a Sui address: a `0x` prefix followed by exactly 64 hex characters.
+///
+/// Short forms such as `0x2` are rejected on purpose, since a truncated address copied
+/// from chat would otherwise silently resolve to a different account.
+pub fn is_valid_sui_address(address: &str) -> bool {
+    match address.strip_prefix("0x") {
+        Some(hex) => {
+            hex.len() == SUI_ADDRESS_HEX_LENGTH && hex.chars().all(|c| c.is_ascii_hexdigit())
+        }
+        None => false,
+    }
+}
+
+/// Validate and normalize a Sui address to lowercase.
+pub fn normalize_sui_address(address: &str) -> Option<String> {
+    let address = address.trim();
+
+    if is_valid_sui_address(address) {
+        Some(address.to_lowercase())
+    } else {
+        None
+    }
+}
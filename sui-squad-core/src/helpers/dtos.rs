@@ -44,10 +44,213 @@ pub struct DigestResponse {
 #[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct WithdrawRequest {
     pub amount: u64,
+    /// A Sui address or a SuiNS name such as `alice.sui`.
     pub address: String,
 }
 
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct WithdrawResponse {
+    pub digest: String,
+    /// The address the funds were sent to, after name resolution.
+    pub address: String,
+    /// The SuiNS name from the request, if one was used.
+    pub name: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UserPayload {
     pub telegram_id: String,
 }
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default, ToSchema)]
+pub struct SpendingLimits {
+    pub daily: Option<u64>,
+    pub weekly: Option<u64>,
+    pub per_transaction: Option<u64>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct LimitsRequest {
+    pub coin_type: String,
+    pub limits: SpendingLimits,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct LimitsResponse {
+    pub coin_type: String,
+    pub policy: SpendingLimits,
+    pub user_limits: SpendingLimits,
+    pub effective: SpendingLimits,
+    pub spent_daily: u64,
+    pub spent_weekly: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct AdminStats {
+    pub admin_address: String,
+    pub admin_gas_balance: u64,
+    pub accounts: usize,
+    pub paused: bool,
+    pub chain_paused: bool,
+    pub relayers: Vec<RelayerStatus>,
+    pub spent_daily: u64,
+    pub spent_weekly: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct AdminUserInfo {
+    pub telegram_id: String,
+    pub account_id: Option<String>,
+    pub balance: Option<u64>,
+    pub frozen: Option<bool>,
+    pub limits: Option<LimitsResponse>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct PauseRequest {
+    pub paused: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct PauseResponse {
+    pub paused: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct ChainPauseResponse {
+    pub paused: bool,
+    pub digest: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct FreezeRequest {
+    pub telegram_id: String,
+    pub frozen: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct FreezeResponse {
+    pub telegram_id: String,
+    pub frozen: bool,
+    pub digest: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct RotateAdminRequest {
+    /// Address of the new admin key, which accepts the rotation itself.
+    pub address: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct RotateAdminResponse {
+    pub admin: String,
+    pub pending_admin: String,
+    pub digests: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct RelayerStatus {
+    pub address: String,
+    pub gas_balance: u64,
+    /// Whether the `Admin` object currently lists this key as an authorized relayer.
+    pub authorized: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct RelayerRequest {
+    pub address: String,
+    pub authorized: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct RelayerResponse {
+    pub address: String,
+    pub authorized: bool,
+    pub digest: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    FundingCompleted,
+    PaymentReceived,
+    WithdrawalExecuted,
+    /// Coins arrived at the user's zkLogin address outside the fund flow.
+    DepositDetected,
+    /// The user's zkLogin session is about to reach its `max_epoch` and needs a new login.
+    SessionExpiring,
+    /// A zkLogin address was registered as the owner of the user's account.
+    SelfCustodyEnabled,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct Notification {
+    /// Outbox id, echoed back in [`NotificationAck`] once delivered.
+    pub id: u64,
+    /// The Telegram user to notify.
+    pub telegram_id: String,
+    pub kind: NotificationKind,
+    pub amount: u64,
+    pub coin_type: String,
+    /// Telegram id of the paying user, or the address the deposit came from.
+    pub sender: Option<String>,
+    /// Destination address of a withdrawal, or the zkLogin address a deposit arrived at.
+    pub recipient: Option<String>,
+    /// Empty for detected deposits and expiring sessions, which are not tied to a transaction.
+    pub digest: String,
+    pub timestamp: i64,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct NotificationAck {
+    pub ids: Vec<u64>,
+}
+
+/// What a login link is for; custody actions require a login made for them.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LoginPurpose {
+    #[default]
+    Fund,
+    Custody,
+}
+
+/// Ephemeral zkLogin parameters the bot registers before sending a login link.
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct SessionRequest {
+    pub telegram_id: String,
+    pub public_key: String,
+    pub max_epoch: u64,
+    pub randomness: String,
+    /// Amount the funding page prefills, set when offering to move a detected deposit.
+    pub amount: Option<u64>,
+    pub coin_type: Option<String>,
+    /// Telegram `language_code` of the user, used to localize the login and funding pages.
+    pub language: Option<String>,
+    #[serde(default)]
+    pub purpose: LoginPurpose,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+pub struct SessionResponse {
+    /// Single-use token of the `/webhook/{token}` login link.
+    pub token: String,
+    pub expires_at: i64,
+}
+
+/// zkLogin session of a user that is still valid long enough to be reused for a new login link.
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct ZkLoginSessionInfo {
+    pub telegram_id: String,
+    pub public_key: String,
+    pub max_epoch: u64,
+    pub randomness: String,
+    pub current_epoch: u64,
+}
+
+/// zkLogin address a user last logged in with, known once a proof was made for it.
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct ZkLoginAddressInfo {
+    pub telegram_id: String,
+    pub address: String,
+}
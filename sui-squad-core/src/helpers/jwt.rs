@@ -41,6 +41,14 @@ impl JwtManager {
         )
     }
 
+    /// Token for a raw Telegram ID, for callers such as the server CLI that do not use teloxide types.
+    pub fn generate_token_for_id(
+        &self,
+        telegram_id: u64,
+    ) -> Result<String, jsonwebtoken::errors::Error> {
+        self.generate_token(UserId(telegram_id))
+    }
+
     pub fn validate_token(&self, token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
         let token_data: TokenData<Claims> = decode(
             token,
@@ -1,2 +1,4 @@
+pub mod address;
 pub mod dtos;
 pub mod jwt;
+pub mod token;
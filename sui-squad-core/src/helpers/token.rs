@@ -0,0 +1,10 @@
+use aes_gcm::aead::{OsRng, rand_core::RngCore};
+use fastcrypto::encoding::{Encoding, Hex};
+
+/// Random 32-byte token, hex encoded so it can be used in URL paths.
+pub fn random_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+
+    Hex::encode(bytes)
+}
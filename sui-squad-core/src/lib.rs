@@ -5,7 +5,10 @@ pub mod commands;
 pub mod config;
 pub mod conversation;
 pub mod error;
+pub mod helpers;
+pub mod package;
 pub mod permissions;
+pub mod signer;
 pub mod sui_gateway;
-pub mod helpers;
-pub mod package;
\ No newline at end of file
+pub mod suins;
+pub mod zklogin;
@@ -13,6 +13,11 @@ pub enum AccountFunction {
 pub enum Event {
     AdminEvent,
     AccountEvent,
+    PauseEvent,
+    FreezeEvent,
+    AdminRotationEvent,
+    RelayerEvent,
+    OwnerEvent,
 }
 
 pub enum Function {
@@ -25,6 +30,11 @@ impl fmt::Display for Event {
         match self {
             Event::AdminEvent => write!(f, "{}::admin::AdminEvent", package),
             Event::AccountEvent => write!(f, "{}::account::AccountEvent", package),
+            Event::PauseEvent => write!(f, "{}::admin::PauseEvent", package),
+            Event::FreezeEvent => write!(f, "{}::account::FreezeEvent", package),
+            Event::AdminRotationEvent => write!(f, "{}::admin::AdminRotationEvent", package),
+            Event::RelayerEvent => write!(f, "{}::admin::RelayerEvent", package),
+            Event::OwnerEvent => write!(f, "{}::account::OwnerEvent", package),
         }
     }
 }
@@ -0,0 +1,60 @@
+use sui_sdk::types::dynamic_field::DynamicFieldName;
+
+/// Coin type `T` of an `AccountBalance<T>` dynamic field, `None` for other fields.
+///
+/// Flags such as the freeze and the owner are dynamic fields of the account too, so the
+/// balances have to be picked out by the name of their field.
+pub fn balance_coin_type(name: &DynamicFieldName) -> Option<String> {
+    let name_type = name.type_.to_string();
+    let (_, coin_type) = name_type.split_once("::account::AccountBalance<")?;
+
+    coin_type
+        .strip_suffix('>')
+        .map(|coin_type| coin_type.to_string())
+}
+
+/// Whether the dynamic field holds the account's SUI balance.
+pub fn is_sui_balance_field(name: &DynamicFieldName) -> bool {
+    balance_coin_type(name).is_some_and(|coin_type| coin_type.ends_with("::sui::SUI"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use serde_json::json;
+    use sui_sdk::types::TypeTag;
+
+    use super::*;
+
+    fn field(type_name: &str) -> DynamicFieldName {
+        DynamicFieldName {
+            type_: TypeTag::from_str(type_name).unwrap(),
+            value: json!({ "dummy_field": false }),
+        }
+    }
+
+    #[test]
+    fn finds_the_coin_type_of_balances() {
+        let name = field("0x5::account::AccountBalance<0x2::sui::SUI>");
+
+        assert!(balance_coin_type(&name).unwrap().ends_with("::sui::SUI"));
+        assert!(is_sui_balance_field(&name));
+    }
+
+    #[test]
+    fn other_coins_are_not_the_sui_balance() {
+        let name = field("0x5::account::AccountBalance<0x7::usdc::USDC>");
+
+        assert!(balance_coin_type(&name).unwrap().ends_with("::usdc::USDC"));
+        assert!(!is_sui_balance_field(&name));
+    }
+
+    #[test]
+    fn flags_are_not_balances() {
+        for type_name in ["0x5::account::FrozenKey", "0x5::account::OwnerKey"] {
+            assert_eq!(balance_coin_type(&field(type_name)), None);
+            assert!(!is_sui_balance_field(&field(type_name)));
+        }
+    }
+}
@@ -1 +1,2 @@
-pub mod dto;
\ No newline at end of file
+pub mod dto;
+pub mod helpers;
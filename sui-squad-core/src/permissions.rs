@@ -1,14 +1,25 @@
+use std::env;
+
 /// Check if a user ID is in the list of admin IDs.
 pub fn is_admin(user_id: i64, admins: &[i64]) -> bool {
     admins.contains(&user_id)
 }
 
+/// Operator Telegram IDs from the comma-separated `ADMIN_TELEGRAM_IDS` variable.
+pub fn admin_ids_from_env() -> Vec<i64> {
+    env::var("ADMIN_TELEGRAM_IDS")
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|id| id.trim().parse::<i64>().ok())
+        .collect()
+}
+
 /// Macro to require admin privileges in a function context.
 #[macro_export]
 macro_rules! require_admin {
     ($user_id:expr, $admins:expr) => {
-        if !crate::permissions::is_admin($user_id, $admins) {
-            return Err(crate::error::BotError::Unauthorized);
+        if !$crate::permissions::is_admin($user_id, $admins) {
+            return Err($crate::error::CoreError::Unauthorized.into());
         }
     };
 }
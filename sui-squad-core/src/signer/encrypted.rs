@@ -0,0 +1,247 @@
+use std::{fs, path::Path};
+
+use aes_gcm::{
+    Aes256Gcm, KeyInit, Nonce,
+    aead::{Aead, OsRng, rand_core::RngCore},
+};
+use argon2::Argon2;
+use async_trait::async_trait;
+use fastcrypto::encoding::{Base64, Encoding};
+use serde::{Deserialize, Serialize};
+use shared_crypto::intent::IntentMessage;
+use sui_sdk::types::{base_types::SuiAddress, crypto::Signature, transaction::TransactionData};
+
+use crate::error::CoreError;
+
+use super::{InMemorySigner, Signer};
+
+/// On-disk format: the mnemonic encrypted with AES-256-GCM under an Argon2id key derived from the passphrase.
+#[derive(Serialize, Deserialize)]
+struct EncryptedKeystore {
+    version: u8,
+    address: String,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Length of an AES-256-GCM nonce in bytes.
+const NONCE_LENGTH: usize = 12;
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32], CoreError> {
+    let mut key = [0u8; 32];
+
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| CoreError::Other(e.to_string()))?;
+
+    Ok(key)
+}
+
+fn decode(field: &str, value: &str) -> Result<Vec<u8>, CoreError> {
+    Base64::decode(value)
+        .map_err(|e| CoreError::ConfigurationError(format!("Invalid keystore {}: {}", field, e)))
+}
+
+/// Key stored in a passphrase-encrypted keystore file, decrypted once when opened.
+pub struct EncryptedFileSigner {
+    inner: InMemorySigner,
+}
+
+impl EncryptedFileSigner {
+    pub fn open(path: &Path, passphrase: &str) -> Result<Self, CoreError> {
+        let contents = fs::read_to_string(path).map_err(|e| {
+            CoreError::ConfigurationError(format!("Failed to read keystore {:?}: {}", path, e))
+        })?;
+
+        let keystore: EncryptedKeystore = serde_json::from_str(&contents)
+            .map_err(|e| CoreError::ConfigurationError(format!("Invalid keystore: {}", e)))?;
+
+        let key = derive_key(passphrase, &decode("salt", &keystore.salt)?)?;
+        let nonce = decode("nonce", &keystore.nonce)?;
+
+        if nonce.len() != NONCE_LENGTH {
+            return Err(CoreError::ConfigurationError(
+                "Invalid keystore nonce".to_string(),
+            ));
+        }
+
+        let cipher =
+            Aes256Gcm::new_from_slice(&key).map_err(|e| CoreError::Other(e.to_string()))?;
+
+        let mnemonic = cipher
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                decode("ciphertext", &keystore.ciphertext)?.as_ref(),
+            )
+            .map_err(|_| CoreError::ConfigurationError("Wrong keystore passphrase".to_string()))?;
+
+        let mnemonic = String::from_utf8(mnemonic).map_err(|e| CoreError::Other(e.to_string()))?;
+
+        let inner = InMemorySigner::from_mnemonic(&mnemonic)?;
+
+        if inner.address().to_string() != keystore.address {
+            return Err(CoreError::ConfigurationError(format!(
+                "Keystore holds {} instead of {}",
+                inner.address(),
+                keystore.address
+            )));
+        }
+
+        Ok(Self { inner })
+    }
+
+    /// Encrypt `mnemonic` with `passphrase` into a new keystore file at `path`, replacing any existing one.
+    pub fn write(path: &Path, mnemonic: &str, passphrase: &str) -> Result<SuiAddress, CoreError> {
+        let address = InMemorySigner::from_mnemonic(mnemonic)?.address();
+
+        let mut salt = [0u8; 16];
+        let mut nonce = [0u8; NONCE_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut nonce);
+
+        let key = derive_key(passphrase, &salt)?;
+
+        let cipher =
+            Aes256Gcm::new_from_slice(&key).map_err(|e| CoreError::Other(e.to_string()))?;
+
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), mnemonic.trim().as_bytes())
+            .map_err(|e| CoreError::Other(e.to_string()))?;
+
+        let keystore = EncryptedKeystore {
+            version: 1,
+            address: address.to_string(),
+            salt: Base64::encode(salt),
+            nonce: Base64::encode(nonce),
+            ciphertext: Base64::encode(ciphertext),
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| CoreError::Other(e.to_string()))?;
+        }
+
+        let contents =
+            serde_json::to_string_pretty(&keystore).map_err(|e| CoreError::Other(e.to_string()))?;
+
+        fs::write(path, contents).map_err(|e| CoreError::Other(e.to_string()))?;
+
+        Ok(address)
+    }
+}
+
+#[async_trait]
+impl Signer for EncryptedFileSigner {
+    fn address(&self) -> SuiAddress {
+        self.inner.address()
+    }
+
+    async fn sign_intent_message(
+        &self,
+        message: &IntentMessage<TransactionData>,
+    ) -> Result<Signature, CoreError> {
+        self.inner.sign_intent_message(message).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, path::PathBuf};
+
+    use crate::helpers::token::random_token;
+
+    use super::*;
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn keystore_path() -> PathBuf {
+        env::temp_dir().join(format!("keystore-{}.json", random_token()))
+    }
+
+    fn expect_configuration_error(result: Result<EncryptedFileSigner, CoreError>) -> String {
+        match result {
+            Err(CoreError::ConfigurationError(message)) => message,
+            Err(e) => panic!("expected a configuration error, got {}", e),
+            Ok(_) => panic!("expected a configuration error"),
+        }
+    }
+
+    #[test]
+    fn write_then_open_round_trips() {
+        let path = keystore_path();
+
+        let address = EncryptedFileSigner::write(&path, MNEMONIC, "correct horse").unwrap();
+        let signer = EncryptedFileSigner::open(&path, "correct horse").unwrap();
+
+        assert_eq!(signer.address(), address);
+        assert_eq!(
+            address,
+            InMemorySigner::from_mnemonic(MNEMONIC).unwrap().address()
+        );
+
+        // The mnemonic is never written in clear
+        assert!(!fs::read_to_string(&path).unwrap().contains("abandon"));
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn wrong_passphrase_is_rejected() {
+        let path = keystore_path();
+
+        EncryptedFileSigner::write(&path, MNEMONIC, "correct horse").unwrap();
+
+        let message = expect_configuration_error(EncryptedFileSigner::open(&path, "wrong horse"));
+
+        assert_eq!(message, "Wrong keystore passphrase");
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn corrupted_file_is_rejected() {
+        let path = keystore_path();
+
+        EncryptedFileSigner::write(&path, MNEMONIC, "correct horse").unwrap();
+
+        let mut keystore: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+
+        // A flipped ciphertext byte fails authentication like a wrong passphrase
+        let mut ciphertext = Base64::decode(keystore["ciphertext"].as_str().unwrap()).unwrap();
+        ciphertext[0] ^= 1;
+        keystore["ciphertext"] = Base64::encode(ciphertext).into();
+        fs::write(&path, keystore.to_string()).unwrap();
+
+        expect_configuration_error(EncryptedFileSigner::open(&path, "correct horse"));
+
+        // A truncated nonce is reported instead of panicking
+        keystore["nonce"] = Base64::encode([0u8; 4]).into();
+        fs::write(&path, keystore.to_string()).unwrap();
+
+        assert_eq!(
+            expect_configuration_error(EncryptedFileSigner::open(&path, "correct horse")),
+            "Invalid keystore nonce"
+        );
+
+        // Not a keystore at all
+        fs::write(&path, "not json").unwrap();
+
+        assert!(
+            expect_configuration_error(EncryptedFileSigner::open(&path, "correct horse"))
+                .starts_with("Invalid keystore")
+        );
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn missing_file_is_rejected() {
+        let message = expect_configuration_error(EncryptedFileSigner::open(
+            &keystore_path(),
+            "correct horse",
+        ));
+
+        assert!(message.starts_with("Failed to read keystore"));
+    }
+}
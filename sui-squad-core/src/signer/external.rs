@@ -0,0 +1,177 @@
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use fastcrypto::{
+    encoding::{Base64, Encoding},
+    traits::ToFromBytes,
+};
+use serde::Deserialize;
+use serde_json::json;
+use shared_crypto::intent::IntentMessage;
+use sui_sdk::types::{base_types::SuiAddress, crypto::Signature, transaction::TransactionData};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::UnixStream,
+};
+
+use crate::error::CoreError;
+
+use super::Signer;
+
+/// Reply of the signer process; exactly one field is set.
+#[derive(Deserialize)]
+struct SignerReply {
+    address: Option<String>,
+    signature: Option<String>,
+    error: Option<String>,
+}
+
+/// Key held by a separate signer process, reached over a local Unix socket.
+///
+/// Requests and replies are single lines of JSON:
+/// `{"method":"address"}` returns `{"address":"0x…"}`, and
+/// `{"method":"sign","address":"0x…","intent_message":"<base64 BCS>"}` returns
+/// `{"signature":"<base64 flag || signature || public key>"}`. Failures return `{"error":"…"}`.
+pub struct ExternalSigner {
+    socket: PathBuf,
+    timeout: Duration,
+    address: SuiAddress,
+}
+
+impl ExternalSigner {
+    /// Ask the signer process for its address; `timeout` bounds connecting and each reply.
+    pub async fn connect(socket: &Path, timeout: Duration) -> Result<Self, CoreError> {
+        let reply = request(socket, timeout, json!({ "method": "address" })).await?;
+
+        let address = reply
+            .address
+            .ok_or_else(|| CoreError::GatewayError("Signer returned no address".to_string()))?;
+
+        let address = SuiAddress::from_str(&address)
+            .map_err(|e| CoreError::GatewayError(format!("Signer returned {}: {}", address, e)))?;
+
+        Ok(Self {
+            socket: socket.to_path_buf(),
+            timeout,
+            address,
+        })
+    }
+}
+
+fn timed_out(socket: &Path, timeout: Duration) -> CoreError {
+    CoreError::GatewayError(format!(
+        "Signer at {:?} did not answer within {:?}",
+        socket, timeout
+    ))
+}
+
+async fn request(
+    socket: &Path,
+    timeout: Duration,
+    body: serde_json::Value,
+) -> Result<SignerReply, CoreError> {
+    let stream = tokio::time::timeout(timeout, UnixStream::connect(socket))
+        .await
+        .map_err(|_| timed_out(socket, timeout))?
+        .map_err(|e| {
+            CoreError::GatewayError(format!("Failed to reach signer at {:?}: {}", socket, e))
+        })?;
+
+    let (reader, mut writer) = stream.into_split();
+
+    let mut line = body.to_string();
+    line.push('\n');
+
+    writer
+        .write_all(line.as_bytes())
+        .await
+        .map_err(|e| CoreError::GatewayError(e.to_string()))?;
+
+    let mut reply = String::new();
+
+    let mut reader = BufReader::new(reader);
+
+    tokio::time::timeout(timeout, reader.read_line(&mut reply))
+        .await
+        .map_err(|_| timed_out(socket, timeout))?
+        .map_err(|e| CoreError::GatewayError(e.to_string()))?;
+
+    let reply: SignerReply =
+        serde_json::from_str(&reply).map_err(|e| CoreError::GatewayError(e.to_string()))?;
+
+    match reply.error {
+        Some(error) => Err(CoreError::GatewayError(format!("Signer error: {}", error))),
+        None => Ok(reply),
+    }
+}
+
+#[async_trait]
+impl Signer for ExternalSigner {
+    fn address(&self) -> SuiAddress {
+        self.address
+    }
+
+    async fn sign_intent_message(
+        &self,
+        message: &IntentMessage<TransactionData>,
+    ) -> Result<Signature, CoreError> {
+        let bytes = bcs::to_bytes(message).map_err(|e| CoreError::Other(e.to_string()))?;
+
+        let reply = request(
+            &self.socket,
+            self.timeout,
+            json!({
+                "method": "sign",
+                "address": self.address.to_string(),
+                "intent_message": Base64::encode(bytes),
+            }),
+        )
+        .await?;
+
+        let signature = reply
+            .signature
+            .ok_or_else(|| CoreError::GatewayError("Signer returned no signature".to_string()))?;
+
+        let bytes =
+            Base64::decode(&signature).map_err(|e| CoreError::GatewayError(e.to_string()))?;
+
+        Signature::from_bytes(&bytes).map_err(|e| CoreError::GatewayError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use tokio::net::UnixListener;
+
+    use crate::helpers::token::random_token;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn hung_signer_times_out() {
+        let socket = env::temp_dir().join(format!("signer-{}.sock", &random_token()[..16]));
+        let listener = UnixListener::bind(&socket).unwrap();
+
+        // Accept the connection but never answer
+        let hung = tokio::spawn(async move {
+            let (_stream, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        });
+
+        let result = ExternalSigner::connect(&socket, Duration::from_millis(100)).await;
+
+        hung.abort();
+        std::fs::remove_file(&socket).ok();
+
+        match result {
+            Err(CoreError::GatewayError(message)) => assert!(message.contains("did not answer")),
+            _ => panic!("expected a timeout"),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use async_trait::async_trait;
+use shared_crypto::intent::IntentMessage;
+use sui_keys::keystore::{AccountKeystore, InMemKeystore};
+use sui_sdk::types::{
+    base_types::SuiAddress,
+    crypto::{Signature, SignatureScheme},
+    transaction::TransactionData,
+};
+
+use crate::error::CoreError;
+
+use super::Signer;
+
+/// Ed25519 key derived from a mnemonic and kept in memory for the lifetime of the process.
+pub struct InMemorySigner {
+    keystore: InMemKeystore,
+    address: SuiAddress,
+}
+
+impl InMemorySigner {
+    pub fn from_mnemonic(mnemonic: &str) -> Result<Self, CoreError> {
+        let mut keystore = InMemKeystore::default();
+
+        let address = keystore
+            .import_from_mnemonic(
+                mnemonic.trim().trim_matches('"'),
+                SignatureScheme::ED25519,
+                None,
+                None,
+            )
+            .map_err(|e| CoreError::ConfigurationError(format!("Invalid mnemonic: {}", e)))?;
+
+        Ok(Self { keystore, address })
+    }
+}
+
+#[async_trait]
+impl Signer for InMemorySigner {
+    fn address(&self) -> SuiAddress {
+        self.address
+    }
+
+    async fn sign_intent_message(
+        &self,
+        message: &IntentMessage<TransactionData>,
+    ) -> Result<Signature, CoreError> {
+        self.keystore
+            .sign_secure(&self.address, &message.value, message.intent.clone())
+            .map_err(|e| CoreError::Other(e.to_string()))
+    }
+}
@@ -0,0 +1,91 @@
+pub mod encrypted;
+pub mod external;
+pub mod memory;
+
+use std::{env, path::PathBuf, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use shared_crypto::intent::{Intent, IntentMessage};
+use sui_sdk::types::{base_types::SuiAddress, crypto::Signature, transaction::TransactionData};
+
+use crate::error::CoreError;
+
+pub use encrypted::EncryptedFileSigner;
+pub use external::ExternalSigner;
+pub use memory::InMemorySigner;
+
+/// A key able to sign transactions on behalf of the relayer.
+#[async_trait]
+pub trait Signer: Send + Sync + 'static {
+    fn address(&self) -> SuiAddress;
+
+    async fn sign_intent_message(
+        &self,
+        message: &IntentMessage<TransactionData>,
+    ) -> Result<Signature, CoreError>;
+
+    async fn sign_transaction(&self, tx: &TransactionData) -> Result<Signature, CoreError> {
+        self.sign_intent_message(&IntentMessage::new(Intent::sui_transaction(), tx.clone()))
+            .await
+    }
+}
+
+/// Where the admin key comes from, selected with the `SIGNER` environment variable.
+#[derive(Clone)]
+pub enum SignerConfig {
+    /// `SIGNER=memory` (default): the `SEED` mnemonic, loaded once into memory.
+    Memory { seed: String },
+    /// `SIGNER=encrypted`: a keystore file at `SIGNER_KEYSTORE_PATH` unlocked with `SIGNER_PASSPHRASE`.
+    EncryptedFile { path: PathBuf, passphrase: String },
+    /// `SIGNER=external`: a signer process listening on the Unix socket at `SIGNER_SOCKET`,
+    /// given `SIGNER_TIMEOUT_SECS` (10 by default) to connect and to answer.
+    External { socket: PathBuf, timeout: Duration },
+}
+
+fn required_var(name: &str) -> Result<String, CoreError> {
+    env::var(name).map_err(|_| CoreError::ConfigurationError(format!("{} must be set", name)))
+}
+
+impl SignerConfig {
+    pub fn from_env() -> Result<Self, CoreError> {
+        let signer = env::var("SIGNER")
+            .ok()
+            .filter(|signer| !signer.is_empty())
+            .unwrap_or_else(|| "memory".to_string());
+
+        match signer.as_str() {
+            "memory" => Ok(SignerConfig::Memory {
+                seed: required_var("SEED")?,
+            }),
+            "encrypted" => Ok(SignerConfig::EncryptedFile {
+                path: PathBuf::from(required_var("SIGNER_KEYSTORE_PATH")?),
+                passphrase: required_var("SIGNER_PASSPHRASE")?,
+            }),
+            "external" => Ok(SignerConfig::External {
+                socket: PathBuf::from(required_var("SIGNER_SOCKET")?),
+                timeout: Duration::from_secs(
+                    env::var("SIGNER_TIMEOUT_SECS")
+                        .ok()
+                        .and_then(|secs| secs.parse().ok())
+                        .unwrap_or(10),
+                ),
+            }),
+            other => Err(CoreError::ConfigurationError(format!(
+                "Unknown SIGNER {}, expected memory, encrypted or external",
+                other
+            ))),
+        }
+    }
+
+    pub async fn load(&self) -> Result<Arc<dyn Signer>, CoreError> {
+        match self {
+            SignerConfig::Memory { seed } => Ok(Arc::new(InMemorySigner::from_mnemonic(seed)?)),
+            SignerConfig::EncryptedFile { path, passphrase } => {
+                Ok(Arc::new(EncryptedFileSigner::open(path, passphrase)?))
+            }
+            SignerConfig::External { socket, timeout } => {
+                Ok(Arc::new(ExternalSigner::connect(socket, *timeout).await?))
+            }
+        }
+    }
+}
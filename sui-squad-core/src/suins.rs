@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::{Value, json};
+
+use crate::{error::CoreError, sui_gateway::Address};
+
+/// Check whether `name` looks like a SuiNS name such as `alice.sui` or `pay.alice.sui`.
+pub fn is_sui_name(name: &str) -> bool {
+    let name = name.trim().to_lowercase();
+
+    match name.strip_suffix(".sui") {
+        Some(labels) if !labels.is_empty() => labels.split('.').all(|label| {
+            !label.is_empty()
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        }),
+        _ => false,
+    }
+}
+
+/// Default public fullnode RPC endpoint for a `SUI_NETWORK` value.
+pub fn default_rpc_url(network: &str) -> String {
+    match network {
+        "mainnet" => "https://fullnode.mainnet.sui.io:443".to_string(),
+        "testnet" => "https://fullnode.testnet.sui.io:443".to_string(),
+        _ => "https://fullnode.devnet.sui.io:443".to_string(),
+    }
+}
+
+/// Trait resolving SuiNS names to addresses.
+#[async_trait]
+pub trait NameResolver: Send + Sync + 'static {
+    /// Returns `None` when the name is not registered or has no target address.
+    async fn resolve(&self, name: &str) -> Result<Option<Address>, CoreError>;
+}
+
+/// Resolver backed by the fullnode `suix_resolveNameServiceAddress` JSON-RPC method.
+#[derive(Clone)]
+pub struct RpcNameResolver {
+    client: Client,
+    rpc_url: String,
+}
+
+impl RpcNameResolver {
+    pub fn new(rpc_url: String) -> Self {
+        Self {
+            client: Client::new(),
+            rpc_url,
+        }
+    }
+}
+
+#[async_trait]
+impl NameResolver for RpcNameResolver {
+    async fn resolve(&self, name: &str) -> Result<Option<Address>, CoreError> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "suix_resolveNameServiceAddress",
+            "params": [name.trim().to_lowercase()],
+        });
+
+        let response: Value = self
+            .client
+            .post(&self.rpc_url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| CoreError::GatewayError(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| CoreError::GatewayError(e.to_string()))?;
+
+        if let Some(error) = response.get("error") {
+            return Err(CoreError::GatewayError(format!(
+                "Failed to resolve {}: {}",
+                name, error
+            )));
+        }
+
+        Ok(response
+            .get("result")
+            .and_then(|result| result.as_str())
+            .map(|address| address.to_string()))
+    }
+}
+
+/// In-memory resolver with a fixed set of names, used as a stand-in for the name service in tests.
+#[derive(Clone, Default)]
+pub struct LocalNameResolver {
+    names: HashMap<String, Address>,
+}
+
+impl LocalNameResolver {
+    pub fn new(names: HashMap<String, Address>) -> Self {
+        Self { names }
+    }
+}
+
+#[async_trait]
+impl NameResolver for LocalNameResolver {
+    async fn resolve(&self, name: &str) -> Result<Option<Address>, CoreError> {
+        Ok(self.names.get(&name.trim().to_lowercase()).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognises_sui_names() {
+        assert!(is_sui_name("alice.sui"));
+        assert!(is_sui_name("Pay.Alice.SUI"));
+        assert!(!is_sui_name(".sui"));
+        assert!(!is_sui_name("-alice.sui"));
+        assert!(!is_sui_name("alice..sui"));
+        assert!(!is_sui_name("alice.eth"));
+        assert!(!is_sui_name("0x2"));
+    }
+
+    #[tokio::test]
+    async fn local_resolver_normalizes_names() {
+        let resolver = LocalNameResolver::new(HashMap::from([(
+            "alice.sui".to_string(),
+            "0xa11ce".to_string(),
+        )]));
+
+        assert_eq!(
+            resolver.resolve(" ALICE.sui").await.unwrap().as_deref(),
+            Some("0xa11ce")
+        );
+        assert_eq!(resolver.resolve("bob.sui").await.unwrap(), None);
+    }
+}
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{error::CoreError, sui_gateway::Address};
+
+/// zkLogin account derived from an OAuth id_token.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ZkLoginAccount {
+    pub address: Address,
+    pub salt: String,
+}
+
+/// Trait looking up the zkLogin account behind an OAuth id_token.
+#[async_trait]
+pub trait ZkLoginProvider: Send + Sync + 'static {
+    /// Returns [`CoreError::Unauthorized`] when the provider rejects the token.
+    async fn lookup(&self, id_token: &str) -> Result<ZkLoginAccount, CoreError>;
+}
+
+/// Provider backed by the Enoki `GET /v1/zklogin` API; the API key stays on the server.
+#[derive(Clone)]
+pub struct EnokiZkLogin {
+    client: Client,
+    api_key: String,
+    api_url: String,
+}
+
+impl EnokiZkLogin {
+    pub fn new(api_key: String) -> Self {
+        Self {
+            client: Client::new(),
+            api_key,
+            api_url: "https://api.enoki.mystenlabs.com/v1/zklogin".to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl ZkLoginProvider for EnokiZkLogin {
+    async fn lookup(&self, id_token: &str) -> Result<ZkLoginAccount, CoreError> {
+        let response = self
+            .client
+            .get(&self.api_url)
+            .header("zklogin-jwt", id_token)
+            .bearer_auth(&self.api_key)
+            .send()
+            .await
+            .map_err(|e| CoreError::GatewayError(e.to_string()))?;
+
+        match response.status() {
+            StatusCode::BAD_REQUEST | StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                return Err(CoreError::Unauthorized);
+            }
+            status if !status.is_success() => {
+                return Err(CoreError::GatewayError(format!(
+                    "Enoki responded with status {}",
+                    status
+                )));
+            }
+            _ => {}
+        }
+
+        let mut body: Value = response
+            .json()
+            .await
+            .map_err(|e| CoreError::GatewayError(e.to_string()))?;
+
+        serde_json::from_value(body["data"].take())
+            .map_err(|e| CoreError::GatewayError(format!("Unexpected Enoki response: {}", e)))
+    }
+}
+
+/// In-memory provider with a fixed set of tokens, used as a stand-in for Enoki in tests.
+#[derive(Clone, Default)]
+pub struct LocalZkLogin {
+    accounts: HashMap<String, ZkLoginAccount>,
+}
+
+impl LocalZkLogin {
+    pub fn new(accounts: HashMap<String, ZkLoginAccount>) -> Self {
+        Self { accounts }
+    }
+}
+
+#[async_trait]
+impl ZkLoginProvider for LocalZkLogin {
+    async fn lookup(&self, id_token: &str) -> Result<ZkLoginAccount, CoreError> {
+        self.accounts
+            .get(id_token)
+            .cloned()
+            .ok_or(CoreError::Unauthorized)
+    }
+}
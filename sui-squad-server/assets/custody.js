@@ -0,0 +1,198 @@
+const config = JSON.parse(document.getElementById('page-config').textContent);
+const messages = config.messages;
+
+// Withdrawals are expressed in MIST
+const SUI_DECIMALS = 9;
+
+let walletAddress = null;
+let session = null;
+let idToken = null;
+
+// Look up a page text and fill in its {placeholders}
+function t(key, values) {
+    let text = messages[key];
+    for (const [name, value] of Object.entries(values || {})) {
+        text = text.split(`{${name}}`).join(value);
+    }
+    return text;
+}
+
+function setStatus(text, className) {
+    const status = document.getElementById('status');
+    status.textContent = text;
+    status.className = className || '';
+}
+
+function showMessage(text, className) {
+    const messageDiv = document.getElementById('message');
+    messageDiv.className = `message ${className}`;
+    messageDiv.textContent = text;
+    messageDiv.style.whiteSpace = 'pre-line';
+}
+
+function errorMessage(response) {
+    return response.json().catch(() => ({})).then(body => {
+        throw new Error(body.message || `Server responded with status: ${response.status}`);
+    });
+}
+
+function showFailure(title, message) {
+    const errorDiv = document.getElementById('custody-error');
+    errorDiv.replaceChildren();
+
+    for (const [tag, text] of [['h4', title], ['p', t('error', { error: message })], ['p', t('try_again')]]) {
+        const element = document.createElement(tag);
+        element.textContent = text;
+        errorDiv.appendChild(element);
+    }
+
+    errorDiv.classList.remove('hidden');
+}
+
+function parseUnits(value, decimals) {
+    const match = value.trim().match(/^(\d*)(?:\.(\d*))?$/);
+    if (!match || (!match[1] && !match[2])) {
+        throw new Error(t('invalid_amount'));
+    }
+    const fraction = match[2] || '';
+    if (fraction.length > decimals) {
+        throw new Error(t('too_many_decimals', { decimals: decimals }));
+    }
+    return BigInt((match[1] || '0') + fraction.padEnd(decimals, '0'));
+}
+
+document.addEventListener('DOMContentLoaded', function() {
+    // The funding page forwards custody logins here with the same fragment
+    const params = new URLSearchParams(window.location.hash.substring(1));
+    const state = params.get('state');
+
+    idToken = params.get('id_token');
+
+    if (state) {
+        try {
+            session = JSON.parse(decodeURIComponent(state)).session || null;
+        } catch (error) {
+            console.error('Error parsing state:', error);
+        }
+    }
+
+    document.getElementById('enable-btn').addEventListener('click', enableCustody);
+    document.getElementById('withdraw-btn').addEventListener('click', withdraw);
+
+    if (!idToken || !session) {
+        document.getElementById('spinner').style.display = 'none';
+        setStatus(t('invalid_link'), 'warning');
+        showMessage(t('invalid_link_details'), 'warning');
+        return;
+    }
+
+    setStatus(t('getting_address'));
+
+    fetch('/zklogin/address', {
+        method: 'POST',
+        headers: {
+            'Authorization': `Bearer ${idToken}`,
+        },
+    })
+    .then(response => response.ok ? response.json() : errorMessage(response))
+    .then(response => {
+        walletAddress = response.address;
+        return fetch(`/custody/status?${new URLSearchParams({ session: session })}`);
+    })
+    .then(response => response.ok ? response.json() : errorMessage(response))
+    .then(status => {
+        document.getElementById('spinner').style.display = 'none';
+        document.getElementById('message').classList.add('hidden');
+        setStatus(t('address_retrieved'), 'success');
+
+        document.getElementById('wallet-address').textContent = walletAddress;
+        document.getElementById('custody-panel').classList.remove('hidden');
+
+        showSection(status.owner);
+    })
+    .catch(error => {
+        document.getElementById('spinner').style.display = 'none';
+        setStatus(t('address_failed'), 'error');
+        showMessage(t('custody_status_failed', { error: error.message }), 'error');
+    });
+});
+
+// Offer to enable self-custody, or to withdraw when this address is the owner
+function showSection(owner) {
+    document.getElementById('enable-section').classList.toggle('hidden', owner !== null);
+    document.getElementById('withdraw-section').classList.toggle('hidden', owner !== walletAddress);
+
+    if (owner !== null && owner !== walletAddress) {
+        showMessage(t('custody_other_owner', { owner: owner }), 'warning');
+    }
+}
+
+function enableCustody() {
+    const button = document.getElementById('enable-btn');
+
+    button.disabled = true;
+    button.textContent = t('custody_enabling');
+    document.getElementById('custody-error').classList.add('hidden');
+
+    fetch('/custody/enable', {
+        method: 'POST',
+        headers: {
+            'Content-Type': 'application/json',
+            'Authorization': `Bearer ${idToken}`,
+        },
+        body: JSON.stringify({ session: session }),
+    })
+    .then(response => response.ok ? response.json() : errorMessage(response))
+    .then(data => showSection(data.owner))
+    .catch(error => {
+        button.disabled = false;
+        button.textContent = t('custody_enable_button');
+        showFailure(t('custody_enable_failed'), error.message);
+    });
+}
+
+function withdraw() {
+    const button = document.getElementById('withdraw-btn');
+    const recipient = document.getElementById('recipient-input').value.trim();
+
+    let amount;
+    try {
+        amount = parseUnits(document.getElementById('amount-input').value, SUI_DECIMALS);
+        if (amount <= 0n) {
+            throw new Error(t('amount_zero'));
+        }
+        if (!recipient) {
+            throw new Error(t('recipient_required'));
+        }
+    } catch (error) {
+        document.getElementById('withdraw-info').textContent = `⚠️ ${error.message}`;
+        return;
+    }
+
+    button.disabled = true;
+    button.textContent = t('withdrawing');
+    document.getElementById('custody-error').classList.add('hidden');
+
+    fetch('/custody/withdraw', {
+        method: 'POST',
+        headers: {
+            'Content-Type': 'application/json',
+            'Authorization': `Bearer ${idToken}`,
+        },
+        body: JSON.stringify({
+            session: session,
+            amount: amount.toString(),
+            address: recipient,
+            coin_type: config.coin_type,
+        }),
+    })
+    .then(response => response.ok ? response.json() : errorMessage(response))
+    .then(data => {
+        window.location.href = `/webhook/result?${new URLSearchParams({ digest: data.digest, kind: 'withdrawal' })}`;
+    })
+    .catch(error => {
+        button.disabled = false;
+        button.textContent = t('withdraw_button');
+        showFailure(t('withdrawal_failed'), error.message);
+    });
+}
@@ -0,0 +1,264 @@
+const config = JSON.parse(document.getElementById('page-config').textContent);
+const messages = config.messages;
+
+let coinType = new URLSearchParams(window.location.search).get('coin_type') || config.coin_type;
+let walletAddress = null;
+let balanceInfo = null;
+let requestedAmount = null;
+let session = null;
+let idToken = null;
+
+// Look up a page text and fill in its {placeholders}
+function t(key, values) {
+    let text = messages[key];
+    for (const [name, value] of Object.entries(values || {})) {
+        text = text.split(`{${name}}`).join(value);
+    }
+    return text;
+}
+
+function setStatus(text, className) {
+    const status = document.getElementById('status');
+    status.textContent = text;
+    status.className = className || '';
+}
+
+function showMessage(text, className) {
+    const messageDiv = document.getElementById('message');
+    messageDiv.className = `message ${className}`;
+    messageDiv.textContent = text;
+    messageDiv.style.whiteSpace = 'pre-line';
+}
+
+function errorMessage(response) {
+    return response.json().catch(() => ({})).then(body => {
+        throw new Error(body.message || `Server responded with status: ${response.status}`);
+    });
+}
+
+document.addEventListener('DOMContentLoaded', function() {
+    // Parse the URL fragment (everything after #) to get id_token and state
+    const params = new URLSearchParams(window.location.hash.substring(1));
+    const state = params.get('state');
+
+    idToken = params.get('id_token');
+
+    // The state carries the server-side session the login link opened
+    if (state) {
+        try {
+            const stateObj = JSON.parse(decodeURIComponent(state));
+
+            // Google only redirects to the registered callback, so custody logins continue there
+            if (stateObj.purpose === 'custody') {
+                window.location.replace(`/webhook/custody${window.location.hash}`);
+                return;
+            }
+
+            session = stateObj.session || null;
+            requestedAmount = stateObj.amount || null;
+            coinType = stateObj.coin_type || coinType;
+        } catch (error) {
+            console.error('Error parsing state:', error);
+        }
+    }
+
+    document.getElementById('copy-btn').addEventListener('click', copyAddress);
+    document.getElementById('max-btn').addEventListener('click', useMaxAmount);
+    document.getElementById('refresh-btn').addEventListener('click', loadBalance);
+    document.getElementById('fund-btn').addEventListener('click', fundAccount);
+
+    if (!idToken || !session) {
+        document.getElementById('spinner').style.display = 'none';
+        setStatus(t('invalid_link'), 'warning');
+        showMessage(t('invalid_link_details'), 'warning');
+        return;
+    }
+
+    setStatus(t('getting_address'));
+
+    // Ask the server for the zkLogin address; it calls Enoki with its own key
+    fetch('/zklogin/address', {
+        method: 'POST',
+        headers: {
+            'Authorization': `Bearer ${idToken}`,
+        },
+    })
+    .then(response => response.ok ? response.json() : errorMessage(response))
+    .then(response => {
+        document.getElementById('spinner').style.display = 'none';
+        document.getElementById('message').classList.add('hidden');
+        setStatus(t('address_retrieved'), 'success');
+
+        walletAddress = response.address;
+        document.getElementById('wallet-address').textContent = walletAddress;
+        document.getElementById('fund-panel').classList.remove('hidden');
+
+        loadBalance();
+        watchAddress();
+    })
+    .catch(error => {
+        document.getElementById('spinner').style.display = 'none';
+        setStatus(t('address_failed'), 'error');
+        showMessage(t('address_failed_details', { error: error.message }), 'error');
+    });
+});
+
+// Let the server report later direct transfers to this address in Telegram
+function watchAddress() {
+    fetch('/fund/watch', {
+        method: 'POST',
+        headers: {
+            'Content-Type': 'application/json',
+            'Authorization': `Bearer ${idToken}`,
+        },
+        body: JSON.stringify({ session: session }),
+    }).catch(error => console.error('Failed to watch address:', error));
+}
+
+function formatUnits(value, decimals) {
+    const raw = BigInt(value).toString().padStart(decimals + 1, '0');
+    const whole = raw.slice(0, raw.length - decimals);
+    const fraction = raw.slice(raw.length - decimals).replace(/0+$/, '');
+    return fraction ? `${whole}.${fraction}` : whole;
+}
+
+function parseUnits(value, decimals) {
+    const match = value.trim().match(/^(\d*)(?:\.(\d*))?$/);
+    if (!match || (!match[1] && !match[2])) {
+        throw new Error(t('invalid_amount'));
+    }
+    const fraction = match[2] || '';
+    if (fraction.length > decimals) {
+        throw new Error(t('too_many_decimals', { decimals: decimals }));
+    }
+    return BigInt((match[1] || '0') + fraction.padEnd(decimals, '0'));
+}
+
+function loadBalance() {
+    const info = document.getElementById('balance-info');
+    const query = new URLSearchParams({ address: walletAddress, coin_type: coinType });
+
+    fetch(`/fund/balance?${query}`)
+    .then(response => response.ok ? response.json() : errorMessage(response))
+    .then(data => {
+        balanceInfo = data;
+
+        // Prefill the deposit the bot detected, capped at what can be moved
+        const amountInput = document.getElementById('amount-input');
+        if (requestedAmount && !amountInput.value) {
+            const prefill = BigInt(requestedAmount) < BigInt(data.available) ? BigInt(requestedAmount) : BigInt(data.available);
+            amountInput.value = formatUnits(prefill, data.decimals);
+        }
+
+        info.textContent = t('available', {
+            available: formatUnits(data.available, data.decimals),
+            balance: formatUnits(data.balance, data.decimals),
+            symbol: data.symbol,
+        }) + (data.sponsored ? t('fees_covered') : '');
+    })
+    .catch(error => {
+        info.textContent = t('balance_failed', { error: error.message });
+    });
+}
+
+function useMaxAmount() {
+    if (balanceInfo) {
+        document.getElementById('amount-input').value = formatUnits(balanceInfo.available, balanceInfo.decimals);
+    }
+}
+
+function flashButton(button, text) {
+    button.textContent = text;
+    button.classList.add('copied');
+
+    setTimeout(() => {
+        button.textContent = t('copy');
+        button.classList.remove('copied');
+    }, 2000);
+}
+
+function copyAddress() {
+    const copyButton = document.getElementById('copy-btn');
+
+    navigator.clipboard.writeText(walletAddress).then(() => {
+        flashButton(copyButton, t('copied'));
+    }).catch(err => {
+        console.error('Failed to copy: ', err);
+        // Fallback for older browsers
+        try {
+            const textArea = document.createElement('textarea');
+            textArea.value = walletAddress;
+            document.body.appendChild(textArea);
+            textArea.select();
+            document.execCommand('copy');
+            document.body.removeChild(textArea);
+
+            flashButton(copyButton, t('copied'));
+        } catch (fallbackErr) {
+            flashButton(copyButton, t('copy_failed'));
+        }
+    });
+}
+
+function showFundError(message) {
+    const errorDiv = document.getElementById('fund-error');
+    errorDiv.replaceChildren();
+
+    for (const [tag, text] of [['h4', t('funding_failed')], ['p', t('error', { error: message })], ['p', t('try_again')]]) {
+        const element = document.createElement(tag);
+        element.textContent = text;
+        errorDiv.appendChild(element);
+    }
+
+    errorDiv.classList.remove('hidden');
+}
+
+function fundAccount() {
+    const button = document.getElementById('fund-btn');
+
+    let amount;
+    try {
+        if (!balanceInfo) {
+            throw new Error(t('balance_not_loaded'));
+        }
+        amount = parseUnits(document.getElementById('amount-input').value, balanceInfo.decimals);
+        if (amount <= 0n) {
+            throw new Error(t('amount_zero'));
+        }
+        if (amount > BigInt(balanceInfo.available)) {
+            throw new Error(t('amount_too_high', {
+                amount: formatUnits(balanceInfo.available, balanceInfo.decimals),
+                symbol: balanceInfo.symbol,
+            }));
+        }
+    } catch (error) {
+        document.getElementById('balance-info').textContent = `⚠️ ${error.message}`;
+        return;
+    }
+
+    button.disabled = true;
+    button.textContent = t('funding');
+    document.getElementById('fund-error').classList.add('hidden');
+
+    fetch('/fund', {
+        method: 'POST',
+        headers: {
+            'Content-Type': 'application/json',
+            'Authorization': `Bearer ${idToken}`,
+        },
+        body: JSON.stringify({
+            session: session,
+            amount: amount.toString(),
+            coin_type: coinType,
+        }),
+    })
+    .then(response => response.ok ? response.json() : errorMessage(response))
+    .then(data => {
+        window.location.href = `/webhook/result?${new URLSearchParams({ digest: data.digest })}`;
+    })
+    .catch(error => {
+        button.disabled = false;
+        button.textContent = t('funding_failed_button');
+        showFundError(error.message);
+    });
+}
@@ -0,0 +1,38 @@
+document.addEventListener('DOMContentLoaded', function() {
+    const copyButton = document.getElementById('copy-digest');
+
+    if (copyButton) {
+        copyButton.addEventListener('click', () => {
+            const originalText = copyButton.textContent;
+
+            navigator.clipboard.writeText(copyButton.dataset.digest).then(() => {
+                copyButton.textContent = copyButton.dataset.copied;
+                copyButton.classList.add('copied');
+
+                setTimeout(() => {
+                    copyButton.textContent = originalText;
+                    copyButton.classList.remove('copied');
+                }, 2000);
+            }).catch(err => {
+                console.error('Failed to copy digest: ', err);
+            });
+        });
+    }
+
+    // Count down and send the user back to the bot
+    const countdownElement = document.getElementById('countdown');
+
+    if (countdownElement) {
+        let countdown = parseInt(countdownElement.textContent);
+
+        const countdownTimer = setInterval(() => {
+            countdown--;
+            countdownElement.textContent = countdown;
+
+            if (countdown <= 0) {
+                clearInterval(countdownTimer);
+                window.location.href = countdownElement.dataset.botUrl;
+            }
+        }, 1000);
+    }
+});
@@ -1,12 +1,35 @@
-use std::{env, fs, path::PathBuf};
+use std::{env, fs, path::PathBuf, str::FromStr, sync::Arc};
 
-use sui_keys::keystore::{AccountKeystore, FileBasedKeystore};
-use sui_sdk::types::{base_types::SuiAddress, crypto::SignatureScheme};
+use axum::extract::{Json, Path, State};
+use chrono::{Duration, Utc};
+use sui_sdk::{
+    json::SuiJsonValue,
+    rpc_types::EventFilter,
+    types::base_types::{ObjectID, SuiAddress},
+};
+use sui_squad_core::{
+    helpers::address::normalize_sui_address,
+    helpers::dtos::{
+        AdminStats, AdminUserInfo, ChainPauseResponse, FreezeRequest, FreezeResponse, PauseRequest,
+        PauseResponse, RelayerRequest, RelayerResponse, RelayerStatus, RotateAdminRequest,
+        RotateAdminResponse,
+    },
+    package::{dto::Event, helpers::is_sui_balance_field},
+};
 
-pub fn get_account() -> (SuiAddress, PathBuf) {
-    let seed = env::var("SEED").expect("SEED must be set");
-    let seed = seed.trim_matches('"');
+use crate::{
+    error::ErrorKeeper, limits::handler::build_limits_response, limits::helpers::total_spent_since,
+    state::KeeperState,
+};
 
+use super::helpers::{
+    execute_admin_call, find_account_id, find_account_object_id, find_admin_object_id,
+    get_chain_relayers, is_account_frozen, is_chain_paused, is_paused, propose_admin_key,
+    refresh_relayers, set_paused,
+};
+
+/// Path of the keystore holding the zkLogin ephemeral keys, created empty if missing.
+pub fn get_keystore_path() -> PathBuf {
     let keystore_path = env::var("KEYSTORE_PATH").expect("KEYSTORE_PATH must be set");
 
     let path = PathBuf::from(keystore_path);
@@ -21,17 +44,404 @@ pub fn get_account() -> (SuiAddress, PathBuf) {
         fs::write(&path, "[]").expect("Failed to create keystore file");
     }
 
-    let mut file_keystore = FileBasedKeystore::new(&path).unwrap_or_else(|e| {
-        eprintln!("Failed to create keystore at {:?}: {}", path, e);
-        eprintln!("Make sure the keystore directory exists and has proper permissions");
-        panic!("Keystore initialization failed");
-    });
+    path
+}
+
+async fn get_account_balance(
+    keeper_state: &KeeperState,
+    account_id: &str,
+) -> Result<Option<u64>, ErrorKeeper> {
+    let node = keeper_state.squad_connect_client().get_node();
+
+    let account_object_id = ObjectID::from_hex_literal(account_id).map_err(|e| ErrorKeeper {
+        message: e.to_string(),
+        status: 500,
+    })?;
+
+    let fields = node
+        .read_api()
+        .get_dynamic_fields(account_object_id, None, None)
+        .await
+        .map_err(|e| ErrorKeeper {
+            message: e.to_string(),
+            status: 500,
+        })?;
+
+    let Some(field) = fields
+        .data
+        .iter()
+        .find(|field| is_sui_balance_field(&field.name))
+    else {
+        return Ok(None);
+    };
+
+    let object = node
+        .read_api()
+        .get_dynamic_field_object(account_object_id, field.name.clone())
+        .await
+        .map_err(|e| ErrorKeeper {
+            message: e.to_string(),
+            status: 500,
+        })?;
+
+    let content = object
+        .data
+        .and_then(|data| data.content)
+        .and_then(|content| serde_json::to_value(content).ok());
+
+    Ok(content
+        .as_ref()
+        .and_then(|content| content.pointer("/fields/value/fields/balance"))
+        .and_then(|balance| balance.as_str())
+        .and_then(|balance| balance.parse::<u64>().ok()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/stats",
+    summary = "Get operational statistics",
+    description = "Returns the admin and relayer addresses and gas, number of accounts, pause states and spending totals",
+    responses(
+        (status = 200, description = "Operational statistics", body = [AdminStats])
+    )
+)]
+#[axum::debug_handler]
+pub async fn stats(
+    State(keeper_state): State<Arc<KeeperState>>,
+) -> Result<Json<AdminStats>, ErrorKeeper> {
+    let node = keeper_state.squad_connect_client().get_node();
+    let admin = keeper_state.admin();
+    let db = keeper_state.db();
+
+    let account_events = node
+        .event_api()
+        .query_events(
+            EventFilter::MoveEventType(Event::AccountEvent.to_string().parse().unwrap()),
+            None,
+            None,
+            false,
+        )
+        .await
+        .map_err(|e| ErrorKeeper {
+            message: e.to_string(),
+            status: 500,
+        })?;
+
+    let gas_balance = node
+        .coin_read_api()
+        .get_balance(admin, None)
+        .await
+        .map_err(|e| ErrorKeeper {
+            message: e.to_string(),
+            status: 500,
+        })?;
+
+    let admin_object_id = find_admin_object_id(&keeper_state).await?;
+
+    let chain_relayers = get_chain_relayers(&keeper_state, admin_object_id).await?;
+
+    let mut relayers = Vec::new();
+
+    for relayer in keeper_state.relayers() {
+        let relayer = relayer.address();
+
+        let gas_balance = node
+            .coin_read_api()
+            .get_balance(relayer, None)
+            .await
+            .map_err(|e| ErrorKeeper {
+                message: e.to_string(),
+                status: 500,
+            })?;
+
+        relayers.push(RelayerStatus {
+            address: relayer.to_string(),
+            gas_balance: gas_balance.total_balance as u64,
+            authorized: chain_relayers.contains(&relayer.to_string()),
+        });
+    }
+
+    let now = Utc::now();
+
+    Ok(Json(AdminStats {
+        admin_address: admin.to_string(),
+        admin_gas_balance: gas_balance.total_balance as u64,
+        accounts: account_events.data.len(),
+        paused: is_paused(db)?,
+        chain_paused: is_chain_paused(&keeper_state, admin_object_id).await?,
+        relayers,
+        spent_daily: total_spent_since(db, (now - Duration::days(1)).timestamp_millis())?,
+        spent_weekly: total_spent_since(db, (now - Duration::weeks(1)).timestamp_millis())?,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/user/{telegram_id}",
+    summary = "Get user details",
+    description = "Returns the Squad account, SUI balance, freeze state and spending limits of a Telegram user",
+    params(("telegram_id" = String, Path, description = "Telegram ID of the user")),
+    responses(
+        (status = 200, description = "User details", body = [AdminUserInfo])
+    )
+)]
+#[axum::debug_handler]
+pub async fn user_info(
+    State(keeper_state): State<Arc<KeeperState>>,
+    Path(telegram_id): Path<String>,
+) -> Result<Json<AdminUserInfo>, ErrorKeeper> {
+    let node = keeper_state.squad_connect_client().get_node();
+
+    let account_events = node
+        .event_api()
+        .query_events(
+            EventFilter::MoveEventType(Event::AccountEvent.to_string().parse().unwrap()),
+            None,
+            None,
+            false,
+        )
+        .await
+        .map_err(|e| ErrorKeeper {
+            message: e.to_string(),
+            status: 500,
+        })?;
+
+    let account_id = find_account_id(&account_events.data, &telegram_id);
+
+    let (balance, frozen) = match &account_id {
+        Some(account_id) => {
+            let account_object_id =
+                ObjectID::from_hex_literal(account_id).map_err(|e| ErrorKeeper {
+                    message: e.to_string(),
+                    status: 500,
+                })?;
+
+            (
+                get_account_balance(&keeper_state, account_id).await?,
+                Some(is_account_frozen(&keeper_state, account_object_id).await?),
+            )
+        }
+        None => (None, None),
+    };
+
+    let limits = build_limits_response(&keeper_state, &telegram_id, "0x2::sui::SUI".to_string())?;
+
+    Ok(Json(AdminUserInfo {
+        telegram_id,
+        account_id,
+        balance,
+        frozen,
+        limits: Some(limits),
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/pause",
+    summary = "Pause or resume the service",
+    description = "While paused the server refuses to build payment, withdraw, fund and account transactions",
+    request_body = [PauseRequest],
+    responses(
+        (status = 200, description = "Current pause state", body = [PauseResponse])
+    )
+)]
+#[axum::debug_handler]
+pub async fn pause(
+    State(keeper_state): State<Arc<KeeperState>>,
+    Json(pause_request): Json<PauseRequest>,
+) -> Result<Json<PauseResponse>, ErrorKeeper> {
+    set_paused(keeper_state.db(), pause_request.paused)?;
+
+    println!(
+        "Service {} by operator",
+        if pause_request.paused {
+            "paused"
+        } else {
+            "resumed"
+        }
+    );
+
+    Ok(Json(PauseResponse {
+        paused: pause_request.paused,
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/chain-pause",
+    summary = "Emergency pause or unpause the contract",
+    description = "Calls admin::pause or admin::unpause; while paused the contract rejects payments, withdrawals and new accounts",
+    request_body = [PauseRequest],
+    responses(
+        (status = 200, description = "On-chain pause state and transaction digest", body = [ChainPauseResponse])
+    )
+)]
+#[axum::debug_handler]
+pub async fn chain_pause(
+    State(keeper_state): State<Arc<KeeperState>>,
+    Json(pause_request): Json<PauseRequest>,
+) -> Result<Json<ChainPauseResponse>, ErrorKeeper> {
+    let admin_object_id = find_admin_object_id(&keeper_state).await?;
+
+    let function = if pause_request.paused {
+        "pause"
+    } else {
+        "unpause"
+    };
+
+    let digest = execute_admin_call(
+        &keeper_state,
+        "admin",
+        function,
+        vec![SuiJsonValue::from_object_id(admin_object_id)],
+    )
+    .await?;
+
+    println!("Contract {} by operator: {}", function, digest);
+
+    Ok(Json(ChainPauseResponse {
+        paused: pause_request.paused,
+        digest,
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/freeze",
+    summary = "Freeze or unfreeze an account",
+    description = "Calls account::freeze_account or account::unfreeze_account; a frozen account cannot pay or withdraw",
+    request_body = [FreezeRequest],
+    responses(
+        (status = 200, description = "Account freeze state and transaction digest", body = [FreezeResponse])
+    )
+)]
+#[axum::debug_handler]
+pub async fn freeze(
+    State(keeper_state): State<Arc<KeeperState>>,
+    Json(freeze_request): Json<FreezeRequest>,
+) -> Result<Json<FreezeResponse>, ErrorKeeper> {
+    let admin_object_id = find_admin_object_id(&keeper_state).await?;
+
+    let account_object_id = find_account_object_id(&keeper_state, &freeze_request.telegram_id)
+        .await?
+        .ok_or_else(|| ErrorKeeper {
+            message: "Account not found".to_string(),
+            status: 404,
+        })?;
+
+    let function = if freeze_request.frozen {
+        "freeze_account"
+    } else {
+        "unfreeze_account"
+    };
+
+    let digest = execute_admin_call(
+        &keeper_state,
+        "account",
+        function,
+        vec![
+            SuiJsonValue::from_object_id(account_object_id),
+            SuiJsonValue::from_object_id(admin_object_id),
+        ],
+    )
+    .await?;
+
+    println!(
+        "Account of {} {} by operator: {}",
+        freeze_request.telegram_id,
+        if freeze_request.frozen {
+            "frozen"
+        } else {
+            "unfrozen"
+        },
+        digest
+    );
+
+    Ok(Json(FreezeResponse {
+        telegram_id: freeze_request.telegram_id,
+        frozen: freeze_request.frozen,
+        digest,
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/rotate",
+    summary = "Rotate the admin key",
+    description = "Proposes the address as the new on-chain admin and sends it gas to accept; the new key accepts with `sui-squad-server accept-admin`, then the server switches to it once its signer configuration holds the new key",
+    request_body = [RotateAdminRequest],
+    responses(
+        (status = 200, description = "Current and proposed admin addresses with the proposal transactions", body = [RotateAdminResponse])
+    )
+)]
+#[axum::debug_handler]
+pub async fn rotate(
+    State(keeper_state): State<Arc<KeeperState>>,
+    Json(rotate_request): Json<RotateAdminRequest>,
+) -> Result<Json<RotateAdminResponse>, ErrorKeeper> {
+    let address = normalize_sui_address(&rotate_request.address)
+        .and_then(|address| SuiAddress::from_str(&address).ok())
+        .ok_or_else(|| ErrorKeeper {
+            message: format!("Invalid Sui address: {}", rotate_request.address),
+            status: 400,
+        })?;
+
+    let response = propose_admin_key(&keeper_state, address).await?;
+
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/relayer",
+    summary = "Authorize or revoke a relayer",
+    description = "Calls admin::add_relayer or admin::remove_relayer; authorized relayers can sign payments, withdrawals and account creation",
+    request_body = [RelayerRequest],
+    responses(
+        (status = 200, description = "Relayer state and transaction digest", body = [RelayerResponse])
+    )
+)]
+#[axum::debug_handler]
+pub async fn relayer(
+    State(keeper_state): State<Arc<KeeperState>>,
+    Json(relayer_request): Json<RelayerRequest>,
+) -> Result<Json<RelayerResponse>, ErrorKeeper> {
+    let address = normalize_sui_address(&relayer_request.address).ok_or_else(|| ErrorKeeper {
+        message: format!("Invalid Sui address: {}", relayer_request.address),
+        status: 400,
+    })?;
+
+    let admin_object_id = find_admin_object_id(&keeper_state).await?;
+
+    let function = if relayer_request.authorized {
+        "add_relayer"
+    } else {
+        "remove_relayer"
+    };
+
+    let digest = execute_admin_call(
+        &keeper_state,
+        "admin",
+        function,
+        vec![
+            SuiJsonValue::from_object_id(admin_object_id),
+            SuiJsonValue::new(serde_json::Value::String(address.clone())).map_err(|e| {
+                ErrorKeeper {
+                    message: e.to_string(),
+                    status: 500,
+                }
+            })?,
+        ],
+    )
+    .await?;
 
-    let sender = file_keystore
-        .import_from_mnemonic(seed, SignatureScheme::ED25519, None, None)
-        .expect("Failed to import from mnemonic");
+    println!("Relayer {} {}: {}", address, function, digest);
 
-    println!("Sender: {}", sender);
+    refresh_relayers(&keeper_state).await?;
 
-    (sender, path)
+    Ok(Json(RelayerResponse {
+        address,
+        authorized: relayer_request.authorized,
+        digest,
+    }))
 }
@@ -0,0 +1,649 @@
+use std::{env, str::FromStr, sync::Arc, time::Duration};
+
+use serde_json::{Value, json};
+use sled::Db;
+use sui_sdk::{
+    SuiClient,
+    json::SuiJsonValue,
+    rpc_types::{EventFilter, SuiEvent, SuiObjectDataOptions, SuiTransactionBlockResponseOptions},
+    types::{
+        TypeTag,
+        base_types::{ObjectID, SuiAddress},
+        dynamic_field::DynamicFieldName,
+        quorum_driver_types::ExecuteTransactionRequestType,
+        transaction::{Transaction, TransactionData},
+    },
+};
+use sui_squad_core::{
+    helpers::dtos::RotateAdminResponse,
+    package::dto::Event,
+    signer::{InMemorySigner, Signer, SignerConfig},
+};
+
+use crate::{error::ErrorKeeper, state::KeeperState};
+
+const ADMIN_TREE: &str = "admin";
+const PAUSED_KEY: &str = "paused";
+
+/// SUI handed to the new admin key so it can pay for accepting the rotation.
+const ROTATION_GAS: u64 = 100_000_000;
+
+fn db_error(e: impl ToString) -> ErrorKeeper {
+    ErrorKeeper {
+        message: e.to_string(),
+        status: 500,
+    }
+}
+
+pub fn is_paused(db: &Db) -> Result<bool, ErrorKeeper> {
+    let tree = db.open_tree(ADMIN_TREE).map_err(db_error)?;
+
+    Ok(tree
+        .get(PAUSED_KEY)
+        .map_err(db_error)?
+        .map(|value| value.as_ref() == [1])
+        .unwrap_or(false))
+}
+
+pub fn set_paused(db: &Db, paused: bool) -> Result<(), ErrorKeeper> {
+    let tree = db.open_tree(ADMIN_TREE).map_err(db_error)?;
+
+    tree.insert(PAUSED_KEY, vec![paused as u8])
+        .map_err(db_error)?;
+
+    Ok(())
+}
+
+/// Refuse to build transactions while an operator has paused the service.
+pub fn ensure_not_paused(db: &Db) -> Result<(), ErrorKeeper> {
+    if is_paused(db)? {
+        return Err(ErrorKeeper {
+            message: "Sui Squad is paused by an operator, please try again later".to_string(),
+            status: 503,
+        });
+    }
+
+    Ok(())
+}
+
+fn node_error(e: impl ToString) -> ErrorKeeper {
+    ErrorKeeper {
+        message: e.to_string(),
+        status: 500,
+    }
+}
+
+pub fn find_account_id(events: &[SuiEvent], telegram_id: &str) -> Option<String> {
+    events
+        .iter()
+        .find(|event| {
+            event
+                .parsed_json
+                .get("telegram_id")
+                .and_then(|id| id.as_str())
+                .map(|id| id == telegram_id)
+                .unwrap_or(false)
+        })
+        .and_then(|event| event.parsed_json.get("account_id"))
+        .and_then(|id| id.as_str())
+        .map(|id| id.to_string())
+}
+
+/// Object id of the shared `Admin` object created by the relayer.
+pub async fn find_admin_object_id(keeper_state: &KeeperState) -> Result<ObjectID, ErrorKeeper> {
+    let node = keeper_state.squad_connect_client().get_node();
+    let admin = keeper_state.admin().to_string();
+
+    let admin_events = node
+        .event_api()
+        .query_events(
+            EventFilter::MoveEventType(Event::AdminEvent.to_string().parse().unwrap()),
+            None,
+            None,
+            false,
+        )
+        .await
+        .map_err(node_error)?;
+
+    let admin_id = admin_events
+        .data
+        .iter()
+        .find(|event| {
+            event
+                .parsed_json
+                .get("wallet")
+                .and_then(|wallet| wallet.as_str())
+                .map(|wallet| wallet == admin)
+                .unwrap_or(false)
+        })
+        .and_then(|event| event.parsed_json.get("admin_id"))
+        .and_then(|id| id.as_str())
+        .ok_or_else(|| ErrorKeeper {
+            message: "Admin not found".to_string(),
+            status: 404,
+        })?;
+
+    ObjectID::from_hex_literal(admin_id).map_err(node_error)
+}
+
+/// Object id of the Squad account of a Telegram user, if it exists.
+pub async fn find_account_object_id(
+    keeper_state: &KeeperState,
+    telegram_id: &str,
+) -> Result<Option<ObjectID>, ErrorKeeper> {
+    let node = keeper_state.squad_connect_client().get_node();
+
+    let account_events = node
+        .event_api()
+        .query_events(
+            EventFilter::MoveEventType(Event::AccountEvent.to_string().parse().unwrap()),
+            None,
+            None,
+            false,
+        )
+        .await
+        .map_err(node_error)?;
+
+    find_account_id(&account_events.data, telegram_id)
+        .map(|id| ObjectID::from_hex_literal(&id).map_err(node_error))
+        .transpose()
+}
+
+/// Content of the dynamic field keyed by the empty struct `key` of the package, such as
+/// `admin::PausedKey`, or `None` when the object does not carry it.
+pub async fn get_key_field(
+    keeper_state: &KeeperState,
+    object_id: ObjectID,
+    key: &str,
+) -> Result<Option<Value>, ErrorKeeper> {
+    let package_id = env::var("SUI_SQUAD_PACKAGE_ID").map_err(node_error)?;
+    let node = keeper_state.squad_connect_client().get_node();
+
+    let name = DynamicFieldName {
+        type_: TypeTag::from_str(&format!("{}::{}", package_id, key)).map_err(node_error)?,
+        value: json!({ "dummy_field": false }),
+    };
+
+    let field = node
+        .read_api()
+        .get_dynamic_field_object(object_id, name)
+        .await
+        .map_err(node_error)?;
+
+    Ok(field
+        .data
+        .and_then(|data| data.content)
+        .and_then(|content| serde_json::to_value(content).ok()))
+}
+
+/// Relayer addresses currently authorized on the `Admin` object.
+pub async fn get_chain_relayers(
+    keeper_state: &KeeperState,
+    admin_object_id: ObjectID,
+) -> Result<Vec<String>, ErrorKeeper> {
+    let field = get_key_field(keeper_state, admin_object_id, "admin::RelayersKey").await?;
+
+    Ok(field
+        .as_ref()
+        .and_then(|field| field.pointer("/fields/value/fields/contents"))
+        .and_then(|contents| contents.as_array())
+        .map(|contents| {
+            contents
+                .iter()
+                .filter_map(|relayer| relayer.as_str().map(|relayer| relayer.to_string()))
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Rotate relayed transactions only over the configured relayer keys the `Admin` object
+/// authorizes, so keys missing on chain don't sign transactions the contract would abort.
+pub async fn refresh_relayers(keeper_state: &KeeperState) -> Result<(), ErrorKeeper> {
+    let admin_object_id = find_admin_object_id(keeper_state).await?;
+
+    let authorized: Vec<SuiAddress> = get_chain_relayers(keeper_state, admin_object_id)
+        .await?
+        .iter()
+        .filter_map(|relayer| SuiAddress::from_str(relayer).ok())
+        .collect();
+
+    for relayer in keeper_state.relayers() {
+        if !authorized.contains(&relayer.address()) {
+            println!(
+                "WARNING: relayer {} is not authorized on chain, it won't sign transactions",
+                relayer.address()
+            );
+        }
+    }
+
+    keeper_state.set_authorized_relayers(authorized);
+
+    Ok(())
+}
+
+pub async fn is_chain_paused(
+    keeper_state: &KeeperState,
+    admin_object_id: ObjectID,
+) -> Result<bool, ErrorKeeper> {
+    Ok(
+        get_key_field(keeper_state, admin_object_id, "admin::PausedKey")
+            .await?
+            .is_some(),
+    )
+}
+
+pub async fn is_account_frozen(
+    keeper_state: &KeeperState,
+    account_object_id: ObjectID,
+) -> Result<bool, ErrorKeeper> {
+    Ok(
+        get_key_field(keeper_state, account_object_id, "account::FrozenKey")
+            .await?
+            .is_some(),
+    )
+}
+
+/// Refuse to build transactions the contract would abort because of the emergency pause.
+pub async fn ensure_chain_not_paused(
+    keeper_state: &KeeperState,
+    admin_object_id: ObjectID,
+) -> Result<(), ErrorKeeper> {
+    if is_chain_paused(keeper_state, admin_object_id).await? {
+        return Err(ErrorKeeper {
+            message: "Sui Squad is under an emergency pause, payments, withdrawals and new accounts are disabled"
+                .to_string(),
+            status: 503,
+        });
+    }
+
+    Ok(())
+}
+
+/// Refuse to build transactions the contract would abort because of the emergency pause
+/// or an account freeze, so the user gets a clear error instead of a failed transaction.
+pub async fn ensure_chain_allows(
+    keeper_state: &KeeperState,
+    admin_object_id: ObjectID,
+    account_object_id: ObjectID,
+) -> Result<(), ErrorKeeper> {
+    ensure_chain_not_paused(keeper_state, admin_object_id).await?;
+
+    if is_account_frozen(keeper_state, account_object_id).await? {
+        return Err(ErrorKeeper {
+            message:
+                "This Squad account is frozen by an operator, payments and withdrawals are disabled"
+                    .to_string(),
+            status: 403,
+        });
+    }
+
+    Ok(())
+}
+
+async fn sign_and_execute(
+    node: &SuiClient,
+    signer: &dyn Signer,
+    tx: TransactionData,
+) -> Result<String, ErrorKeeper> {
+    let signature = signer.sign_transaction(&tx).await.map_err(node_error)?;
+
+    let transaction_response = node
+        .quorum_driver_api()
+        .execute_transaction_block(
+            Transaction::from_data(tx, vec![signature]),
+            SuiTransactionBlockResponseOptions::full_content(),
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+        )
+        .await
+        .map_err(node_error)?;
+
+    println!("{}", transaction_response);
+
+    Ok(transaction_response.digest.to_string())
+}
+
+/// Build, sign with `signer` and execute a call to one of the package's functions.
+pub async fn execute_move_call(
+    node: &SuiClient,
+    signer: &dyn Signer,
+    module: &str,
+    function: &str,
+    arguments: Vec<SuiJsonValue>,
+) -> Result<String, ErrorKeeper> {
+    let package_id = env::var("SUI_SQUAD_PACKAGE_ID").map_err(node_error)?;
+
+    let package_object_id = ObjectID::from_hex_literal(&package_id).map_err(node_error)?;
+
+    let tx = node
+        .transaction_builder()
+        .move_call(
+            signer.address(),
+            package_object_id,
+            module,
+            function,
+            vec![],
+            arguments,
+            None,
+            10_000_000,
+            None,
+        )
+        .await
+        .map_err(node_error)?;
+
+    sign_and_execute(node, signer, tx).await
+}
+
+/// Build, sign with the relayer key and execute a call to one of the package's admin-only functions.
+pub async fn execute_admin_call(
+    keeper_state: &KeeperState,
+    module: &str,
+    function: &str,
+    arguments: Vec<SuiJsonValue>,
+) -> Result<String, ErrorKeeper> {
+    execute_move_call(
+        &keeper_state.squad_connect_client().get_node(),
+        keeper_state.admin_signer().as_ref(),
+        module,
+        function,
+        arguments,
+    )
+    .await
+}
+
+/// Load the extra relayer keys from the comma-separated mnemonics in `RELAYER_SEEDS`.
+pub fn load_relayers() -> Vec<Arc<dyn Signer>> {
+    let Ok(seeds) = env::var("RELAYER_SEEDS") else {
+        return Vec::new();
+    };
+
+    seeds
+        .split(',')
+        .filter(|seed| !seed.trim().is_empty())
+        .map(|seed| {
+            let relayer =
+                InMemorySigner::from_mnemonic(seed).expect("Failed to load relayer from mnemonic");
+
+            println!("Relayer: {}", relayer.address());
+
+            Arc::new(relayer) as Arc<dyn Signer>
+        })
+        .collect()
+}
+
+/// Refuse to start with a signer the `Admin` object no longer recognizes, such as the old key
+/// after a rotation was accepted, instead of failing every admin and relayed transaction.
+pub async fn check_active_admin(keeper_state: &KeeperState) -> Result<(), ErrorKeeper> {
+    let admin = keeper_state.admin();
+    let admin_object_id = find_admin_object_id(keeper_state).await?;
+    let chain_admin = get_chain_admin(keeper_state, admin_object_id).await?;
+
+    if chain_admin != admin {
+        return Err(ErrorKeeper {
+            message: format!(
+                "The configured signer {} is not the on-chain admin {}; start the server with the signer configuration of the current admin key",
+                admin, chain_admin
+            ),
+            status: 500,
+        });
+    }
+
+    Ok(())
+}
+
+/// Address stored as the admin in the `Admin` object.
+async fn get_chain_admin(
+    keeper_state: &KeeperState,
+    admin_object_id: ObjectID,
+) -> Result<SuiAddress, ErrorKeeper> {
+    let node = keeper_state.squad_connect_client().get_node();
+
+    let object = node
+        .read_api()
+        .get_object_with_options(admin_object_id, SuiObjectDataOptions::new().with_content())
+        .await
+        .map_err(node_error)?;
+
+    object
+        .data
+        .and_then(|data| data.content)
+        .and_then(|content| serde_json::to_value(content).ok())
+        .and_then(|content| {
+            content
+                .pointer("/fields/account")
+                .and_then(|account| account.as_str())
+                .and_then(|account| SuiAddress::from_str(account).ok())
+        })
+        .ok_or_else(|| ErrorKeeper {
+            message: "Admin object has no account".to_string(),
+            status: 500,
+        })
+}
+
+/// First step of a key rotation: nominate `new_admin` as the on-chain admin and hand it
+/// enough gas to accept. The server keeps signing with the current key; the new key accepts
+/// on its own with `sui-squad-server accept-admin`, so its secret never reaches the server
+/// until [`watch_admin_rotation`] loads it from the updated signer configuration.
+pub async fn propose_admin_key(
+    keeper_state: &KeeperState,
+    new_admin: SuiAddress,
+) -> Result<RotateAdminResponse, ErrorKeeper> {
+    let admin_signer = keeper_state.admin_signer();
+    let admin = admin_signer.address();
+
+    if new_admin == admin {
+        return Err(ErrorKeeper {
+            message: "The new key is already the admin".to_string(),
+            status: 400,
+        });
+    }
+
+    let node = keeper_state.squad_connect_client().get_node();
+    let admin_object_id = find_admin_object_id(keeper_state).await?;
+    let mut digests = Vec::new();
+
+    digests.push(
+        execute_move_call(
+            &node,
+            admin_signer.as_ref(),
+            "admin",
+            "propose_admin",
+            vec![
+                SuiJsonValue::from_object_id(admin_object_id),
+                SuiJsonValue::new(Value::String(new_admin.to_string())).map_err(node_error)?,
+            ],
+        )
+        .await?,
+    );
+
+    let coins = node
+        .coin_read_api()
+        .get_coins(admin, None, None, None)
+        .await
+        .map_err(node_error)?;
+
+    let gas_coin = coins
+        .data
+        .iter()
+        .max_by_key(|coin| coin.balance)
+        .ok_or_else(|| ErrorKeeper {
+            message: "The current admin has no SUI to pay for the rotation".to_string(),
+            status: 400,
+        })?;
+
+    let tx = node
+        .transaction_builder()
+        .transfer_sui(
+            admin,
+            gas_coin.coin_object_id,
+            10_000_000,
+            new_admin,
+            Some(ROTATION_GAS),
+        )
+        .await
+        .map_err(node_error)?;
+
+    digests.push(sign_and_execute(&node, admin_signer.as_ref(), tx).await?);
+
+    println!("Admin rotation proposed from {} to {}", admin, new_admin);
+
+    Ok(RotateAdminResponse {
+        admin: admin.to_string(),
+        pending_admin: new_admin.to_string(),
+        digests,
+    })
+}
+
+/// Object id of the `Admin` object on which `pending_admin` was nominated by the last rotation.
+pub async fn find_pending_rotation(
+    node: &SuiClient,
+    pending_admin: SuiAddress,
+) -> Result<ObjectID, ErrorKeeper> {
+    let pending_admin = pending_admin.to_string();
+
+    let rotation_events = node
+        .event_api()
+        .query_events(
+            EventFilter::MoveEventType(Event::AdminRotationEvent.to_string().parse().unwrap()),
+            None,
+            None,
+            true,
+        )
+        .await
+        .map_err(node_error)?;
+
+    let admin_id = rotation_events
+        .data
+        .iter()
+        .find(|event| {
+            event
+                .parsed_json
+                .get("proposed")
+                .and_then(|proposed| proposed.as_str())
+                .map(|proposed| proposed == pending_admin)
+                .unwrap_or(false)
+        })
+        .and_then(|event| event.parsed_json.get("admin_id"))
+        .and_then(|id| id.as_str())
+        .ok_or_else(|| ErrorKeeper {
+            message: format!("No admin rotation was proposed to {}", pending_admin),
+            status: 404,
+        })?;
+
+    ObjectID::from_hex_literal(admin_id).map_err(node_error)
+}
+
+/// Admin accepted by the last completed rotation of the `Admin` object, if it was ever rotated.
+pub async fn find_accepted_rotation(
+    node: &SuiClient,
+    admin_object_id: ObjectID,
+) -> Result<Option<SuiAddress>, ErrorKeeper> {
+    let admin_object_id = admin_object_id.to_string();
+
+    let rotation_events = node
+        .event_api()
+        .query_events(
+            EventFilter::MoveEventType(Event::AdminRotationEvent.to_string().parse().unwrap()),
+            None,
+            None,
+            true,
+        )
+        .await
+        .map_err(node_error)?;
+
+    rotation_events
+        .data
+        .iter()
+        .find(|event| {
+            event.parsed_json.get("admin_id").and_then(|id| id.as_str())
+                == Some(admin_object_id.as_str())
+                && event
+                    .parsed_json
+                    .get("accepted")
+                    .and_then(|accepted| accepted.as_bool())
+                    .unwrap_or(false)
+        })
+        .and_then(|event| event.parsed_json.get("proposed"))
+        .and_then(|proposed| proposed.as_str())
+        .map(|proposed| SuiAddress::from_str(proposed).map_err(node_error))
+        .transpose()
+}
+
+/// Follow a rotation accepted on chain: stop relaying with the old key at once, and switch to
+/// the new key as soon as the signer configuration holds it.
+async fn follow_admin_rotation(keeper_state: &KeeperState) -> Result<(), ErrorKeeper> {
+    let node = keeper_state.squad_connect_client().get_node();
+    let admin_object_id = find_admin_object_id(keeper_state).await?;
+
+    let Some(accepted) = find_accepted_rotation(&node, admin_object_id).await? else {
+        return Ok(());
+    };
+
+    if accepted == keeper_state.admin() {
+        return Ok(());
+    }
+
+    keeper_state.retire_admin();
+
+    // The operator points `.env` (or the keystore or signer process) at the new key
+    dotenvy::dotenv_override().ok();
+
+    let signer = SignerConfig::from_env()
+        .map_err(node_error)?
+        .load()
+        .await
+        .map_err(node_error)?;
+
+    if signer.address() != accepted {
+        return Err(ErrorKeeper {
+            message: format!(
+                "{} accepted the admin rotation but the signer configuration holds {}",
+                accepted,
+                signer.address()
+            ),
+            status: 500,
+        });
+    }
+
+    if keeper_state
+        .sponsor()
+        .is_some_and(|sponsor| sponsor.address() == accepted)
+    {
+        return Err(ErrorKeeper {
+            message: "The new admin key must not be the gas sponsor key".to_string(),
+            status: 500,
+        });
+    }
+
+    println!(
+        "Admin key switched from {} to {}",
+        keeper_state.admin(),
+        accepted
+    );
+
+    keeper_state.set_admin(signer);
+
+    Ok(())
+}
+
+/// Switch the admin signer without a restart once a rotation is accepted on chain.
+pub async fn watch_admin_rotation(keeper_state: Arc<KeeperState>) {
+    let watch_secs = env::var("ADMIN_WATCH_SECS")
+        .ok()
+        .and_then(|secs| secs.parse().ok())
+        .unwrap_or(30);
+
+    if watch_secs == 0 {
+        println!("Admin rotation watcher disabled");
+        return;
+    }
+
+    let mut interval = tokio::time::interval(Duration::from_secs(watch_secs));
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = follow_admin_rotation(&keeper_state).await {
+            println!("Failed to follow the admin rotation: {}", e.message);
+        }
+    }
+}
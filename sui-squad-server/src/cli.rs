@@ -0,0 +1,148 @@
+use std::{env, io, path::PathBuf};
+
+use sui_sdk::json::SuiJsonValue;
+use sui_squad_core::{
+    helpers::{
+        dtos::{RotateAdminRequest, RotateAdminResponse},
+        jwt::JwtManager,
+    },
+    permissions::admin_ids_from_env,
+    signer::{EncryptedFileSigner, SignerConfig},
+};
+
+use crate::{
+    admin::helpers::{execute_move_call, find_pending_rotation},
+    router::{build_node, network_from_str},
+};
+
+fn read_secret(name: &str, prompt: &str) -> Result<String, String> {
+    if let Ok(value) = env::var(name) {
+        return Ok(value);
+    }
+
+    println!("{}", prompt);
+
+    let mut value = String::new();
+    io::stdin()
+        .read_line(&mut value)
+        .map_err(|e| e.to_string())?;
+
+    Ok(value.trim_end_matches(['\n', '\r']).to_string())
+}
+
+/// `sui-squad-server encrypt-keystore`: write `SEED` (or a mnemonic read from stdin) to the
+/// passphrase-encrypted keystore at `SIGNER_KEYSTORE_PATH`, for use with `SIGNER=encrypted`.
+pub fn encrypt_keystore() -> Result<(), String> {
+    let path = env::var("SIGNER_KEYSTORE_PATH").map_err(|_| "SIGNER_KEYSTORE_PATH must be set")?;
+
+    let seed = read_secret("SEED", "Enter the mnemonic of the admin key:")?;
+    let passphrase = read_secret("SIGNER_PASSPHRASE", "Enter the keystore passphrase:")?;
+
+    let address = EncryptedFileSigner::write(&PathBuf::from(&path), &seed, &passphrase)
+        .map_err(|e| e.to_string())?;
+
+    println!("Encrypted key for {} written to {}", address, path);
+
+    Ok(())
+}
+
+/// `sui-squad-server rotate-admin`: ask the running server to propose `NEW_ADMIN_ADDRESS` (or
+/// an address read from stdin) as the new admin, authenticated as the first operator in
+/// `ADMIN_TELEGRAM_IDS`. Only the address is sent; the new key accepts with `accept-admin`.
+pub async fn rotate_admin() -> Result<(), String> {
+    let address = read_secret(
+        "NEW_ADMIN_ADDRESS",
+        "Enter the address of the new admin key:",
+    )?;
+
+    let address = address.trim().trim_matches('"').to_string();
+
+    if address.is_empty() {
+        return Err("No address provided".to_string());
+    }
+
+    let operator = admin_ids_from_env()
+        .into_iter()
+        .next()
+        .ok_or("ADMIN_TELEGRAM_IDS must contain at least one operator")?;
+
+    let token = JwtManager::new()
+        .generate_token_for_id(operator as u64)
+        .map_err(|e| e.to_string())?;
+
+    let server_url = env::var("SERVER_URL").unwrap_or_else(|_| {
+        format!(
+            "http://{}",
+            env::var("SERVER_DOMAIN").unwrap_or("localhost".to_string())
+        )
+    });
+
+    let response = reqwest::Client::new()
+        .post(format!("{}/admin/rotate", server_url))
+        .bearer_auth(token)
+        .json(&RotateAdminRequest { address })
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("Rotation failed with status {}: {}", status, body));
+    }
+
+    let rotation = response
+        .json::<RotateAdminResponse>()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    println!(
+        "Admin rotation proposed from {} to {}",
+        rotation.admin, rotation.pending_admin
+    );
+
+    for digest in rotation.digests {
+        println!("  tx {}", digest);
+    }
+
+    println!("Run `sui-squad-server accept-admin` with the signer configuration of the new key.");
+
+    Ok(())
+}
+
+/// `sui-squad-server accept-admin`: accept a proposed rotation with the key of the signer
+/// configuration (`SIGNER`, `SEED`, ...), signing locally so the key never leaves this host.
+pub async fn accept_admin() -> Result<(), String> {
+    let network = env::var("SUI_NETWORK").map_err(|_| "SUI_NETWORK must be set")?;
+
+    let signer = SignerConfig::from_env()
+        .map_err(|e| e.to_string())?
+        .load()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let node = build_node(&network_from_str(&network))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let admin_object_id = find_pending_rotation(&node, signer.address())
+        .await
+        .map_err(|e| e.message)?;
+
+    let digest = execute_move_call(
+        &node,
+        signer.as_ref(),
+        "admin",
+        "accept_admin",
+        vec![SuiJsonValue::from_object_id(admin_object_id)],
+    )
+    .await
+    .map_err(|e| e.message)?;
+
+    println!("{} is now the admin: tx {}", signer.address(), digest);
+    println!(
+        "Point the server's signer configuration at this key; it switches over without a restart. Then move the SUI left on the old key."
+    );
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// `session` is the token of a custody login link the bot issued with `/custody`.
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CustodyStatusQuery {
+    pub session: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct CustodyStatusResponse {
+    /// zkLogin address allowed to withdraw without the relayer, if self-custody is enabled.
+    pub owner: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct EnableCustodyRequest {
+    pub session: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct EnableCustodyResponse {
+    pub owner: String,
+    pub digest: String,
+}
+
+/// Amounts are decimal strings in the smallest unit of the coin (MIST for SUI), since JSON
+/// numbers lose precision above 2^53.
+#[derive(Deserialize, ToSchema)]
+pub struct CustodyWithdrawRequest {
+    pub session: String,
+    pub amount: String,
+    /// A Sui address or a SuiNS name such as `alice.sui`.
+    pub address: String,
+    pub coin_type: Option<String>,
+}
@@ -0,0 +1,315 @@
+use std::{env, sync::Arc};
+
+use axum::{
+    extract::{Json, Query, State},
+    http::HeaderMap,
+};
+use sui_sdk::{json::SuiJsonValue, types::base_types::ObjectID};
+use sui_squad_core::helpers::dtos::{Notification, NotificationKind, WithdrawResponse};
+
+use crate::{
+    admin::helpers::{
+        ensure_chain_allows, ensure_not_paused, execute_admin_call, find_account_object_id,
+        find_admin_object_id,
+    },
+    error::ErrorKeeper,
+    fund::helpers::{SUI_COIN_TYPE, parse_amount, zk_login_address, zk_login_client},
+    notifications::helpers::{executed, notify},
+    sessions::helpers::consume_session,
+    state::KeeperState,
+    withdraw::helpers::resolve_recipient,
+};
+
+use super::{
+    dto::{
+        CustodyStatusQuery, CustodyStatusResponse, CustodyWithdrawRequest, EnableCustodyRequest,
+        EnableCustodyResponse,
+    },
+    helpers::{
+        build_enable_custody_transaction, build_owner_withdraw_transaction, custody_owner,
+        custody_session, execute_as_owner,
+    },
+};
+
+fn node_error(e: impl ToString) -> ErrorKeeper {
+    ErrorKeeper {
+        message: e.to_string(),
+        status: 500,
+    }
+}
+
+async fn account_object_id(
+    keeper_state: &KeeperState,
+    telegram_id: &str,
+) -> Result<ObjectID, ErrorKeeper> {
+    find_account_object_id(keeper_state, telegram_id)
+        .await?
+        .ok_or_else(|| ErrorKeeper {
+            message: "Account not found".to_string(),
+            status: 404,
+        })
+}
+
+#[utoipa::path(
+    get,
+    path = "/custody/status",
+    summary = "Get the self-custody owner of the user's account",
+    description = "Returns the zkLogin address registered as owner of the account behind a custody login session, if any",
+    params(CustodyStatusQuery),
+    responses(
+        (status = 200, description = "Self-custody status", body = [CustodyStatusResponse])
+    )
+)]
+#[axum::debug_handler]
+pub async fn status(
+    State(keeper_state): State<Arc<KeeperState>>,
+    Query(query): Query<CustodyStatusQuery>,
+) -> Result<Json<CustodyStatusResponse>, ErrorKeeper> {
+    let session = custody_session(keeper_state.db(), &query.session)?;
+
+    let account_object_id = account_object_id(&keeper_state, &session.telegram_id).await?;
+
+    let owner = custody_owner(&keeper_state, account_object_id).await?;
+
+    Ok(Json(CustodyStatusResponse {
+        owner: owner.map(|owner| owner.to_string()),
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/custody/enable",
+    summary = "Enable self-custody",
+    description = "Proposes the zkLogin address of the id_token sent as a Bearer token as the owner of the user's account and accepts with a transaction signed by that address, so it can withdraw with its own signature; gas is sponsored when sponsorship is enabled",
+    request_body = [EnableCustodyRequest],
+    responses(
+        (status = 200, description = "The registered owner", body = [EnableCustodyResponse]),
+        (status = 409, description = "Self-custody is already enabled", body = [String])
+    )
+)]
+#[axum::debug_handler]
+pub async fn enable(
+    State(keeper_state): State<Arc<KeeperState>>,
+    headers: HeaderMap,
+    Json(enable_request): Json<EnableCustodyRequest>,
+) -> Result<Json<EnableCustodyResponse>, ErrorKeeper> {
+    ensure_not_paused(keeper_state.db())?;
+
+    let session = custody_session(keeper_state.db(), &enable_request.session)?;
+
+    let account_object_id = account_object_id(&keeper_state, &session.telegram_id).await?;
+    let admin_object_id = find_admin_object_id(&keeper_state).await?;
+
+    ensure_chain_allows(&keeper_state, admin_object_id, account_object_id).await?;
+
+    if let Some(owner) = custody_owner(&keeper_state, account_object_id).await? {
+        return Err(ErrorKeeper {
+            message: format!("Self-custody is already enabled for {}", owner),
+            status: 409,
+        });
+    }
+
+    let mut squad_connect_client = zk_login_client(
+        &keeper_state,
+        &headers,
+        session.randomness.clone(),
+        session.public_key.clone(),
+        session.max_epoch,
+    )?;
+
+    let owner = zk_login_address(&mut squad_connect_client).await?;
+
+    let package_id = env::var("SUI_SQUAD_PACKAGE_ID").map_err(node_error)?;
+    let package_object_id = ObjectID::from_hex_literal(&package_id).map_err(node_error)?;
+
+    // The relayer vouches that the address belongs to the Telegram user, the owner accepts
+    execute_admin_call(
+        &keeper_state,
+        "account",
+        "propose_self_custody",
+        vec![
+            SuiJsonValue::from_object_id(account_object_id),
+            SuiJsonValue::from_object_id(admin_object_id),
+            SuiJsonValue::from_object_id(ObjectID::from_address(owner)),
+        ],
+    )
+    .await?;
+
+    let node = keeper_state.squad_connect_client().get_node();
+    let sponsor = keeper_state.sponsor();
+
+    let tx = build_enable_custody_transaction(
+        &node,
+        owner,
+        sponsor.as_ref().map(|sponsor| sponsor.address()),
+        package_object_id,
+        account_object_id,
+        admin_object_id,
+    )
+    .await?;
+
+    let transaction_response = execute_as_owner(
+        &keeper_state,
+        &mut squad_connect_client,
+        &session,
+        owner,
+        sponsor,
+        package_object_id,
+        tx,
+    )
+    .await?;
+
+    println!("{}", transaction_response);
+
+    if !executed(&transaction_response) {
+        return Err(ErrorKeeper {
+            message: format!(
+                "Enabling self-custody failed: {}",
+                transaction_response.digest
+            ),
+            status: 500,
+        });
+    }
+
+    let digest = transaction_response.digest.to_string();
+
+    notify(
+        keeper_state.db(),
+        Notification {
+            id: 0,
+            telegram_id: session.telegram_id,
+            kind: NotificationKind::SelfCustodyEnabled,
+            amount: 0,
+            coin_type: String::new(),
+            sender: None,
+            recipient: Some(owner.to_string()),
+            digest: digest.clone(),
+            timestamp: 0,
+        },
+    );
+
+    Ok(Json(EnableCustodyResponse {
+        owner: owner.to_string(),
+        digest,
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/custody/withdraw",
+    summary = "Withdraw as the account owner",
+    description = "Withdraws from the user's account with a transaction signed by the owner's zkLogin address instead of a relayer; gas is sponsored when sponsorship is enabled",
+    request_body = [CustodyWithdrawRequest],
+    responses(
+        (status = 200, description = "Digest of the withdrawal", body = [WithdrawResponse]),
+        (status = 403, description = "The signed-in address is not the account owner", body = [String])
+    )
+)]
+#[axum::debug_handler]
+pub async fn withdraw(
+    State(keeper_state): State<Arc<KeeperState>>,
+    headers: HeaderMap,
+    Json(withdraw_request): Json<CustodyWithdrawRequest>,
+) -> Result<Json<WithdrawResponse>, ErrorKeeper> {
+    ensure_not_paused(keeper_state.db())?;
+
+    let amount = parse_amount(&withdraw_request.amount)?;
+
+    let session = custody_session(keeper_state.db(), &withdraw_request.session)?;
+
+    let (recipient, address, name) = resolve_recipient(
+        keeper_state.name_resolver().as_ref(),
+        &withdraw_request.address,
+    )
+    .await?;
+
+    let package_id = env::var("SUI_SQUAD_PACKAGE_ID").map_err(node_error)?;
+    let package_object_id = ObjectID::from_hex_literal(&package_id).map_err(node_error)?;
+
+    let account_object_id = account_object_id(&keeper_state, &session.telegram_id).await?;
+    let admin_object_id = find_admin_object_id(&keeper_state).await?;
+
+    ensure_chain_allows(&keeper_state, admin_object_id, account_object_id).await?;
+
+    let mut squad_connect_client = zk_login_client(
+        &keeper_state,
+        &headers,
+        session.randomness.clone(),
+        session.public_key.clone(),
+        session.max_epoch,
+    )?;
+
+    let sender = zk_login_address(&mut squad_connect_client).await?;
+
+    if custody_owner(&keeper_state, account_object_id).await? != Some(sender) {
+        return Err(ErrorKeeper {
+            message: format!(
+                "{} is not the self-custody owner of this account, sign in with the owner's Google account",
+                sender
+            ),
+            status: 403,
+        });
+    }
+
+    let coin_type = withdraw_request
+        .coin_type
+        .clone()
+        .unwrap_or_else(|| SUI_COIN_TYPE.to_string());
+
+    let node = keeper_state.squad_connect_client().get_node();
+    let sponsor = keeper_state.sponsor();
+
+    let tx = build_owner_withdraw_transaction(
+        &node,
+        sender,
+        sponsor.as_ref().map(|sponsor| sponsor.address()),
+        package_object_id,
+        account_object_id,
+        admin_object_id,
+        &coin_type,
+        amount,
+        recipient,
+    )
+    .await?;
+
+    let transaction_response = execute_as_owner(
+        &keeper_state,
+        &mut squad_connect_client,
+        &session,
+        sender,
+        sponsor,
+        package_object_id,
+        tx,
+    )
+    .await?;
+
+    println!("{}", transaction_response);
+
+    if executed(&transaction_response) {
+        consume_session(keeper_state.db(), &withdraw_request.session);
+
+        notify(
+            keeper_state.db(),
+            Notification {
+                id: 0,
+                telegram_id: session.telegram_id.clone(),
+                kind: NotificationKind::WithdrawalExecuted,
+                amount,
+                coin_type,
+                sender: Some(sender.to_string()),
+                recipient: Some(match &name {
+                    Some(name) => format!("{} ({})", name, address),
+                    None => address.clone(),
+                }),
+                digest: transaction_response.digest.to_string(),
+                timestamp: 0,
+            },
+        );
+    }
+
+    Ok(Json(WithdrawResponse {
+        digest: transaction_response.digest.to_string(),
+        address,
+        name,
+    }))
+}
@@ -0,0 +1,285 @@
+use std::{str::FromStr, sync::Arc};
+
+use sled::Db;
+use squad_connect::client::squad_connect::SquadConnect;
+use sui_sdk::{
+    SuiClient,
+    rpc_types::{
+        SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+        SuiTransactionBlockResponseOptions,
+    },
+    types::{
+        Identifier,
+        base_types::{ObjectID, SuiAddress},
+        programmable_transaction_builder::ProgrammableTransactionBuilder,
+        quorum_driver_types::ExecuteTransactionRequestType,
+        transaction::TransactionData,
+    },
+};
+use sui_squad_core::{helpers::dtos::LoginPurpose, signer::Signer};
+
+use crate::{
+    admin::helpers::get_key_field,
+    error::ErrorKeeper,
+    fund::helpers::{
+        FUND_GAS_BUDGET, SUI_COIN_TYPE, parse_coin_type, select_coins, shared_object_arg,
+    },
+    sessions::{dto::ZkLoginSession, helpers::get_session},
+    sponsor::helpers::{
+        add_sponsor_signature, ensure_sponsorable, release_gas, reserve_gas, settle_gas,
+    },
+    state::KeeperState,
+    zklogin::helpers::sign_with_zk_login,
+};
+
+fn node_error(e: impl ToString) -> ErrorKeeper {
+    ErrorKeeper {
+        message: e.to_string(),
+        status: 500,
+    }
+}
+
+/// Session of a login link issued by `/custody`; funding links cannot manage custody.
+pub fn custody_session(db: &Db, token: &str) -> Result<ZkLoginSession, ErrorKeeper> {
+    let session = get_session(db, token)?;
+
+    if session.purpose != LoginPurpose::Custody {
+        return Err(ErrorKeeper {
+            message: "This login link cannot manage self-custody, use /custody in the bot"
+                .to_string(),
+            status: 403,
+        });
+    }
+
+    Ok(session)
+}
+
+/// Owner registered on the account, read from its `OwnerKey` dynamic field.
+pub async fn custody_owner(
+    keeper_state: &KeeperState,
+    account_object_id: ObjectID,
+) -> Result<Option<SuiAddress>, ErrorKeeper> {
+    let field = get_key_field(keeper_state, account_object_id, "account::OwnerKey").await?;
+
+    field
+        .as_ref()
+        .and_then(|field| field.pointer("/fields/value"))
+        .and_then(|owner| owner.as_str())
+        .map(|owner| SuiAddress::from_str(owner).map_err(node_error))
+        .transpose()
+}
+
+/// Refuse relayed payments and withdrawals the contract would abort because only the
+/// self-custody owner can move the account's funds.
+pub async fn ensure_not_self_custody(
+    keeper_state: &KeeperState,
+    account_object_id: ObjectID,
+) -> Result<(), ErrorKeeper> {
+    if custody_owner(keeper_state, account_object_id)
+        .await?
+        .is_some()
+    {
+        return Err(ErrorKeeper {
+            message:
+                "This Squad account is in self-custody, withdraw from the /custody page instead"
+                    .to_string(),
+            status: 403,
+        });
+    }
+
+    Ok(())
+}
+
+/// Build a transaction sent by the owner's zkLogin address, with gas paid by the sponsor when
+/// there is one.
+async fn build_owner_transaction(
+    node: &SuiClient,
+    sender: SuiAddress,
+    sponsor: Option<SuiAddress>,
+    builder: ProgrammableTransactionBuilder,
+) -> Result<TransactionData, ErrorKeeper> {
+    let gas_coins = select_coins(
+        node,
+        sponsor.unwrap_or(sender),
+        SUI_COIN_TYPE,
+        FUND_GAS_BUDGET,
+    )
+    .await
+    .map_err(|e| match sponsor {
+        Some(_) => ErrorKeeper {
+            message: format!(
+                "The gas sponsor cannot pay for this transaction: {}",
+                e.message
+            ),
+            status: 503,
+        },
+        None => e,
+    })?;
+
+    let gas_payment = gas_coins.iter().map(|coin| coin.object_ref()).collect();
+
+    let gas_price = node
+        .read_api()
+        .get_reference_gas_price()
+        .await
+        .map_err(node_error)?;
+
+    Ok(match sponsor {
+        Some(sponsor) => TransactionData::new_programmable_allow_sponsor(
+            sender,
+            gas_payment,
+            builder.finish(),
+            FUND_GAS_BUDGET,
+            gas_price,
+            sponsor,
+        ),
+        None => TransactionData::new_programmable(
+            sender,
+            gas_payment,
+            builder.finish(),
+            FUND_GAS_BUDGET,
+            gas_price,
+        ),
+    })
+}
+
+/// Build the `account::enable_self_custody` call with which the proposed owner accepts.
+pub async fn build_enable_custody_transaction(
+    node: &SuiClient,
+    sender: SuiAddress,
+    sponsor: Option<SuiAddress>,
+    package_id: ObjectID,
+    account_id: ObjectID,
+    admin_id: ObjectID,
+) -> Result<TransactionData, ErrorKeeper> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+
+    let account_arg = builder
+        .obj(shared_object_arg(node, account_id).await?)
+        .map_err(node_error)?;
+    let admin_arg = builder
+        .obj(shared_object_arg(node, admin_id).await?)
+        .map_err(node_error)?;
+
+    builder.programmable_move_call(
+        package_id,
+        Identifier::new("account").map_err(node_error)?,
+        Identifier::new("enable_self_custody").map_err(node_error)?,
+        vec![],
+        vec![account_arg, admin_arg],
+    );
+
+    build_owner_transaction(node, sender, sponsor, builder).await
+}
+
+/// Build an `account::owner_withdraw` call sent by the owner's zkLogin address.
+#[allow(clippy::too_many_arguments)]
+pub async fn build_owner_withdraw_transaction(
+    node: &SuiClient,
+    sender: SuiAddress,
+    sponsor: Option<SuiAddress>,
+    package_id: ObjectID,
+    account_id: ObjectID,
+    admin_id: ObjectID,
+    coin_type: &str,
+    amount: u64,
+    recipient: SuiAddress,
+) -> Result<TransactionData, ErrorKeeper> {
+    if amount == 0 {
+        return Err(ErrorKeeper {
+            message: "The amount must be greater than zero".to_string(),
+            status: 400,
+        });
+    }
+
+    let type_tag = parse_coin_type(coin_type)?;
+
+    let mut builder = ProgrammableTransactionBuilder::new();
+
+    let account_arg = builder
+        .obj(shared_object_arg(node, account_id).await?)
+        .map_err(node_error)?;
+    let admin_arg = builder
+        .obj(shared_object_arg(node, admin_id).await?)
+        .map_err(node_error)?;
+    let amount_arg = builder.pure(amount).map_err(node_error)?;
+    let recipient_arg = builder.pure(recipient).map_err(node_error)?;
+
+    builder.programmable_move_call(
+        package_id,
+        Identifier::new("account").map_err(node_error)?,
+        Identifier::new("owner_withdraw").map_err(node_error)?,
+        vec![type_tag],
+        vec![account_arg, admin_arg, amount_arg, recipient_arg],
+    );
+
+    build_owner_transaction(node, sender, sponsor, builder).await
+}
+
+/// Sign `tx` with the owner's zkLogin proof, add the sponsor signature when the sponsor pays
+/// for gas and execute it, charging the gas to the user's daily sponsorship allowance.
+pub async fn execute_as_owner(
+    keeper_state: &KeeperState,
+    squad_connect_client: &mut SquadConnect,
+    session: &ZkLoginSession,
+    sender: SuiAddress,
+    sponsor: Option<Arc<dyn Signer>>,
+    package_id: ObjectID,
+    tx: TransactionData,
+) -> Result<SuiTransactionBlockResponse, ErrorKeeper> {
+    let node = keeper_state.squad_connect_client().get_node();
+
+    let gas_record = match &sponsor {
+        Some(sponsor) => {
+            ensure_sponsorable(&tx, package_id, sponsor.address())?;
+
+            Some(reserve_gas(
+                keeper_state.db(),
+                keeper_state.sponsor_policy(),
+                &session.telegram_id,
+                FUND_GAS_BUDGET,
+            )?)
+        }
+        None => None,
+    };
+
+    let result = async {
+        let transaction =
+            sign_with_zk_login(keeper_state, squad_connect_client, session, sender, tx).await?;
+
+        let transaction = match &sponsor {
+            Some(sponsor) => add_sponsor_signature(transaction, sponsor.as_ref()).await?,
+            None => transaction,
+        };
+
+        node.quorum_driver_api()
+            .execute_transaction_block(
+                transaction,
+                SuiTransactionBlockResponseOptions::full_content(),
+                Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+            )
+            .await
+            .map_err(node_error)
+    }
+    .await;
+
+    match (result, gas_record) {
+        (Ok(transaction_response), Some(record)) => {
+            let gas_used = transaction_response
+                .effects
+                .as_ref()
+                .map(|effects| effects.gas_cost_summary().net_gas_usage().max(0) as u64)
+                .unwrap_or(FUND_GAS_BUDGET);
+
+            settle_gas(keeper_state.db(), &session.telegram_id, &record, gas_used);
+
+            Ok(transaction_response)
+        }
+        (Ok(transaction_response), None) => Ok(transaction_response),
+        (Err(e), Some(record)) => {
+            release_gas(keeper_state.db(), &session.telegram_id, &record);
+            Err(e)
+        }
+        (Err(e), None) => Err(e),
+    }
+}
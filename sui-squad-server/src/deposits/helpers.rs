@@ -0,0 +1,187 @@
+use std::{collections::HashMap, env, str::FromStr, sync::Arc, time::Duration};
+
+use sled::Db;
+use sui_sdk::types::base_types::SuiAddress;
+use sui_squad_core::helpers::dtos::{Notification, NotificationKind};
+
+use crate::{error::ErrorKeeper, notifications::helpers::notify, state::KeeperState};
+
+const WATCHED_TREE: &str = "watched_addresses";
+const SEEN_BALANCES_TREE: &str = "seen_balances";
+
+fn db_error(e: impl ToString) -> ErrorKeeper {
+    ErrorKeeper {
+        message: e.to_string(),
+        status: 500,
+    }
+}
+
+/// Current balance of every coin type held by `address`.
+async fn current_balances(
+    keeper_state: &KeeperState,
+    address: SuiAddress,
+) -> Result<HashMap<String, u64>, ErrorKeeper> {
+    let balances = keeper_state
+        .squad_connect_client()
+        .get_node()
+        .coin_read_api()
+        .get_all_balances(address)
+        .await
+        .map_err(db_error)?;
+
+    Ok(balances
+        .into_iter()
+        .map(|balance| {
+            (
+                balance.coin_type,
+                u64::try_from(balance.total_balance).unwrap_or(u64::MAX),
+            )
+        })
+        .collect())
+}
+
+fn get_seen_balances(db: &Db, address: &str) -> Result<HashMap<String, u64>, ErrorKeeper> {
+    let tree = db.open_tree(SEEN_BALANCES_TREE).map_err(db_error)?;
+
+    match tree.get(address).map_err(db_error)? {
+        Some(bytes) => serde_json::from_slice(&bytes).map_err(db_error),
+        None => Ok(HashMap::new()),
+    }
+}
+
+fn save_seen_balances(
+    db: &Db,
+    address: &str,
+    balances: &HashMap<String, u64>,
+) -> Result<(), ErrorKeeper> {
+    let tree = db.open_tree(SEEN_BALANCES_TREE).map_err(db_error)?;
+    let bytes = serde_json::to_vec(balances).map_err(db_error)?;
+
+    tree.insert(address, bytes).map_err(db_error)?;
+
+    Ok(())
+}
+
+/// Start watching the user's zkLogin address, taking its current balances as the baseline.
+///
+/// Failures are only logged; watching is a convenience and never blocks the caller.
+pub async fn watch_address(keeper_state: &KeeperState, telegram_id: &str, address: SuiAddress) {
+    let result = async {
+        let tree = keeper_state
+            .db()
+            .open_tree(WATCHED_TREE)
+            .map_err(db_error)?;
+        let address_str = address.to_string();
+
+        let previous = tree
+            .insert(telegram_id, address_str.as_bytes())
+            .map_err(db_error)?;
+
+        if previous.as_deref() != Some(address_str.as_bytes()) {
+            let balances = current_balances(keeper_state, address).await?;
+            save_seen_balances(keeper_state.db(), &address_str, &balances)?;
+        }
+
+        Ok::<_, ErrorKeeper>(())
+    }
+    .await;
+
+    if let Err(e) = result {
+        println!(
+            "Failed to watch the zkLogin address of {}: {}",
+            telegram_id, e.message
+        );
+    }
+}
+
+fn watched_addresses(db: &Db) -> Result<Vec<(String, String)>, ErrorKeeper> {
+    let tree = db.open_tree(WATCHED_TREE).map_err(db_error)?;
+
+    tree.iter()
+        .map(|entry| {
+            let (telegram_id, address) = entry.map_err(db_error)?;
+
+            Ok((
+                String::from_utf8(telegram_id.to_vec()).map_err(db_error)?,
+                String::from_utf8(address.to_vec()).map_err(db_error)?,
+            ))
+        })
+        .collect()
+}
+
+async fn check_address(
+    keeper_state: &KeeperState,
+    telegram_id: &str,
+    address: &str,
+) -> Result<(), ErrorKeeper> {
+    let sui_address = SuiAddress::from_str(address).map_err(db_error)?;
+    let seen = get_seen_balances(keeper_state.db(), address)?;
+    let balances = current_balances(keeper_state, sui_address).await?;
+
+    for (coin_type, balance) in &balances {
+        let previous = seen.get(coin_type).copied().unwrap_or_default();
+
+        if *balance > previous {
+            println!(
+                "Deposit of {} {} detected at {} for {}",
+                balance - previous,
+                coin_type,
+                address,
+                telegram_id
+            );
+
+            notify(
+                keeper_state.db(),
+                Notification {
+                    id: 0,
+                    telegram_id: telegram_id.to_string(),
+                    kind: NotificationKind::DepositDetected,
+                    amount: balance - previous,
+                    coin_type: coin_type.clone(),
+                    sender: None,
+                    recipient: Some(address.to_string()),
+                    digest: String::new(),
+                    timestamp: 0,
+                },
+            );
+        }
+    }
+
+    // Coin types that are gone are dropped, so a later deposit of them counts from zero
+    save_seen_balances(keeper_state.db(), address, &balances)
+}
+
+/// Poll the balances of every watched zkLogin address and notify users about incoming coins.
+///
+/// Runs every `DEPOSIT_WATCH_SECS` seconds (30 by default); `0` disables the watcher.
+pub async fn watch_deposits(keeper_state: Arc<KeeperState>) {
+    let watch_secs = env::var("DEPOSIT_WATCH_SECS")
+        .ok()
+        .and_then(|secs| secs.parse().ok())
+        .unwrap_or(30);
+
+    if watch_secs == 0 {
+        println!("Deposit watcher disabled");
+        return;
+    }
+
+    let mut interval = tokio::time::interval(Duration::from_secs(watch_secs));
+
+    loop {
+        interval.tick().await;
+
+        let addresses = match watched_addresses(keeper_state.db()) {
+            Ok(addresses) => addresses,
+            Err(e) => {
+                println!("Failed to load watched addresses: {}", e.message);
+                continue;
+            }
+        };
+
+        for (telegram_id, address) in addresses {
+            if let Err(e) = check_address(&keeper_state, &telegram_id, &address).await {
+                println!("Failed to check deposits at {}: {}", address, e.message);
+            }
+        }
+    }
+}
@@ -1,12 +1,89 @@
+use crate::admin;
+use crate::custody;
 use crate::fund;
-use crate::fund::dto::FundRequest;
+use crate::fund::dto::{BalanceResponse, FundRequest, WatchRequest, WatchResponse};
 use crate::info;
+use crate::limits;
+use crate::notifications;
+use crate::sessions;
 use crate::webhook;
+use crate::zklogin;
+use sui_squad_core::helpers::dtos::{
+    AdminStats, AdminUserInfo, ChainPauseResponse, DigestResponse, FreezeRequest, FreezeResponse,
+    LimitsRequest, LimitsResponse, LoginPurpose, Notification, NotificationAck, NotificationKind,
+    PauseRequest, PauseResponse, RelayerRequest, RelayerResponse, RelayerStatus,
+    RotateAdminRequest, RotateAdminResponse, SessionRequest, SessionResponse, SpendingLimits,
+    WithdrawResponse, ZkLoginAddressInfo, ZkLoginSessionInfo,
+};
 use utoipa::OpenApi;
 
 #[derive(OpenApi)]
 #[openapi(
-    paths(info::handler::info, webhook::handler::webhook, fund::handler::fund),
-    components(schemas(info::dto::Info, FundRequest))
+    paths(
+        info::handler::info,
+        webhook::handler::webhook,
+        webhook::handler::callback,
+        webhook::handler::result,
+        webhook::handler::custody,
+        webhook::handler::asset,
+        fund::handler::fund,
+        fund::handler::balance,
+        fund::handler::watch,
+        custody::handler::status,
+        custody::handler::enable,
+        custody::handler::withdraw,
+        limits::handler::get_limits,
+        limits::handler::set_limits,
+        admin::handler::stats,
+        admin::handler::user_info,
+        admin::handler::pause,
+        admin::handler::chain_pause,
+        admin::handler::freeze,
+        admin::handler::rotate,
+        admin::handler::relayer,
+        notifications::handler::notifications,
+        notifications::handler::ack,
+        sessions::handler::session,
+        zklogin::handler::address,
+        zklogin::handler::session_info,
+        zklogin::handler::session_address
+    ),
+    components(schemas(
+        info::dto::Info,
+        FundRequest,
+        BalanceResponse,
+        WatchRequest,
+        WatchResponse,
+        DigestResponse,
+        custody::dto::CustodyStatusResponse,
+        custody::dto::EnableCustodyRequest,
+        custody::dto::EnableCustodyResponse,
+        custody::dto::CustodyWithdrawRequest,
+        WithdrawResponse,
+        LimitsRequest,
+        LimitsResponse,
+        SpendingLimits,
+        AdminStats,
+        AdminUserInfo,
+        PauseRequest,
+        PauseResponse,
+        ChainPauseResponse,
+        FreezeRequest,
+        FreezeResponse,
+        RotateAdminRequest,
+        RotateAdminResponse,
+        RelayerStatus,
+        RelayerRequest,
+        RelayerResponse,
+        Notification,
+        NotificationKind,
+        NotificationAck,
+        SessionRequest,
+        SessionResponse,
+        LoginPurpose,
+        ZkLoginSessionInfo,
+        ZkLoginAddressInfo,
+        zklogin::dto::ZkLoginAddressResponse
+    ))
 )]
 pub struct ApiDoc;
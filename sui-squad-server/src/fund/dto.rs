@@ -1,10 +1,45 @@
-use serde::Deserialize;
-use utoipa::ToSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
+/// Amounts are decimal strings in the smallest unit of the coin (MIST for SUI), since JSON
+/// numbers lose precision above 2^53.
+///
+/// `session` is the token of the login link the bot issued; the zkLogin parameters and the
+/// Telegram user are taken from the server-side session.
 #[derive(Deserialize, ToSchema)]
 pub struct FundRequest {
-    pub public_key: String,
-    pub max_epoch: u64,
-    pub telegram_id: String,
-    pub randomness: String,
+    pub session: String,
+    pub amount: String,
+    pub coin_type: Option<String>,
+}
+
+/// Login session used to watch the user's address for direct transfers.
+#[derive(Deserialize, ToSchema)]
+pub struct WatchRequest {
+    pub session: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct WatchResponse {
+    pub address: String,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BalanceQuery {
+    pub address: String,
+    pub coin_type: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct BalanceResponse {
+    pub address: String,
+    pub coin_type: String,
+    pub balance: u64,
+    /// Part of the balance that can be deposited; for SUI the gas budget is kept aside unless sponsored.
+    pub available: u64,
+    pub decimals: u8,
+    pub symbol: String,
+    /// Whether the server pays gas for the funding transaction.
+    pub sponsored: bool,
 }
@@ -1,35 +1,52 @@
 use std::{env, str::FromStr, sync::Arc};
 
 use axum::{
-    extract::State,
+    extract::{Query, State},
     http::HeaderMap,
     response::{Json, Result},
 };
 
 use sui_sdk::{
-    json::SuiJsonValue,
-    rpc_types::{EventFilter, SuiTransactionBlockResponseOptions, SuiTypeTag},
+    rpc_types::{EventFilter, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponseOptions},
     types::{
-        TypeTag,
         base_types::{ObjectID, SuiAddress},
-        crypto::PublicKey,
         quorum_driver_types::ExecuteTransactionRequestType,
     },
 };
 
-use crate::{error::ErrorKeeper, state::KeeperState};
+use crate::{
+    admin::helpers::ensure_not_paused,
+    deposits::helpers::watch_address,
+    error::ErrorKeeper,
+    notifications::helpers::{executed, notify},
+    sessions::helpers::{consume_session, get_session},
+    sponsor::helpers::{
+        add_sponsor_signature, ensure_sponsorable, release_gas, reserve_gas, settle_gas,
+    },
+    state::KeeperState,
+    zklogin::helpers::sign_with_zk_login,
+};
 
-use super::dto::FundRequest;
-use sui_squad_core::{helpers::dtos::DigestResponse, package::dto::Event};
+use super::{
+    dto::{BalanceQuery, BalanceResponse, FundRequest, WatchRequest, WatchResponse},
+    helpers::{
+        FUND_GAS_BUDGET, SUI_COIN_TYPE, build_fund_transaction, get_available_balance,
+        parse_amount, zk_login_address, zk_login_client,
+    },
+};
+use sui_squad_core::{
+    helpers::dtos::{DigestResponse, Notification, NotificationKind},
+    package::dto::Event,
+};
 
 #[utoipa::path(
     post,
     path = "/fund",
-    summary = "Get user auth info",
-    description = "Get user auth info",
+    summary = "Fund the user's account",
+    description = "Deposits the requested amount from the user's zkLogin address, merging and splitting coins as needed",
     request_body = [FundRequest],
     responses(
-        (status = 201, description = "Get user auth info", body = [FundRequest])
+        (status = 201, description = "Digest of the funding transaction", body = [DigestResponse])
     )
 )]
 #[axum::debug_handler]
@@ -38,6 +55,12 @@ pub async fn fund(
     headers: HeaderMap,
     Json(fund_request): Json<FundRequest>,
 ) -> Result<Json<DigestResponse>, ErrorKeeper> {
+    ensure_not_paused(keeper_state.db())?;
+
+    let amount = parse_amount(&fund_request.amount)?;
+
+    let session = get_session(keeper_state.db(), &fund_request.session)?;
+
     let package_id = env::var("SUI_SQUAD_PACKAGE_ID").expect("SUI_SQUAD_PACKAGE_ID is not set");
 
     let node = keeper_state.squad_connect_client().get_node();
@@ -59,7 +82,7 @@ pub async fn fund(
     let account_event = account_events.data.iter().find(|event| {
         if let Some(telegram_id) = event.parsed_json.get("telegram_id") {
             if let Some(telegram_id_str) = telegram_id.as_str() {
-                return telegram_id_str == fund_request.telegram_id;
+                return telegram_id_str == session.telegram_id;
             }
         }
         false
@@ -84,56 +107,18 @@ pub async fn fund(
         })?;
 
     let node = keeper_state.squad_connect_client().get_node();
-    let path = keeper_state.path();
 
-    let jwt = headers.get("Authorization").ok_or_else(|| ErrorKeeper {
-        message: "Authorization header not found".to_string(),
-        status: 401,
-    })?;
+    let mut squad_connect_client = zk_login_client(
+        &keeper_state,
+        &headers,
+        session.randomness.clone(),
+        session.public_key.clone(),
+        session.max_epoch,
+    )?;
 
-    let jwt = jwt
-        .to_str()
-        .map_err(|e| ErrorKeeper {
-            message: e.to_string(),
-            status: 401,
-        })?
-        .split(" ")
-        .nth(1)
-        .ok_or_else(|| ErrorKeeper {
-            message: "JWT is not valid".to_string(),
-            status: 401,
-        })?;
-
-    let mut squad_connect_client = keeper_state.squad_connect_client().clone();
-
-    squad_connect_client.set_jwt(jwt.to_string());
+    let sender = zk_login_address(&mut squad_connect_client).await?;
 
-    squad_connect_client.set_zk_proof_params(
-        fund_request.randomness,
-        fund_request.public_key.clone(),
-        fund_request.max_epoch,
-    );
-
-    let account = squad_connect_client
-        .get_address()
-        .await
-        .map_err(|e| ErrorKeeper {
-            message: e.to_string(),
-            status: 500,
-        })?;
-
-    let zk_login_inputs = squad_connect_client
-        .recover_seed_address()
-        .await
-        .map_err(|e| ErrorKeeper {
-            message: e.to_string(),
-            status: 500,
-        })?;
-
-    let sender = SuiAddress::from_str(&account.address).map_err(|e| ErrorKeeper {
-        message: e.to_string(),
-        status: 500,
-    })?;
+    watch_address(&keeper_state, &session.telegram_id, sender).await;
 
     let package_object_id = ObjectID::from_hex_literal(&package_id).map_err(|e| ErrorKeeper {
         message: e.to_string(),
@@ -145,91 +130,210 @@ pub async fn fund(
         status: 500,
     })?;
 
-    let coin_name = "0x2::sui::SUI".to_string();
+    let coin_type = fund_request
+        .coin_type
+        .clone()
+        .or_else(|| session.coin_type.clone())
+        .unwrap_or_else(|| SUI_COIN_TYPE.to_string());
+
+    let sponsor = keeper_state.sponsor();
+
+    let gas_record = match sponsor {
+        Some(_) => Some(reserve_gas(
+            keeper_state.db(),
+            keeper_state.sponsor_policy(),
+            &session.telegram_id,
+            FUND_GAS_BUDGET,
+        )?),
+        None => None,
+    };
+
+    let result = async {
+        let tx = build_fund_transaction(
+            &node,
+            sender,
+            sponsor.as_ref().map(|sponsor| sponsor.address()),
+            package_object_id,
+            account_id_object_id,
+            session.telegram_id.clone(),
+            &coin_type,
+            amount,
+        )
+        .await?;
 
-    let coins = node
-        .coin_read_api()
-        .get_coins(sender, Some(coin_name.clone()), None, None)
-        .await
-        .map_err(|e| ErrorKeeper {
-            message: e.to_string(),
-            status: 500,
-        })?;
+        if let Some(sponsor) = &sponsor {
+            ensure_sponsorable(&tx, package_object_id, sponsor.address())?;
+        }
 
-    let coin = coins.data.first().ok_or_else(|| ErrorKeeper {
-        message: "Coin not found".to_string(),
-        status: 404,
-    })?;
+        let transaction = sign_with_zk_login(
+            &keeper_state,
+            &mut squad_connect_client,
+            &session,
+            sender,
+            tx.clone(),
+        )
+        .await?;
+
+        let transaction = match &sponsor {
+            Some(sponsor) => add_sponsor_signature(transaction, sponsor.as_ref()).await?,
+            None => transaction,
+        };
+
+        let transaction_response = node
+            .quorum_driver_api()
+            .execute_transaction_block(
+                transaction,
+                SuiTransactionBlockResponseOptions::full_content(),
+                Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+            )
+            .await
+            .map_err(|e| ErrorKeeper {
+                message: e.to_string(),
+                status: 500,
+            })?;
+
+        println!("Transaction created successfully: {:?}", tx);
+
+        Ok::<_, ErrorKeeper>(transaction_response)
+    }
+    .await;
+
+    let transaction_response = match (result, &gas_record) {
+        (Ok(transaction_response), Some(record)) => {
+            let gas_used = transaction_response
+                .effects
+                .as_ref()
+                .map(|effects| effects.gas_cost_summary().net_gas_usage().max(0) as u64)
+                .unwrap_or(FUND_GAS_BUDGET);
+
+            settle_gas(keeper_state.db(), &session.telegram_id, record, gas_used);
+
+            transaction_response
+        }
+        (Ok(transaction_response), None) => transaction_response,
+        (Err(e), Some(record)) => {
+            release_gas(keeper_state.db(), &session.telegram_id, record);
+            return Err(e);
+        }
+        (Err(e), None) => return Err(e),
+    };
 
-    let coin_id = coin.coin_object_id;
+    println!("transaction_response: {:?}", transaction_response);
+    if executed(&transaction_response) {
+        consume_session(keeper_state.db(), &fund_request.session);
+
+        notify(
+            keeper_state.db(),
+            Notification {
+                id: 0,
+                telegram_id: session.telegram_id.clone(),
+                kind: NotificationKind::FundingCompleted,
+                amount,
+                coin_type,
+                sender: Some(sender.to_string()),
+                recipient: None,
+                digest: transaction_response.digest.to_string(),
+                timestamp: 0,
+            },
+        );
+    }
 
-    let coin_type = TypeTag::from_str(&coin_name).map_err(|e| ErrorKeeper {
-        message: e.to_string(),
-        status: 500,
-    })?;
+    Ok(Json(DigestResponse {
+        digest: transaction_response.digest.to_string(),
+    }))
+}
 
-    let tx = node
-        .transaction_builder()
-        .move_call(
-            sender,
-            package_object_id,
-            "account",
-            "fund",
-            vec![SuiTypeTag::from(coin_type)],
-            vec![
-                SuiJsonValue::from_object_id(account_id_object_id),
-                SuiJsonValue::new(serde_json::Value::String(fund_request.telegram_id.clone()))
-                    .map_err(|e| ErrorKeeper {
-                        message: e.to_string(),
-                        status: 500,
-                    })?,
-                SuiJsonValue::from_object_id(coin_id),
-            ],
-            None,
-            10_000_000,
-            None,
-        )
-        .await
-        .map_err(|e| ErrorKeeper {
-            message: e.to_string(),
-            status: 500,
-        })?;
+#[utoipa::path(
+    get,
+    path = "/fund/balance",
+    summary = "Get the balance available for funding",
+    description = "Returns the balance of a zkLogin address for a coin type and how much of it can be deposited",
+    params(BalanceQuery),
+    responses(
+        (status = 200, description = "Balance of the address", body = [BalanceResponse])
+    )
+)]
+#[axum::debug_handler]
+pub async fn balance(
+    State(keeper_state): State<Arc<KeeperState>>,
+    Query(query): Query<BalanceQuery>,
+) -> Result<Json<BalanceResponse>, ErrorKeeper> {
+    let node = keeper_state.squad_connect_client().get_node();
 
-    let signer_pk = PublicKey::from_str(&fund_request.public_key).map_err(|e| ErrorKeeper {
+    let address = SuiAddress::from_str(&query.address).map_err(|e| ErrorKeeper {
         message: e.to_string(),
-        status: 500,
+        status: 400,
     })?;
 
-    let signer_address = SuiAddress::from(&signer_pk);
+    let coin_type = query.coin_type.unwrap_or_else(|| SUI_COIN_TYPE.to_string());
 
-    let transaction = squad_connect_client
-        .sign_transaction(
-            tx.clone(),
-            signer_address,
-            zk_login_inputs,
-            fund_request.max_epoch,
-            path.clone(),
-        )
+    let sponsored = keeper_state.sponsor_policy().enabled;
+
+    let (balance, available) = get_available_balance(&node, address, &coin_type, sponsored).await?;
+
+    let metadata = node
+        .coin_read_api()
+        .get_coin_metadata(coin_type.clone())
         .await
         .map_err(|e| ErrorKeeper {
             message: e.to_string(),
             status: 500,
         })?;
 
-    let transaction_response = node
-        .quorum_driver_api()
-        .execute_transaction_block(
-            transaction,
-            SuiTransactionBlockResponseOptions::full_content(),
-            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
-        )
-        .await
-        .unwrap();
+    let (decimals, symbol) = match metadata {
+        Some(metadata) => (metadata.decimals, metadata.symbol),
+        None => (
+            9,
+            coin_type
+                .rsplit("::")
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+        ),
+    };
+
+    Ok(Json(BalanceResponse {
+        address: address.to_string(),
+        coin_type,
+        balance,
+        available,
+        decimals,
+        symbol,
+        sponsored,
+    }))
+}
 
-    println!("transaction_response: {:?}", transaction_response);
-    println!("Transaction created successfully: {:?}", tx);
+#[utoipa::path(
+    post,
+    path = "/fund/watch",
+    summary = "Watch the user's zkLogin address",
+    description = "Starts watching the zkLogin address of the user behind the login session so direct transfers to it are reported in Telegram",
+    request_body = [WatchRequest],
+    responses(
+        (status = 200, description = "The watched zkLogin address", body = [WatchResponse])
+    )
+)]
+#[axum::debug_handler]
+pub async fn watch(
+    State(keeper_state): State<Arc<KeeperState>>,
+    headers: HeaderMap,
+    Json(watch_request): Json<WatchRequest>,
+) -> Result<Json<WatchResponse>, ErrorKeeper> {
+    let session = get_session(keeper_state.db(), &watch_request.session)?;
 
-    Ok(Json(DigestResponse {
-        digest: transaction_response.digest.to_string(),
+    let mut squad_connect_client = zk_login_client(
+        &keeper_state,
+        &headers,
+        session.randomness,
+        session.public_key,
+        session.max_epoch,
+    )?;
+
+    let address = zk_login_address(&mut squad_connect_client).await?;
+
+    watch_address(&keeper_state, &session.telegram_id, address).await;
+
+    Ok(Json(WatchResponse {
+        address: address.to_string(),
     }))
 }
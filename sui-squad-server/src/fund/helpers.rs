@@ -0,0 +1,330 @@
+use std::str::FromStr;
+
+use axum::http::HeaderMap;
+use squad_connect::client::squad_connect::SquadConnect;
+use sui_sdk::{
+    SuiClient,
+    rpc_types::{Coin, SuiObjectDataOptions},
+    types::{
+        Identifier, TypeTag,
+        base_types::{ObjectID, ObjectRef, SuiAddress},
+        object::Owner,
+        programmable_transaction_builder::ProgrammableTransactionBuilder,
+        transaction::{Argument, Command, ObjectArg, TransactionData},
+    },
+};
+
+use crate::{error::ErrorKeeper, state::KeeperState};
+
+pub const SUI_COIN_TYPE: &str = "0x2::sui::SUI";
+pub const FUND_GAS_BUDGET: u64 = 10_000_000;
+
+fn node_error(e: impl ToString) -> ErrorKeeper {
+    ErrorKeeper {
+        message: e.to_string(),
+        status: 500,
+    }
+}
+
+/// Parse an amount sent as a decimal string of the smallest unit of the coin.
+pub fn parse_amount(amount: &str) -> Result<u64, ErrorKeeper> {
+    amount.trim().parse::<u64>().map_err(|_| ErrorKeeper {
+        message: format!("Invalid amount: {}", amount),
+        status: 400,
+    })
+}
+
+/// Clone the squad connect client with the user's OAuth JWT from the `Authorization` header and
+/// the zk proof parameters the bot generated for this login.
+pub fn zk_login_client(
+    keeper_state: &KeeperState,
+    headers: &HeaderMap,
+    randomness: String,
+    public_key: String,
+    max_epoch: u64,
+) -> Result<SquadConnect, ErrorKeeper> {
+    let jwt = headers.get("Authorization").ok_or_else(|| ErrorKeeper {
+        message: "Authorization header not found".to_string(),
+        status: 401,
+    })?;
+
+    let jwt = jwt
+        .to_str()
+        .map_err(|e| ErrorKeeper {
+            message: e.to_string(),
+            status: 401,
+        })?
+        .split(" ")
+        .nth(1)
+        .ok_or_else(|| ErrorKeeper {
+            message: "JWT is not valid".to_string(),
+            status: 401,
+        })?;
+
+    let mut squad_connect_client = keeper_state.squad_connect_client().clone();
+
+    squad_connect_client.set_jwt(jwt.to_string());
+    squad_connect_client.set_zk_proof_params(randomness, public_key, max_epoch);
+
+    Ok(squad_connect_client)
+}
+
+/// The zkLogin address of the user the client was set up for.
+pub async fn zk_login_address(
+    squad_connect_client: &mut SquadConnect,
+) -> Result<SuiAddress, ErrorKeeper> {
+    let account = squad_connect_client
+        .get_address()
+        .await
+        .map_err(|e| ErrorKeeper {
+            message: e.to_string(),
+            status: 500,
+        })?;
+
+    SuiAddress::from_str(&account.address).map_err(|e| ErrorKeeper {
+        message: e.to_string(),
+        status: 500,
+    })
+}
+
+pub fn parse_coin_type(coin_type: &str) -> Result<TypeTag, ErrorKeeper> {
+    TypeTag::from_str(coin_type).map_err(|e| ErrorKeeper {
+        message: format!("Invalid coin type {}: {}", coin_type, e),
+        status: 400,
+    })
+}
+
+pub fn is_sui(coin_type: &TypeTag) -> bool {
+    TypeTag::from_str(SUI_COIN_TYPE).is_ok_and(|sui| &sui == coin_type)
+}
+
+/// Total balance of `coin_type` held by `owner` and the part of it that can be deposited.
+pub async fn get_available_balance(
+    node: &SuiClient,
+    owner: SuiAddress,
+    coin_type: &str,
+    sponsored: bool,
+) -> Result<(u64, u64), ErrorKeeper> {
+    let type_tag = parse_coin_type(coin_type)?;
+
+    let balance = node
+        .coin_read_api()
+        .get_balance(owner, Some(coin_type.to_string()))
+        .await
+        .map_err(node_error)?;
+
+    let total = u64::try_from(balance.total_balance).unwrap_or(u64::MAX);
+
+    let available = if is_sui(&type_tag) && !sponsored {
+        total.saturating_sub(FUND_GAS_BUDGET)
+    } else {
+        total
+    };
+
+    Ok((total, available))
+}
+
+pub async fn select_coins(
+    node: &SuiClient,
+    owner: SuiAddress,
+    coin_type: &str,
+    amount: u64,
+) -> Result<Vec<Coin>, ErrorKeeper> {
+    node.coin_read_api()
+        .select_coins(owner, Some(coin_type.to_string()), amount as u128, vec![])
+        .await
+        .map_err(|e| ErrorKeeper {
+            message: format!("Not enough {} to cover {}: {}", coin_type, amount, e),
+            status: 400,
+        })
+}
+
+/// Argument for a shared object of the package, such as an `Account` or the `Admin`.
+pub async fn shared_object_arg(
+    node: &SuiClient,
+    object_id: ObjectID,
+) -> Result<ObjectArg, ErrorKeeper> {
+    let object = node
+        .read_api()
+        .get_object_with_options(object_id, SuiObjectDataOptions::new().with_owner())
+        .await
+        .map_err(node_error)?;
+
+    let owner = object
+        .data
+        .and_then(|data| data.owner)
+        .ok_or_else(|| ErrorKeeper {
+            message: format!("Object {} not found", object_id),
+            status: 404,
+        })?;
+
+    match owner {
+        Owner::Shared {
+            initial_shared_version,
+        } => Ok(ObjectArg::SharedObject {
+            id: object_id,
+            initial_shared_version,
+            mutable: true,
+        }),
+        _ => Err(ErrorKeeper {
+            message: format!("Object {} is not shared", object_id),
+            status: 500,
+        }),
+    }
+}
+
+/// Build a programmable transaction depositing exactly `amount` of `coin_type` into the account.
+///
+/// Unsponsored SUI deposits are split off the gas coin, which the node smashes from every
+/// selected coin. Otherwise the user's coins are merged into the first selected coin before
+/// splitting, with SUI coins of the sponsor (or the user) paying for gas.
+#[allow(clippy::too_many_arguments)]
+pub async fn build_fund_transaction(
+    node: &SuiClient,
+    sender: SuiAddress,
+    sponsor: Option<SuiAddress>,
+    package_id: ObjectID,
+    account_id: ObjectID,
+    telegram_id: String,
+    coin_type: &str,
+    amount: u64,
+) -> Result<TransactionData, ErrorKeeper> {
+    if amount == 0 {
+        return Err(ErrorKeeper {
+            message: "The amount must be greater than zero".to_string(),
+            status: 400,
+        });
+    }
+
+    let type_tag = parse_coin_type(coin_type)?;
+
+    let (total, available) =
+        get_available_balance(node, sender, coin_type, sponsor.is_some()).await?;
+
+    if amount > available {
+        return Err(ErrorKeeper {
+            message: format!(
+                "Insufficient balance: {} available of {} ({} held)",
+                available, coin_type, total
+            ),
+            status: 400,
+        });
+    }
+
+    let mut builder = ProgrammableTransactionBuilder::new();
+
+    let from_gas_coin = sponsor.is_none() && is_sui(&type_tag);
+
+    let (source, gas_payment): (Argument, Vec<ObjectRef>) = if from_gas_coin {
+        let coins = select_coins(node, sender, coin_type, amount + FUND_GAS_BUDGET).await?;
+
+        (
+            Argument::GasCoin,
+            coins.iter().map(|coin| coin.object_ref()).collect(),
+        )
+    } else {
+        let coins = select_coins(node, sender, coin_type, amount).await?;
+
+        let mut coin_args = coins
+            .iter()
+            .map(|coin| builder.obj(ObjectArg::ImmOrOwnedObject(coin.object_ref())))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(node_error)?;
+
+        let primary = coin_args.remove(0);
+
+        if !coin_args.is_empty() {
+            builder.command(Command::MergeCoins(primary, coin_args));
+        }
+
+        let gas_coins = select_coins(
+            node,
+            sponsor.unwrap_or(sender),
+            SUI_COIN_TYPE,
+            FUND_GAS_BUDGET,
+        )
+        .await
+        .map_err(|e| match sponsor {
+            Some(_) => ErrorKeeper {
+                message: format!("The gas sponsor cannot pay for this deposit: {}", e.message),
+                status: 503,
+            },
+            None => e,
+        })?;
+
+        (
+            primary,
+            gas_coins.iter().map(|coin| coin.object_ref()).collect(),
+        )
+    };
+
+    let amount_arg = builder.pure(amount).map_err(node_error)?;
+
+    let Argument::Result(split) = builder.command(Command::SplitCoins(source, vec![amount_arg]))
+    else {
+        return Err(node_error("Unexpected split result"));
+    };
+
+    let account_arg = builder
+        .obj(shared_object_arg(node, account_id).await?)
+        .map_err(node_error)?;
+    let telegram_id_arg = builder.pure(telegram_id).map_err(node_error)?;
+
+    builder.programmable_move_call(
+        package_id,
+        Identifier::new("account").map_err(node_error)?,
+        Identifier::new("fund").map_err(node_error)?,
+        vec![type_tag],
+        vec![
+            account_arg,
+            telegram_id_arg,
+            Argument::NestedResult(split, 0),
+        ],
+    );
+
+    let gas_price = node
+        .read_api()
+        .get_reference_gas_price()
+        .await
+        .map_err(node_error)?;
+
+    Ok(match sponsor {
+        Some(sponsor) => TransactionData::new_programmable_allow_sponsor(
+            sender,
+            gas_payment,
+            builder.finish(),
+            FUND_GAS_BUDGET,
+            gas_price,
+            sponsor,
+        ),
+        None => TransactionData::new_programmable(
+            sender,
+            gas_payment,
+            builder.finish(),
+            FUND_GAS_BUDGET,
+            gas_price,
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_amounts_beyond_json_number_precision() {
+        assert_eq!(
+            parse_amount("9007199254740993").unwrap(),
+            9_007_199_254_740_993
+        );
+        assert_eq!(parse_amount(&u64::MAX.to_string()).unwrap(), u64::MAX);
+        assert_eq!(parse_amount(" 1500000000 ").unwrap(), 1_500_000_000);
+    }
+
+    #[test]
+    fn rejects_invalid_amounts() {
+        for amount in ["", "abc", "-1", "1.5", "18446744073709551616"] {
+            assert_eq!(parse_amount(amount).unwrap_err().status, 400, "{}", amount);
+        }
+    }
+}
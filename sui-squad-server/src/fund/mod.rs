@@ -1,2 +1,3 @@
 pub mod dto;
 pub mod handler;
+pub mod helpers;
@@ -0,0 +1,38 @@
+use std::{collections::HashMap, env};
+
+use serde::{Deserialize, Serialize};
+use sui_squad_core::helpers::dtos::SpendingLimits;
+use utoipa::IntoParams;
+
+/// Admin spending policy per coin type, loaded from `SPENDING_POLICY`.
+///
+/// The variable holds a JSON object keyed by coin type, for example
+/// `{"0x2::sui::SUI":{"daily":100000000000,"weekly":500000000000,"per_transaction":20000000000}}`.
+/// Amounts are expressed in the smallest unit of the coin (MIST for SUI).
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SpendingPolicy(HashMap<String, SpendingLimits>);
+
+impl SpendingPolicy {
+    pub fn from_env() -> Self {
+        match env::var("SPENDING_POLICY") {
+            Ok(policy) => serde_json::from_str(&policy).expect("SPENDING_POLICY is not valid JSON"),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn limits(&self, coin_type: &str) -> SpendingLimits {
+        self.0.get(coin_type).cloned().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpendRecord {
+    pub timestamp: i64,
+    pub amount: u64,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct LimitsQuery {
+    pub coin_type: Option<String>,
+}
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use axum::extract::{Extension, Json, Query, State};
+use chrono::{Duration, Utc};
+use sui_squad_core::helpers::dtos::{LimitsRequest, LimitsResponse, UserPayload};
+
+use crate::{error::ErrorKeeper, state::KeeperState};
+
+use super::{
+    dto::LimitsQuery,
+    helpers::{
+        effective_limits, get_spend_records, get_user_limits, save_user_limits, spent_since,
+        validate_user_limits,
+    },
+};
+
+pub fn build_limits_response(
+    keeper_state: &KeeperState,
+    telegram_id: &str,
+    coin_type: String,
+) -> Result<LimitsResponse, ErrorKeeper> {
+    let db = keeper_state.db();
+    let policy = keeper_state.spending_policy().limits(&coin_type);
+    let user_limits = get_user_limits(db, telegram_id, &coin_type)?;
+    let records = get_spend_records(db, telegram_id, &coin_type)?;
+    let now = Utc::now();
+
+    Ok(LimitsResponse {
+        effective: effective_limits(&policy, &user_limits),
+        spent_daily: spent_since(&records, (now - Duration::days(1)).timestamp_millis()),
+        spent_weekly: spent_since(&records, (now - Duration::weeks(1)).timestamp_millis()),
+        coin_type,
+        policy,
+        user_limits,
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/limits",
+    summary = "Get spending limits",
+    description = "Returns the admin policy, the user's own limits and the amount spent for a coin type",
+    params(LimitsQuery),
+    responses(
+        (status = 200, description = "Spending limits of the user", body = [LimitsResponse])
+    )
+)]
+#[axum::debug_handler]
+pub async fn get_limits(
+    State(keeper_state): State<Arc<KeeperState>>,
+    Extension(user): Extension<UserPayload>,
+    Query(query): Query<LimitsQuery>,
+) -> Result<Json<LimitsResponse>, ErrorKeeper> {
+    let coin_type = query
+        .coin_type
+        .unwrap_or_else(|| "0x2::sui::SUI".to_string());
+
+    let response = build_limits_response(&keeper_state, &user.telegram_id, coin_type)?;
+
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/limits",
+    summary = "Set spending limits",
+    description = "Sets the user's own limits for a coin type; they can never exceed the admin policy",
+    request_body = [LimitsRequest],
+    responses(
+        (status = 200, description = "Updated spending limits of the user", body = [LimitsResponse])
+    )
+)]
+#[axum::debug_handler]
+pub async fn set_limits(
+    State(keeper_state): State<Arc<KeeperState>>,
+    Extension(user): Extension<UserPayload>,
+    Json(limits_request): Json<LimitsRequest>,
+) -> Result<Json<LimitsResponse>, ErrorKeeper> {
+    let policy = keeper_state
+        .spending_policy()
+        .limits(&limits_request.coin_type);
+
+    validate_user_limits(&policy, &limits_request.limits)?;
+
+    save_user_limits(
+        keeper_state.db(),
+        &user.telegram_id,
+        &limits_request.coin_type,
+        &limits_request.limits,
+    )?;
+
+    let response =
+        build_limits_response(&keeper_state, &user.telegram_id, limits_request.coin_type)?;
+
+    Ok(Json(response))
+}
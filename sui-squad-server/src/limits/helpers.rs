@@ -0,0 +1,377 @@
+use chrono::{Duration, Utc};
+use sled::{Db, IVec};
+use sui_squad_core::helpers::dtos::SpendingLimits;
+
+use crate::error::ErrorKeeper;
+
+use super::dto::{SpendRecord, SpendingPolicy};
+
+const LIMITS_TREE: &str = "spending_limits";
+const RECORDS_TREE: &str = "spending_records";
+
+fn key(telegram_id: &str, coin_type: &str) -> String {
+    format!("{}:{}", telegram_id, coin_type)
+}
+
+fn db_error(e: impl ToString) -> ErrorKeeper {
+    ErrorKeeper {
+        message: e.to_string(),
+        status: 500,
+    }
+}
+
+fn lowest(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Combine the admin policy with the user's own limits, keeping the strictest value of each.
+pub fn effective_limits(policy: &SpendingLimits, user_limits: &SpendingLimits) -> SpendingLimits {
+    SpendingLimits {
+        daily: lowest(policy.daily, user_limits.daily),
+        weekly: lowest(policy.weekly, user_limits.weekly),
+        per_transaction: lowest(policy.per_transaction, user_limits.per_transaction),
+    }
+}
+
+/// Reject user limits that are looser than the admin policy.
+pub fn validate_user_limits(
+    policy: &SpendingLimits,
+    user_limits: &SpendingLimits,
+) -> Result<(), ErrorKeeper> {
+    let fields = [
+        ("daily", policy.daily, user_limits.daily),
+        ("weekly", policy.weekly, user_limits.weekly),
+        (
+            "per transaction",
+            policy.per_transaction,
+            user_limits.per_transaction,
+        ),
+    ];
+
+    for (name, policy_limit, user_limit) in fields {
+        if let (Some(policy_limit), Some(user_limit)) = (policy_limit, user_limit) {
+            if user_limit > policy_limit {
+                return Err(ErrorKeeper {
+                    message: format!(
+                        "The {} limit cannot be raised above the admin policy of {}",
+                        name, policy_limit
+                    ),
+                    status: 400,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn get_user_limits(
+    db: &Db,
+    telegram_id: &str,
+    coin_type: &str,
+) -> Result<SpendingLimits, ErrorKeeper> {
+    let tree = db.open_tree(LIMITS_TREE).map_err(db_error)?;
+
+    match tree.get(key(telegram_id, coin_type)).map_err(db_error)? {
+        Some(bytes) => serde_json::from_slice(&bytes).map_err(db_error),
+        None => Ok(SpendingLimits::default()),
+    }
+}
+
+pub fn save_user_limits(
+    db: &Db,
+    telegram_id: &str,
+    coin_type: &str,
+    limits: &SpendingLimits,
+) -> Result<(), ErrorKeeper> {
+    let tree = db.open_tree(LIMITS_TREE).map_err(db_error)?;
+    let bytes = serde_json::to_vec(limits).map_err(db_error)?;
+
+    tree.insert(key(telegram_id, coin_type), bytes)
+        .map_err(db_error)?;
+
+    Ok(())
+}
+
+fn decode_records(bytes: Option<&IVec>) -> Result<Vec<SpendRecord>, ErrorKeeper> {
+    match bytes {
+        Some(bytes) => serde_json::from_slice(bytes).map_err(db_error),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Spend records of the last week; older records no longer count towards any limit.
+pub fn get_spend_records(
+    db: &Db,
+    telegram_id: &str,
+    coin_type: &str,
+) -> Result<Vec<SpendRecord>, ErrorKeeper> {
+    let tree = db.open_tree(RECORDS_TREE).map_err(db_error)?;
+    let records = decode_records(
+        tree.get(key(telegram_id, coin_type))
+            .map_err(db_error)?
+            .as_ref(),
+    )?;
+    let week_ago = (Utc::now() - Duration::weeks(1)).timestamp_millis();
+
+    Ok(records
+        .into_iter()
+        .filter(|record| record.timestamp > week_ago)
+        .collect())
+}
+
+pub fn spent_since(records: &[SpendRecord], since: i64) -> u64 {
+    records
+        .iter()
+        .filter(|record| record.timestamp > since)
+        .fold(0u64, |spent, record| spent.saturating_add(record.amount))
+}
+
+fn check_limits(
+    records: &[SpendRecord],
+    limits: &SpendingLimits,
+    amount: u64,
+) -> Result<(), ErrorKeeper> {
+    let now = Utc::now();
+
+    if let Some(per_transaction) = limits.per_transaction {
+        if amount > per_transaction {
+            return Err(ErrorKeeper {
+                message: format!(
+                    "Amount {} exceeds the per-transaction limit of {}",
+                    amount, per_transaction
+                ),
+                status: 403,
+            });
+        }
+    }
+
+    if let Some(daily) = limits.daily {
+        let spent = spent_since(records, (now - Duration::days(1)).timestamp_millis());
+
+        if spent.saturating_add(amount) > daily {
+            return Err(ErrorKeeper {
+                message: format!(
+                    "Daily spending limit of {} exceeded ({} already spent in the last 24 hours)",
+                    daily, spent
+                ),
+                status: 403,
+            });
+        }
+    }
+
+    if let Some(weekly) = limits.weekly {
+        let spent = spent_since(records, (now - Duration::weeks(1)).timestamp_millis());
+
+        if spent.saturating_add(amount) > weekly {
+            return Err(ErrorKeeper {
+                message: format!(
+                    "Weekly spending limit of {} exceeded ({} already spent in the last 7 days)",
+                    weekly, spent
+                ),
+                status: 403,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Check `amount` against the user's effective limits and reserve it in the spend records.
+///
+/// The check and the reservation happen in a single compare-and-swap so concurrent
+/// payments cannot both slip under the limit. Call [`release_spending`] with the returned
+/// record if the transaction is not executed.
+pub fn reserve_spending(
+    db: &Db,
+    policy: &SpendingPolicy,
+    telegram_id: &str,
+    coin_type: &str,
+    amount: u64,
+) -> Result<SpendRecord, ErrorKeeper> {
+    let user_limits = get_user_limits(db, telegram_id, coin_type)?;
+    let limits = effective_limits(&policy.limits(coin_type), &user_limits);
+
+    let tree = db.open_tree(RECORDS_TREE).map_err(db_error)?;
+    let key = key(telegram_id, coin_type);
+
+    loop {
+        let current = tree.get(&key).map_err(db_error)?;
+        let week_ago = (Utc::now() - Duration::weeks(1)).timestamp_millis();
+
+        let mut records: Vec<SpendRecord> = decode_records(current.as_ref())?
+            .into_iter()
+            .filter(|record| record.timestamp > week_ago)
+            .collect();
+
+        check_limits(&records, &limits, amount)?;
+
+        let record = SpendRecord {
+            timestamp: Utc::now().timestamp_millis(),
+            amount,
+        };
+
+        records.push(record.clone());
+
+        let bytes = serde_json::to_vec(&records).map_err(db_error)?;
+
+        if tree
+            .compare_and_swap(&key, current, Some(bytes))
+            .map_err(db_error)?
+            .is_ok()
+        {
+            return Ok(record);
+        }
+    }
+}
+
+/// Undo a reservation made by [`reserve_spending`].
+pub fn release_spending(db: &Db, telegram_id: &str, coin_type: &str, record: &SpendRecord) {
+    let tree = match db.open_tree(RECORDS_TREE) {
+        Ok(tree) => tree,
+        Err(e) => {
+            println!("Failed to release spending reservation: {}", e);
+            return;
+        }
+    };
+
+    let result = tree.fetch_and_update(key(telegram_id, coin_type), |current| {
+        let bytes = current?;
+
+        let Ok(mut records) = serde_json::from_slice::<Vec<SpendRecord>>(bytes) else {
+            return Some(bytes.to_vec());
+        };
+
+        if let Some(index) = records.iter().position(|r| r == record) {
+            records.remove(index);
+        }
+
+        Some(serde_json::to_vec(&records).unwrap_or_else(|_| bytes.to_vec()))
+    });
+
+    if let Err(e) = result {
+        println!("Failed to release spending reservation: {}", e);
+    }
+}
+
+/// Total amount spent by all users since `since`, across every coin type.
+pub fn total_spent_since(db: &Db, since: i64) -> Result<u64, ErrorKeeper> {
+    let tree = db.open_tree(RECORDS_TREE).map_err(db_error)?;
+    let mut total = 0;
+
+    for entry in tree.iter() {
+        let (_, bytes) = entry.map_err(db_error)?;
+        let records = decode_records(Some(&bytes))?;
+        total = spent_since(&records, since).saturating_add(total);
+    }
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(
+        daily: Option<u64>,
+        weekly: Option<u64>,
+        per_transaction: Option<u64>,
+    ) -> SpendingLimits {
+        SpendingLimits {
+            daily,
+            weekly,
+            per_transaction,
+        }
+    }
+
+    fn spent_hours_ago(hours: i64, amount: u64) -> SpendRecord {
+        SpendRecord {
+            timestamp: (Utc::now() - Duration::hours(hours)).timestamp_millis(),
+            amount,
+        }
+    }
+
+    #[test]
+    fn effective_limits_keep_the_strictest_value() {
+        let policy = limits(Some(100), None, Some(20));
+        let user = limits(Some(50), Some(300), Some(30));
+
+        let effective = effective_limits(&policy, &user);
+
+        assert_eq!(effective.daily, Some(50));
+        assert_eq!(effective.weekly, Some(300));
+        assert_eq!(effective.per_transaction, Some(20));
+    }
+
+    #[test]
+    fn effective_limits_without_any_limit_are_unlimited() {
+        let effective = effective_limits(&SpendingLimits::default(), &SpendingLimits::default());
+
+        assert_eq!(effective.daily, None);
+        assert_eq!(effective.weekly, None);
+        assert_eq!(effective.per_transaction, None);
+    }
+
+    #[test]
+    fn per_transaction_limit_is_inclusive() {
+        let limits = limits(None, None, Some(10));
+
+        assert!(check_limits(&[], &limits, 10).is_ok());
+        assert!(check_limits(&[], &limits, 11).is_err());
+    }
+
+    #[test]
+    fn daily_limit_counts_the_last_24_hours() {
+        let limits = limits(Some(100), None, None);
+        let records = [spent_hours_ago(1, 60), spent_hours_ago(25, 1_000)];
+
+        assert!(check_limits(&records, &limits, 40).is_ok());
+        assert!(check_limits(&records, &limits, 41).is_err());
+    }
+
+    #[test]
+    fn weekly_limit_counts_the_last_7_days() {
+        let limits = limits(None, Some(100), None);
+        let records = [spent_hours_ago(1, 30), spent_hours_ago(6 * 24, 30)];
+
+        assert!(check_limits(&records, &limits, 40).is_ok());
+        assert!(check_limits(&records, &limits, 41).is_err());
+    }
+
+    #[test]
+    fn huge_amounts_do_not_overflow_past_the_limit() {
+        let limits = limits(Some(u64::MAX - 1), Some(u64::MAX - 1), None);
+        let records = [spent_hours_ago(1, u64::MAX - 1)];
+
+        assert!(check_limits(&records, &limits, u64::MAX).is_err());
+        assert_eq!(
+            spent_since(&[spent_hours_ago(1, u64::MAX), spent_hours_ago(1, 1)], 0),
+            u64::MAX
+        );
+    }
+
+    #[test]
+    fn released_reservations_no_longer_count() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let policy: SpendingPolicy =
+            serde_json::from_value(serde_json::json!({ "0x2::sui::SUI": { "daily": 100 } }))
+                .unwrap();
+
+        let record = reserve_spending(&db, &policy, "1", "0x2::sui::SUI", 80).unwrap();
+
+        assert!(reserve_spending(&db, &policy, "1", "0x2::sui::SUI", 30).is_err());
+
+        // As after a transaction that aborted on chain
+        release_spending(&db, "1", "0x2::sui::SUI", &record);
+
+        assert!(
+            get_spend_records(&db, "1", "0x2::sui::SUI")
+                .unwrap()
+                .is_empty()
+        );
+        assert!(reserve_spending(&db, &policy, "1", "0x2::sui::SUI", 100).is_ok());
+    }
+}
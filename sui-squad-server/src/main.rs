@@ -1,15 +1,24 @@
 mod admin;
+mod cli;
+mod custody;
+mod db;
+mod deposits;
 mod docs;
 mod error;
 mod fund;
 mod info;
+mod limits;
 mod middlewares;
+mod notifications;
 mod payment;
 mod router;
+mod sessions;
+mod sponsor;
 mod state;
 mod user;
 mod webhook;
 mod withdraw;
+mod zklogin;
 
 use std::env;
 
@@ -21,6 +30,23 @@ async fn main() {
     dotenv().ok();
     tracing_subscriber::fmt::init();
 
+    let command = env::args().nth(1);
+
+    let result = match command.as_deref() {
+        Some("rotate-admin") => Some(cli::rotate_admin().await),
+        Some("accept-admin") => Some(cli::accept_admin().await),
+        Some("encrypt-keystore") => Some(cli::encrypt_keystore()),
+        _ => None,
+    };
+
+    if let Some(result) = result {
+        if let Err(e) = result {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let server_domain = env::var("SERVER_DOMAIN").unwrap_or("localhost".to_string());
 
     let app = router().await;
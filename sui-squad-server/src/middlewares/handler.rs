@@ -1,5 +1,10 @@
+use std::env;
+
 use axum::{extract::Request, middleware::Next, response::Response};
-use sui_squad_core::helpers::{dtos::UserPayload, jwt::JwtManager};
+use sui_squad_core::{
+    helpers::{dtos::UserPayload, jwt::JwtManager},
+    permissions::{admin_ids_from_env, is_admin},
+};
 
 use crate::error::ErrorKeeper;
 
@@ -33,3 +38,57 @@ pub async fn auth(mut req: Request, next: Next) -> Result<Response, ErrorKeeper>
 
     Ok(next.run(req).await)
 }
+
+/// Only let operators listed in `ADMIN_TELEGRAM_IDS` through. Must run after [`auth`].
+pub async fn admin(req: Request, next: Next) -> Result<Response, ErrorKeeper> {
+    let user = req
+        .extensions()
+        .get::<UserPayload>()
+        .ok_or_else(|| ErrorKeeper {
+            message: "Unauthorized".to_string(),
+            status: 401,
+        })?;
+
+    let telegram_id = user.telegram_id.parse::<i64>().map_err(|e| ErrorKeeper {
+        message: e.to_string(),
+        status: 403,
+    })?;
+
+    if !is_admin(telegram_id, &admin_ids_from_env()) {
+        return Err(ErrorKeeper {
+            message: "Forbidden".to_string(),
+            status: 403,
+        });
+    }
+
+    Ok(next.run(req).await)
+}
+
+/// Only let the bot through, authenticated with the shared `INTERNAL_SECRET`.
+pub async fn internal(req: Request, next: Next) -> Result<Response, ErrorKeeper> {
+    let secret = env::var("INTERNAL_SECRET").unwrap_or_default();
+
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .unwrap_or_default();
+
+    // Compare every byte so the check does not leak how much of the secret matched
+    let matches = secret.len() == token.len()
+        && secret
+            .bytes()
+            .zip(token.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0;
+
+    if secret.is_empty() || !matches {
+        return Err(ErrorKeeper {
+            message: "Unauthorized".to_string(),
+            status: 401,
+        });
+    }
+
+    Ok(next.run(req).await)
+}
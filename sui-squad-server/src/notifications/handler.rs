@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use axum::extract::{Json, State};
+use sui_squad_core::helpers::dtos::{Notification, NotificationAck};
+
+use crate::{error::ErrorKeeper, state::KeeperState};
+
+use super::helpers::{ack_notifications, pending_notifications};
+
+const NOTIFICATIONS_BATCH: usize = 50;
+
+#[utoipa::path(
+    get,
+    path = "/internal/notifications",
+    summary = "Get pending notifications",
+    description = "Returns queued funding, payment and withdrawal notifications for the bot to deliver",
+    responses(
+        (status = 200, description = "Pending notifications, oldest first", body = [Vec<Notification>])
+    )
+)]
+#[axum::debug_handler]
+pub async fn notifications(
+    State(keeper_state): State<Arc<KeeperState>>,
+) -> Result<Json<Vec<Notification>>, ErrorKeeper> {
+    let notifications = pending_notifications(keeper_state.db(), NOTIFICATIONS_BATCH)?;
+
+    Ok(Json(notifications))
+}
+
+#[utoipa::path(
+    post,
+    path = "/internal/notifications/ack",
+    summary = "Acknowledge notifications",
+    description = "Removes notifications the bot has delivered from the queue",
+    request_body = [NotificationAck],
+    responses(
+        (status = 200, description = "Ids removed from the queue", body = [NotificationAck])
+    )
+)]
+#[axum::debug_handler]
+pub async fn ack(
+    State(keeper_state): State<Arc<KeeperState>>,
+    Json(ack): Json<NotificationAck>,
+) -> Result<Json<NotificationAck>, ErrorKeeper> {
+    let ids = ack_notifications(keeper_state.db(), &ack.ids)?;
+
+    Ok(Json(NotificationAck { ids }))
+}
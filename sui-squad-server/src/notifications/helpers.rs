@@ -0,0 +1,73 @@
+use chrono::Utc;
+use sled::Db;
+use sui_sdk::rpc_types::{SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse};
+use sui_squad_core::helpers::dtos::Notification;
+
+use crate::error::ErrorKeeper;
+
+const NOTIFICATIONS_TREE: &str = "notifications";
+
+fn db_error(e: impl ToString) -> ErrorKeeper {
+    ErrorKeeper {
+        message: e.to_string(),
+        status: 500,
+    }
+}
+
+/// Whether the transaction ran successfully on chain, not just reached a validator.
+pub fn executed(response: &SuiTransactionBlockResponse) -> bool {
+    response
+        .effects
+        .as_ref()
+        .is_some_and(|effects| effects.status().is_ok())
+}
+
+/// Queue a notification for the bot; `id` and `timestamp` are assigned here.
+///
+/// Failures are only logged so a notification never fails the transaction that triggered it.
+pub fn notify(db: &Db, mut notification: Notification) {
+    let result = (|| -> Result<(), ErrorKeeper> {
+        let tree = db.open_tree(NOTIFICATIONS_TREE).map_err(db_error)?;
+
+        notification.id = db.generate_id().map_err(db_error)?;
+        notification.timestamp = Utc::now().timestamp_millis();
+
+        let bytes = serde_json::to_vec(&notification).map_err(db_error)?;
+
+        tree.insert(notification.id.to_be_bytes(), bytes)
+            .map_err(db_error)?;
+
+        Ok(())
+    })();
+
+    if let Err(e) = result {
+        println!("Failed to queue notification: {}", e.message);
+    }
+}
+
+/// Oldest notifications not yet acknowledged by the bot.
+pub fn pending_notifications(db: &Db, limit: usize) -> Result<Vec<Notification>, ErrorKeeper> {
+    let tree = db.open_tree(NOTIFICATIONS_TREE).map_err(db_error)?;
+
+    tree.iter()
+        .take(limit)
+        .map(|entry| {
+            let (_, bytes) = entry.map_err(db_error)?;
+            serde_json::from_slice(&bytes).map_err(db_error)
+        })
+        .collect()
+}
+
+/// Remove delivered notifications, returning the ids that were still queued.
+pub fn ack_notifications(db: &Db, ids: &[u64]) -> Result<Vec<u64>, ErrorKeeper> {
+    let tree = db.open_tree(NOTIFICATIONS_TREE).map_err(db_error)?;
+    let mut removed = Vec::new();
+
+    for id in ids {
+        if tree.remove(id.to_be_bytes()).map_err(db_error)?.is_some() {
+            removed.push(*id);
+        }
+    }
+
+    Ok(removed)
+}
@@ -4,8 +4,6 @@ use axum::{
     Extension,
     extract::{Json, State},
 };
-use shared_crypto::intent::Intent;
-use sui_keys::keystore::{AccountKeystore, FileBasedKeystore};
 use sui_sdk::{
     json::SuiJsonValue,
     rpc_types::{EventFilter, SuiTransactionBlockResponseOptions, SuiTypeTag},
@@ -15,11 +13,18 @@ use sui_sdk::{
     },
 };
 use sui_squad_core::{
-    helpers::dtos::{DigestResponse, PaymentRequest, UserPayload},
+    helpers::dtos::{DigestResponse, Notification, NotificationKind, PaymentRequest, UserPayload},
     package::dto::Event,
 };
 
-use crate::{error::ErrorKeeper, state::KeeperState};
+use crate::{
+    admin::helpers::{ensure_chain_allows, ensure_not_paused},
+    custody::helpers::ensure_not_self_custody,
+    error::ErrorKeeper,
+    limits::helpers::{release_spending, reserve_spending},
+    notifications::helpers::{executed, notify},
+    state::KeeperState,
+};
 
 #[axum::debug_handler]
 pub async fn payment(
@@ -42,12 +47,7 @@ pub async fn payment(
     let node = squad_connect_client.get_node();
 
     let admin = keeper_state.admin();
-    let path = keeper_state.path();
-
-    let keystore = FileBasedKeystore::new(&path).map_err(|e| ErrorKeeper {
-        message: e.to_string(),
-        status: 500,
-    })?;
+    let relayer = keeper_state.next_relayer();
 
     let admin_events = node
         .event_api()
@@ -171,6 +171,9 @@ pub async fn payment(
             status: 500,
         })?;
 
+    ensure_chain_allows(&keeper_state, admin_object_id, account_sender_object_id).await?;
+    ensure_not_self_custody(&keeper_state, account_sender_object_id).await?;
+
     let coin_name = "0x2::sui::SUI".to_string();
 
     let coin_type = TypeTag::from_str(&coin_name).map_err(|e| ErrorKeeper {
@@ -181,7 +184,7 @@ pub async fn payment(
     let tx = node
         .transaction_builder()
         .move_call(
-            admin.clone(),
+            relayer.address(),
             package_object_id,
             "account",
             "payment",
@@ -208,12 +211,28 @@ pub async fn payment(
             status: 500,
         })?;
 
-    let signature = keystore
-        .sign_secure(admin, &tx, Intent::sui_transaction())
-        .map_err(|e| ErrorKeeper {
+    ensure_not_paused(keeper_state.db())?;
+
+    let spend_record = reserve_spending(
+        keeper_state.db(),
+        keeper_state.spending_policy(),
+        &user.telegram_id,
+        &coin_name,
+        payment_request.amount,
+    )?;
+
+    let signature = relayer.sign_transaction(&tx).await.map_err(|e| {
+        release_spending(
+            keeper_state.db(),
+            &user.telegram_id,
+            &coin_name,
+            &spend_record,
+        );
+        ErrorKeeper {
             message: e.to_string(),
             status: 500,
-        })?;
+        }
+    })?;
 
     let transaction_response = node
         .quorum_driver_api()
@@ -223,11 +242,47 @@ pub async fn payment(
             Some(ExecuteTransactionRequestType::WaitForLocalExecution),
         )
         .await
-        .unwrap();
+        .map_err(|e| {
+            release_spending(
+                keeper_state.db(),
+                &user.telegram_id,
+                &coin_name,
+                &spend_record,
+            );
+            ErrorKeeper {
+                message: e.to_string(),
+                status: 500,
+            }
+        })?;
 
     println!("{}", transaction_response);
     println!("Transaction created successfully: {:?}", tx);
 
+    if executed(&transaction_response) {
+        notify(
+            keeper_state.db(),
+            Notification {
+                id: 0,
+                telegram_id: payment_request.receiver_id.clone(),
+                kind: NotificationKind::PaymentReceived,
+                amount: payment_request.amount,
+                coin_type: coin_name,
+                sender: Some(user.telegram_id.clone()),
+                recipient: None,
+                digest: transaction_response.digest.to_string(),
+                timestamp: 0,
+            },
+        );
+    } else {
+        // Aborted on chain, nothing was spent
+        release_spending(
+            keeper_state.db(),
+            &user.telegram_id,
+            &coin_name,
+            &spend_record,
+        );
+    }
+
     Ok(Json(DigestResponse {
         digest: transaction_response.digest.to_string(),
     }))
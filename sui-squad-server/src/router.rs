@@ -6,24 +6,64 @@ use axum::{
 };
 use squad_connect::{client::squad_connect::SquadConnect, service::dtos::Network};
 use std::env;
-use sui_sdk::SuiClientBuilder;
+use sui_sdk::{SuiClient, SuiClientBuilder};
+use sui_squad_core::{
+    signer::SignerConfig,
+    suins::{NameResolver, RpcNameResolver, default_rpc_url},
+    zklogin::{EnokiZkLogin, ZkLoginProvider},
+};
 use utoipa::OpenApi;
 use utoipa_redoc::{Redoc, Servable};
 
 use crate::{
-    admin::handler::get_account,
+    admin::{
+        handler::{
+            chain_pause, freeze, get_keystore_path, pause, relayer, rotate, stats, user_info,
+        },
+        helpers::{check_active_admin, load_relayers, refresh_relayers, watch_admin_rotation},
+    },
+    custody::handler::{enable, status, withdraw as custody_withdraw},
+    db::init_tree,
+    deposits::helpers::watch_deposits,
     docs::{dto::ApiDoc, handler::api_docs},
-    fund::handler::fund,
+    fund::handler::{balance, fund, watch},
     info::handler::info,
-    middlewares::handler::auth,
+    limits::{
+        dto::SpendingPolicy,
+        handler::{get_limits, set_limits},
+    },
+    middlewares::handler::{admin as admin_only, auth, internal},
+    notifications::handler::{ack, notifications},
     payment::handler::payment,
+    sessions::handler::session,
+    sponsor::{dto::SponsorPolicy, helpers::load_sponsor},
     state::KeeperState,
     user::handler::create_user_if_not_exists,
-    webhook::handler::webhook,
+    webhook::handler::{asset, callback, custody, result, webhook},
     withdraw::handler::withdraw,
+    zklogin::{
+        handler::{address, session_address, session_info},
+        helpers::watch_sessions,
+    },
 };
 use tower_http::trace::TraceLayer;
 
+pub fn network_from_str(network: &str) -> Network {
+    match network {
+        "mainnet" => Network::Mainnet,
+        "testnet" => Network::Testnet,
+        _ => Network::Devnet,
+    }
+}
+
+pub async fn build_node(network: &Network) -> Result<SuiClient, sui_sdk::error::Error> {
+    match network {
+        Network::Mainnet => SuiClientBuilder::default().build_mainnet().await,
+        Network::Testnet => SuiClientBuilder::default().build_testnet().await,
+        _ => SuiClientBuilder::default().build_devnet().await,
+    }
+}
+
 pub async fn router() -> Router {
     let network_str: String =
         env::var("SUI_NETWORK").expect("SUI_NETWORK environment variable not set");
@@ -32,40 +72,115 @@ pub async fn router() -> Router {
         env::var("GOOGLE_CLIENT_ID").expect("GOOGLE_CLIENT_ID environment variable not set");
     let api_key = env::var("ENOKI_API_KEY").expect("ENOKI_API_KEY environment variable not set");
 
-    let network = match network_str.as_str() {
-        "mainnet" => Network::Mainnet,
-        "testnet" => Network::Testnet,
-        _ => Network::Devnet,
-    };
+    let network = network_from_str(&network_str);
 
-    let node = match network {
-        Network::Mainnet => SuiClientBuilder::default().build_mainnet().await,
-        Network::Testnet => SuiClientBuilder::default().build_testnet().await,
-        _ => SuiClientBuilder::default().build_devnet().await,
-    }
-    .expect("Failed to build client");
+    let node = build_node(&network).await.expect("Failed to build client");
+
+    let zk_login: Arc<dyn ZkLoginProvider> = Arc::new(EnokiZkLogin::new(api_key.clone()));
 
     let squad_connect_client = SquadConnect::new(node, client_id, network, api_key);
 
     let doc = ApiDoc::openapi();
 
-    let (admin, path) = get_account();
+    let path = get_keystore_path();
+
+    let db = init_tree();
+
+    let signer_config = SignerConfig::from_env().expect("Invalid signer configuration");
+
+    let admin = signer_config
+        .load()
+        .await
+        .expect("Failed to load the admin signer");
+
+    println!("Sender: {}", admin.address());
+
+    let relayers = load_relayers();
+
+    let spending_policy = SpendingPolicy::from_env();
+
+    let rpc_url = env::var("SUI_RPC_URL").unwrap_or_else(|_| default_rpc_url(&network_str));
+
+    let name_resolver: Arc<dyn NameResolver> = Arc::new(RpcNameResolver::new(rpc_url));
+
+    let sponsor_policy = SponsorPolicy::from_env();
 
-    let state = Arc::new(KeeperState::from((squad_connect_client, admin, path)));
+    let sponsor = load_sponsor(&sponsor_policy, admin.address());
+
+    let state = Arc::new(KeeperState::from((
+        squad_connect_client,
+        admin,
+        path,
+        db,
+        spending_policy,
+        name_resolver,
+        relayers,
+        sponsor,
+        sponsor_policy,
+        zk_login,
+    )));
+
+    if let Err(e) = check_active_admin(&state).await {
+        panic!("{}", e.message);
+    }
+
+    // Until the relayers are confirmed on chain only the admin key signs
+    if let Err(e) = refresh_relayers(&state).await {
+        println!("Failed to load the authorized relayers: {}", e.message);
+    }
+
+    tokio::spawn(watch_deposits(state.clone()));
+    tokio::spawn(watch_sessions(state.clone()));
+    tokio::spawn(watch_admin_rotation(state.clone()));
 
     let auth_routers = Router::new()
         .route("/user", post(create_user_if_not_exists))
         .route("/payment", post(payment))
         .route("/withdraw", post(withdraw))
+        .route("/limits", get(get_limits).post(set_limits))
+        .route_layer(middleware::from_fn(auth));
+
+    let admin_routers = Router::new()
+        .route("/admin/stats", get(stats))
+        .route("/admin/user/{telegram_id}", get(user_info))
+        .route("/admin/pause", post(pause))
+        .route("/admin/chain-pause", post(chain_pause))
+        .route("/admin/freeze", post(freeze))
+        .route("/admin/rotate", post(rotate))
+        .route("/admin/relayer", post(relayer))
+        .route_layer(middleware::from_fn(admin_only))
         .route_layer(middleware::from_fn(auth));
 
+    let internal_routers = Router::new()
+        .route("/internal/notifications", get(notifications))
+        .route("/internal/notifications/ack", post(ack))
+        .route("/internal/sessions", post(session))
+        .route("/internal/zklogin/{telegram_id}", get(session_info))
+        .route(
+            "/internal/zklogin/{telegram_id}/address",
+            get(session_address),
+        )
+        .route_layer(middleware::from_fn(internal));
+
     Router::new()
         .merge(Redoc::with_url("/redoc", doc))
         .merge(auth_routers)
+        .merge(admin_routers)
+        .merge(internal_routers)
         .route("/", get(info))
         .route("/docs", get(api_docs))
+        .route("/webhook/callback", get(callback))
+        .route("/webhook/result", get(result))
+        .route("/webhook/custody", get(custody))
+        .route("/webhook/assets/{file}", get(asset))
         .route("/webhook/{token}", get(webhook))
         .route("/fund", post(fund))
+        .route("/fund/balance", get(balance))
+        .route("/fund/watch", post(watch))
+        .route("/custody/status", get(status))
+        .route("/custody/enable", post(enable))
+        .route("/custody/withdraw", post(custody_withdraw))
+        .route("/zklogin/address", post(address))
         .layer(TraceLayer::new_for_http())
         .with_state(state)
 }
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use sui_squad_core::helpers::dtos::{LoginPurpose, SessionRequest};
+
+/// zkLogin session stored server-side under its login token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZkLoginSession {
+    pub telegram_id: String,
+    pub public_key: String,
+    pub max_epoch: u64,
+    pub randomness: String,
+    pub amount: Option<u64>,
+    pub coin_type: Option<String>,
+    pub language: Option<String>,
+    #[serde(default)]
+    pub purpose: LoginPurpose,
+    pub expires_at: i64,
+    /// Set once the login link has been opened; the link cannot be opened again.
+    pub opened: bool,
+}
+
+impl From<(SessionRequest, i64)> for ZkLoginSession {
+    fn from(value: (SessionRequest, i64)) -> Self {
+        let (request, expires_at) = value;
+
+        Self {
+            telegram_id: request.telegram_id,
+            public_key: request.public_key,
+            max_epoch: request.max_epoch,
+            randomness: request.randomness,
+            amount: request.amount,
+            coin_type: request.coin_type,
+            language: request.language,
+            purpose: request.purpose,
+            expires_at,
+            opened: false,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+use axum::extract::{Json, State};
+use sui_squad_core::helpers::dtos::{SessionRequest, SessionResponse};
+
+use crate::{error::ErrorKeeper, state::KeeperState, zklogin::helpers::register_key};
+
+use super::helpers::create_session;
+
+#[utoipa::path(
+    post,
+    path = "/internal/sessions",
+    summary = "Register a zkLogin session",
+    description = "Stores the ephemeral zkLogin parameters of a Telegram user and returns the single-use token of their login link",
+    request_body = [SessionRequest],
+    responses(
+        (status = 200, description = "Token of the login link", body = [SessionResponse])
+    )
+)]
+#[axum::debug_handler]
+pub async fn session(
+    State(keeper_state): State<Arc<KeeperState>>,
+    Json(session_request): Json<SessionRequest>,
+) -> Result<Json<SessionResponse>, ErrorKeeper> {
+    register_key(keeper_state.db(), &session_request)?;
+
+    let (token, expires_at) = create_session(keeper_state.db(), session_request)?;
+
+    Ok(Json(SessionResponse { token, expires_at }))
+}
@@ -0,0 +1,216 @@
+use std::env;
+
+use chrono::{Duration, Utc};
+use sled::Db;
+use sui_squad_core::helpers::{dtos::SessionRequest, token::random_token};
+
+use crate::error::ErrorKeeper;
+
+use super::dto::ZkLoginSession;
+
+const SESSIONS_TREE: &str = "zklogin_sessions";
+
+fn db_error(e: impl ToString) -> ErrorKeeper {
+    ErrorKeeper {
+        message: e.to_string(),
+        status: 500,
+    }
+}
+
+fn expired_error() -> ErrorKeeper {
+    ErrorKeeper {
+        message: "This login link has expired or was already used, request a new one from the bot"
+            .to_string(),
+        status: 410,
+    }
+}
+
+/// How long a login link stays valid, from `SESSION_TTL_SECS` (15 minutes by default).
+fn session_ttl() -> Duration {
+    let secs = env::var("SESSION_TTL_SECS")
+        .ok()
+        .and_then(|secs| secs.parse().ok())
+        .unwrap_or(900);
+
+    Duration::seconds(secs)
+}
+
+fn decode_session(bytes: &[u8]) -> Result<ZkLoginSession, ErrorKeeper> {
+    serde_json::from_slice(bytes).map_err(db_error)
+}
+
+/// Store the session under a fresh token, dropping sessions that have expired.
+pub fn create_session(db: &Db, request: SessionRequest) -> Result<(String, i64), ErrorKeeper> {
+    let tree = db.open_tree(SESSIONS_TREE).map_err(db_error)?;
+    let now = Utc::now().timestamp_millis();
+
+    for entry in tree.iter() {
+        let (token, bytes) = entry.map_err(db_error)?;
+
+        if decode_session(&bytes).map_or(true, |session| session.expires_at <= now) {
+            tree.remove(token).map_err(db_error)?;
+        }
+    }
+
+    let token = random_token();
+    let expires_at = (Utc::now() + session_ttl()).timestamp_millis();
+    let session = ZkLoginSession::from((request, expires_at));
+
+    tree.insert(
+        token.as_bytes(),
+        serde_json::to_vec(&session).map_err(db_error)?,
+    )
+    .map_err(db_error)?;
+
+    Ok((token, expires_at))
+}
+
+/// Mark the login link as opened; a link can only be opened once before it expires.
+pub fn open_session(db: &Db, token: &str) -> Result<ZkLoginSession, ErrorKeeper> {
+    let tree = db.open_tree(SESSIONS_TREE).map_err(db_error)?;
+
+    loop {
+        let current = tree
+            .get(token)
+            .map_err(db_error)?
+            .ok_or_else(expired_error)?;
+        let mut session = decode_session(&current)?;
+
+        if session.opened || session.expires_at <= Utc::now().timestamp_millis() {
+            return Err(expired_error());
+        }
+
+        session.opened = true;
+
+        let bytes = serde_json::to_vec(&session).map_err(db_error)?;
+
+        if tree
+            .compare_and_swap(token, Some(current), Some(bytes))
+            .map_err(db_error)?
+            .is_ok()
+        {
+            return Ok(session);
+        }
+    }
+}
+
+/// Session of a login link that was opened and has not expired.
+pub fn get_session(db: &Db, token: &str) -> Result<ZkLoginSession, ErrorKeeper> {
+    let tree = db.open_tree(SESSIONS_TREE).map_err(db_error)?;
+    let bytes = tree
+        .get(token)
+        .map_err(db_error)?
+        .ok_or_else(expired_error)?;
+    let session = decode_session(&bytes)?;
+
+    if !session.opened || session.expires_at <= Utc::now().timestamp_millis() {
+        return Err(expired_error());
+    }
+
+    Ok(session)
+}
+
+/// Invalidate the session once the funding it was created for went through.
+pub fn consume_session(db: &Db, token: &str) {
+    let result = db
+        .open_tree(SESSIONS_TREE)
+        .and_then(|tree| tree.remove(token));
+
+    if let Err(e) = result {
+        println!("Failed to invalidate login session: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_squad_core::helpers::dtos::LoginPurpose;
+
+    use super::*;
+
+    fn request(telegram_id: &str) -> SessionRequest {
+        SessionRequest {
+            telegram_id: telegram_id.to_string(),
+            public_key: "public-key".to_string(),
+            max_epoch: 10,
+            randomness: "42".to_string(),
+            amount: None,
+            coin_type: None,
+            language: None,
+            purpose: LoginPurpose::Fund,
+        }
+    }
+
+    fn temporary_db() -> Db {
+        sled::Config::new().temporary(true).open().unwrap()
+    }
+
+    #[test]
+    fn links_open_once_for_their_user() {
+        let db = temporary_db();
+        let (token, _) = create_session(&db, request("alice")).unwrap();
+
+        let session = open_session(&db, &token).unwrap();
+
+        assert_eq!(session.telegram_id, "alice");
+        assert_eq!(get_session(&db, &token).unwrap().telegram_id, "alice");
+        assert_eq!(open_session(&db, &token).unwrap_err().status, 410);
+    }
+
+    #[test]
+    fn tokens_are_not_shared_between_users() {
+        let db = temporary_db();
+        let (alice, _) = create_session(&db, request("alice")).unwrap();
+        let (bob, _) = create_session(&db, request("bob")).unwrap();
+
+        assert_ne!(alice, bob);
+        assert_eq!(open_session(&db, &bob).unwrap().telegram_id, "bob");
+        assert_eq!(open_session(&db, &alice).unwrap().telegram_id, "alice");
+    }
+
+    #[test]
+    fn expired_links_are_rejected() {
+        let db = temporary_db();
+        let expired = ZkLoginSession::from((request("alice"), Utc::now().timestamp_millis() - 1));
+
+        db.open_tree(SESSIONS_TREE)
+            .unwrap()
+            .insert("expired", serde_json::to_vec(&expired).unwrap())
+            .unwrap();
+
+        assert_eq!(open_session(&db, "expired").unwrap_err().status, 410);
+        assert_eq!(get_session(&db, "expired").unwrap_err().status, 410);
+
+        // Expired sessions are dropped when the next one is created
+        create_session(&db, request("bob")).unwrap();
+
+        assert!(
+            db.open_tree(SESSIONS_TREE)
+                .unwrap()
+                .get("expired")
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn unopened_and_unknown_links_are_rejected() {
+        let db = temporary_db();
+        let (token, _) = create_session(&db, request("alice")).unwrap();
+
+        assert_eq!(get_session(&db, &token).unwrap_err().status, 410);
+        assert_eq!(get_session(&db, "unknown").unwrap_err().status, 410);
+        assert_eq!(open_session(&db, "unknown").unwrap_err().status, 410);
+    }
+
+    #[test]
+    fn consumed_links_are_invalidated() {
+        let db = temporary_db();
+        let (token, _) = create_session(&db, request("alice")).unwrap();
+
+        open_session(&db, &token).unwrap();
+        consume_session(&db, &token);
+
+        assert_eq!(get_session(&db, &token).unwrap_err().status, 410);
+        assert_eq!(open_session(&db, &token).unwrap_err().status, 410);
+    }
+}
@@ -0,0 +1,31 @@
+use std::env;
+
+/// Gas sponsorship of funding and self-custody withdrawal transactions, loaded from `SPONSOR_GAS` and `SPONSOR_MAX_GAS_PER_DAY`.
+///
+/// Sponsorship is off unless `SPONSOR_GAS=true`; the daily cap is expressed in MIST per user.
+#[derive(Debug, Clone)]
+pub struct SponsorPolicy {
+    pub enabled: bool,
+    pub max_gas_per_day: u64,
+}
+
+impl SponsorPolicy {
+    pub fn from_env() -> Self {
+        let enabled = env::var("SPONSOR_GAS")
+            .map(|value| value.trim() == "true")
+            .unwrap_or(false);
+
+        let max_gas_per_day = match env::var("SPONSOR_MAX_GAS_PER_DAY") {
+            Ok(value) if !value.trim().is_empty() => value
+                .trim()
+                .parse()
+                .expect("SPONSOR_MAX_GAS_PER_DAY is not a number"),
+            _ => 50_000_000,
+        };
+
+        Self {
+            enabled,
+            max_gas_per_day,
+        }
+    }
+}
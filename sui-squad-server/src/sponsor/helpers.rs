@@ -0,0 +1,288 @@
+use std::{env, sync::Arc};
+
+use chrono::{Duration, Utc};
+use sled::Db;
+use sui_sdk::types::{
+    base_types::{ObjectID, SuiAddress},
+    signature::GenericSignature,
+    transaction::{
+        Argument, Command, Transaction, TransactionData, TransactionDataAPI, TransactionKind,
+    },
+};
+use sui_squad_core::signer::{InMemorySigner, Signer};
+
+use crate::{error::ErrorKeeper, limits::dto::SpendRecord};
+
+use super::dto::SponsorPolicy;
+
+const SPONSOR_TREE: &str = "sponsor_gas";
+
+fn db_error(e: impl ToString) -> ErrorKeeper {
+    ErrorKeeper {
+        message: e.to_string(),
+        status: 500,
+    }
+}
+
+fn not_sponsorable(reason: &str) -> ErrorKeeper {
+    ErrorKeeper {
+        message: format!("Transaction cannot be sponsored: {}", reason),
+        status: 403,
+    }
+}
+
+/// Load the dedicated sponsor key from `SPONSOR_SEED` when sponsorship is enabled.
+///
+/// The sponsor signs transactions built from user input, so it must be a separate key holding
+/// only gas money, never the admin key.
+pub fn load_sponsor(policy: &SponsorPolicy, admin: SuiAddress) -> Option<Arc<dyn Signer>> {
+    if !policy.enabled {
+        return None;
+    }
+
+    let seed = env::var("SPONSOR_SEED")
+        .ok()
+        .filter(|seed| !seed.trim().is_empty())
+        .expect("SPONSOR_SEED must be set when SPONSOR_GAS is enabled");
+
+    let sponsor =
+        InMemorySigner::from_mnemonic(&seed).expect("Failed to load sponsor from mnemonic");
+
+    if sponsor.address() == admin {
+        panic!("SPONSOR_SEED must be a separate key from the admin key");
+    }
+
+    println!("Sponsor: {}", sponsor.address());
+
+    Some(Arc::new(sponsor))
+}
+
+fn decode_records(bytes: Option<&[u8]>) -> Result<Vec<SpendRecord>, ErrorKeeper> {
+    match bytes {
+        Some(bytes) => serde_json::from_slice(bytes).map_err(db_error),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Reserve `gas_budget` against the user's daily sponsorship allowance.
+///
+/// Settle the returned record with [`settle_gas`] once the transaction ran, or undo it with
+/// [`release_gas`] if it never reached the chain.
+pub fn reserve_gas(
+    db: &Db,
+    policy: &SponsorPolicy,
+    telegram_id: &str,
+    gas_budget: u64,
+) -> Result<SpendRecord, ErrorKeeper> {
+    let tree = db.open_tree(SPONSOR_TREE).map_err(db_error)?;
+
+    loop {
+        let current = tree.get(telegram_id).map_err(db_error)?;
+        let day_ago = (Utc::now() - Duration::days(1)).timestamp_millis();
+
+        let mut records: Vec<SpendRecord> = decode_records(current.as_deref())?
+            .into_iter()
+            .filter(|record| record.timestamp > day_ago)
+            .collect();
+
+        let used = records
+            .iter()
+            .fold(0u64, |used, record| used.saturating_add(record.amount));
+
+        if used.saturating_add(gas_budget) > policy.max_gas_per_day {
+            return Err(ErrorKeeper {
+                message: format!(
+                    "Daily sponsored gas of {} MIST used up ({} MIST in the last 24 hours), try again later",
+                    policy.max_gas_per_day, used
+                ),
+                status: 429,
+            });
+        }
+
+        let record = SpendRecord {
+            timestamp: Utc::now().timestamp_millis(),
+            amount: gas_budget,
+        };
+
+        records.push(record.clone());
+
+        let bytes = serde_json::to_vec(&records).map_err(db_error)?;
+
+        if tree
+            .compare_and_swap(telegram_id, current, Some(bytes))
+            .map_err(db_error)?
+            .is_ok()
+        {
+            return Ok(record);
+        }
+    }
+}
+
+fn update_record(db: &Db, telegram_id: &str, record: &SpendRecord, gas_used: Option<u64>) {
+    let tree = match db.open_tree(SPONSOR_TREE) {
+        Ok(tree) => tree,
+        Err(e) => {
+            println!("Failed to update sponsored gas: {}", e);
+            return;
+        }
+    };
+
+    let result = tree.fetch_and_update(telegram_id, |current| {
+        let bytes = current?;
+
+        let Ok(mut records) = serde_json::from_slice::<Vec<SpendRecord>>(bytes) else {
+            return Some(bytes.to_vec());
+        };
+
+        if let Some(index) = records.iter().position(|r| r == record) {
+            match gas_used {
+                Some(amount) => records[index].amount = amount,
+                None => {
+                    records.remove(index);
+                }
+            }
+        }
+
+        Some(serde_json::to_vec(&records).unwrap_or_else(|_| bytes.to_vec()))
+    });
+
+    if let Err(e) = result {
+        println!("Failed to update sponsored gas: {}", e);
+    }
+}
+
+/// Replace the reserved budget with the gas the transaction actually used.
+pub fn settle_gas(db: &Db, telegram_id: &str, record: &SpendRecord, gas_used: u64) {
+    update_record(db, telegram_id, record, Some(gas_used));
+}
+
+/// Undo a reservation made by [`reserve_gas`].
+pub fn release_gas(db: &Db, telegram_id: &str, record: &SpendRecord) {
+    update_record(db, telegram_id, record, None);
+}
+
+fn ensure_no_gas_coin(arguments: &[Argument]) -> Result<(), ErrorKeeper> {
+    if arguments
+        .iter()
+        .any(|argument| matches!(argument, Argument::GasCoin))
+    {
+        return Err(not_sponsorable("the sponsor's gas coin cannot be used"));
+    }
+
+    Ok(())
+}
+
+/// Check that a transaction only moves the user's own coins into `account::fund`, or is an
+/// `account::owner_withdraw` signed by the account owner, before the sponsor signs it.
+pub fn ensure_sponsorable(
+    tx: &TransactionData,
+    package_id: ObjectID,
+    sponsor: SuiAddress,
+) -> Result<(), ErrorKeeper> {
+    if tx.gas_owner() != sponsor {
+        return Err(not_sponsorable("gas is not paid by the sponsor"));
+    }
+
+    let TransactionKind::ProgrammableTransaction(pt) = tx.kind() else {
+        return Err(not_sponsorable(
+            "only programmable transactions are allowed",
+        ));
+    };
+
+    let mut calls_account = false;
+
+    for command in &pt.commands {
+        match command {
+            Command::MergeCoins(target, sources) => {
+                ensure_no_gas_coin(&[*target])?;
+                ensure_no_gas_coin(sources)?;
+            }
+            Command::SplitCoins(coin, amounts) => {
+                ensure_no_gas_coin(&[*coin])?;
+                ensure_no_gas_coin(amounts)?;
+            }
+            Command::MoveCall(call)
+                if call.package == package_id
+                    && call.module.as_str() == "account"
+                    && matches!(
+                        call.function.as_str(),
+                        "fund" | "owner_withdraw" | "enable_self_custody"
+                    ) =>
+            {
+                ensure_no_gas_coin(&call.arguments)?;
+                calls_account = true;
+            }
+            _ => {
+                return Err(not_sponsorable(
+                    "only account::fund, account::owner_withdraw and account::enable_self_custody calls are allowed",
+                ));
+            }
+        }
+    }
+
+    if !calls_account {
+        return Err(not_sponsorable(
+            "no account::fund, account::owner_withdraw or account::enable_self_custody call found",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Add the sponsor signature next to the user's signature.
+pub async fn add_sponsor_signature(
+    transaction: Transaction,
+    sponsor: &dyn Signer,
+) -> Result<Transaction, ErrorKeeper> {
+    let data = transaction.data().transaction_data().clone();
+    let mut signatures = transaction.data().tx_signatures().to_vec();
+
+    let signature = sponsor
+        .sign_transaction(&data)
+        .await
+        .map_err(|e| ErrorKeeper {
+            message: e.to_string(),
+            status: 500,
+        })?;
+
+    signatures.push(GenericSignature::Signature(signature));
+
+    Ok(Transaction::from_generic_sig_data(data, signatures))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(max_gas_per_day: u64) -> SponsorPolicy {
+        SponsorPolicy {
+            enabled: true,
+            max_gas_per_day,
+        }
+    }
+
+    #[test]
+    fn daily_allowance_is_enforced() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+
+        let record = reserve_gas(&db, &policy(100), "1", 60).unwrap();
+
+        assert!(reserve_gas(&db, &policy(100), "1", 41).is_err());
+
+        release_gas(&db, "1", &record);
+
+        assert!(reserve_gas(&db, &policy(100), "1", 100).is_ok());
+    }
+
+    #[test]
+    fn huge_allowances_do_not_overflow() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let policy = policy(u64::MAX);
+
+        reserve_gas(&db, &policy, "1", u64::MAX - 1).unwrap();
+
+        let error = reserve_gas(&db, &policy, "1", u64::MAX).unwrap_err();
+
+        assert_eq!(error.status, 429);
+    }
+}
@@ -1,22 +1,89 @@
+use sled::Db;
 use squad_connect::client::squad_connect::SquadConnect;
-use std::path::PathBuf;
+use std::{
+    path::PathBuf,
+    sync::{
+        Arc, RwLock,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+};
 use sui_sdk::types::base_types::SuiAddress;
+use sui_squad_core::{signer::Signer, suins::NameResolver, zklogin::ZkLoginProvider};
+
+use crate::{limits::dto::SpendingPolicy, sponsor::dto::SponsorPolicy};
 
 #[derive(Clone)]
 pub struct KeeperState {
     squad_connect_client: SquadConnect,
-    admin: SuiAddress,
+    admin: Arc<RwLock<Arc<dyn Signer>>>,
+    admin_retired: Arc<AtomicBool>,
     path: PathBuf,
+    db: Db,
+    spending_policy: SpendingPolicy,
+    name_resolver: Arc<dyn NameResolver>,
+    relayers: Vec<Arc<dyn Signer>>,
+    authorized_relayers: Arc<RwLock<Vec<SuiAddress>>>,
+    next_relayer: Arc<AtomicUsize>,
+    sponsor: Option<Arc<dyn Signer>>,
+    sponsor_policy: SponsorPolicy,
+    zk_login: Arc<dyn ZkLoginProvider>,
 }
 
-impl From<(SquadConnect, SuiAddress, PathBuf)> for KeeperState {
-    fn from(state: (SquadConnect, SuiAddress, PathBuf)) -> Self {
-        let (squad_connect_client, admin, path) = state;
+impl
+    From<(
+        SquadConnect,
+        Arc<dyn Signer>,
+        PathBuf,
+        Db,
+        SpendingPolicy,
+        Arc<dyn NameResolver>,
+        Vec<Arc<dyn Signer>>,
+        Option<Arc<dyn Signer>>,
+        SponsorPolicy,
+        Arc<dyn ZkLoginProvider>,
+    )> for KeeperState
+{
+    fn from(
+        state: (
+            SquadConnect,
+            Arc<dyn Signer>,
+            PathBuf,
+            Db,
+            SpendingPolicy,
+            Arc<dyn NameResolver>,
+            Vec<Arc<dyn Signer>>,
+            Option<Arc<dyn Signer>>,
+            SponsorPolicy,
+            Arc<dyn ZkLoginProvider>,
+        ),
+    ) -> Self {
+        let (
+            squad_connect_client,
+            admin,
+            path,
+            db,
+            spending_policy,
+            name_resolver,
+            relayers,
+            sponsor,
+            sponsor_policy,
+            zk_login,
+        ) = state;
 
         Self {
             squad_connect_client,
-            admin,
+            admin: Arc::new(RwLock::new(admin)),
+            admin_retired: Arc::new(AtomicBool::new(false)),
             path,
+            db,
+            spending_policy,
+            name_resolver,
+            relayers,
+            authorized_relayers: Arc::new(RwLock::new(Vec::new())),
+            next_relayer: Arc::new(AtomicUsize::new(0)),
+            sponsor,
+            sponsor_policy,
+            zk_login,
         }
     }
 }
@@ -27,13 +94,93 @@ impl KeeperState {
         &self.squad_connect_client
     }
 
-    /// Get a reference to the admin
-    pub fn admin(&self) -> &SuiAddress {
-        &self.admin
+    /// Get the address of the admin key currently signing transactions
+    pub fn admin(&self) -> SuiAddress {
+        self.admin_signer().address()
+    }
+
+    /// Get the signer of the admin key
+    pub fn admin_signer(&self) -> Arc<dyn Signer> {
+        self.admin.read().unwrap().clone()
+    }
+
+    /// Switch to a new admin key; requests already in flight keep the key they started with
+    pub fn set_admin(&self, admin: Arc<dyn Signer>) {
+        *self.admin.write().unwrap() = admin;
+        self.admin_retired.store(false, Ordering::Relaxed);
     }
 
-    /// Get a reference to the path
+    /// Stop relaying with the admin key once the chain accepted another admin, until
+    /// [`KeeperState::set_admin`] switches to it
+    pub fn retire_admin(&self) {
+        self.admin_retired.store(true, Ordering::Relaxed);
+    }
+
+    /// Get a reference to the zkLogin keystore path
     pub fn path(&self) -> &PathBuf {
         &self.path
     }
+
+    /// Get a reference to the sled database
+    pub fn db(&self) -> &Db {
+        &self.db
+    }
+
+    /// Get a reference to the admin spending policy
+    pub fn spending_policy(&self) -> &SpendingPolicy {
+        &self.spending_policy
+    }
+
+    /// Get a reference to the SuiNS name resolver
+    pub fn name_resolver(&self) -> &Arc<dyn NameResolver> {
+        &self.name_resolver
+    }
+
+    /// Get a reference to the extra relayer signers loaded from `RELAYER_SEEDS`
+    pub fn relayers(&self) -> &Vec<Arc<dyn Signer>> {
+        &self.relayers
+    }
+
+    /// Record the relayer addresses the `Admin` object authorizes
+    pub fn set_authorized_relayers(&self, authorized: Vec<SuiAddress>) {
+        *self.authorized_relayers.write().unwrap() = authorized;
+    }
+
+    /// Pick the key that signs the next relayed transaction, round-robin over the admin and
+    /// the relayers authorized on chain
+    ///
+    /// A retired admin key is left out while any relayer can sign in its place.
+    pub fn next_relayer(&self) -> Arc<dyn Signer> {
+        let authorized = self.authorized_relayers.read().unwrap();
+
+        let mut signers: Vec<Arc<dyn Signer>> = self
+            .relayers
+            .iter()
+            .filter(|relayer| authorized.contains(&relayer.address()))
+            .cloned()
+            .collect();
+
+        if signers.is_empty() || !self.admin_retired.load(Ordering::Relaxed) {
+            signers.push(self.admin_signer());
+        }
+
+        let index = self.next_relayer.fetch_add(1, Ordering::Relaxed) % signers.len();
+
+        signers.swap_remove(index)
+    }
+
+    /// Get the key paying gas for funding transactions, or `None` when sponsorship is disabled
+    pub fn sponsor(&self) -> Option<Arc<dyn Signer>> {
+        self.sponsor.clone()
+    }
+
+    /// Get a reference to the gas sponsorship policy
+    pub fn sponsor_policy(&self) -> &SponsorPolicy {
+        &self.sponsor_policy
+    }
+
+    /// Get a reference to the zkLogin address provider
+    pub fn zk_login(&self) -> &Arc<dyn ZkLoginProvider> {
+        &self.zk_login
+    }
 }
@@ -1,8 +1,6 @@
 use std::{env, sync::Arc};
 
 use axum::extract::{Request, State};
-use shared_crypto::intent::Intent;
-use sui_keys::keystore::{AccountKeystore, FileBasedKeystore};
 use sui_sdk::{
     json::SuiJsonValue,
     rpc_types::{EventFilter, SuiTransactionBlockResponseOptions},
@@ -13,7 +11,11 @@ use sui_sdk::{
 };
 use sui_squad_core::{helpers::dtos::UserPayload, package::dto::Event};
 
-use crate::{error::ErrorKeeper, state::KeeperState};
+use crate::{
+    admin::helpers::{ensure_chain_not_paused, ensure_not_paused},
+    error::ErrorKeeper,
+    state::KeeperState,
+};
 
 #[axum::debug_handler]
 pub async fn create_user_if_not_exists(
@@ -38,12 +40,7 @@ pub async fn create_user_if_not_exists(
     let node = keeper_state.squad_connect_client().get_node();
 
     let admin = keeper_state.admin();
-    let path = keeper_state.path();
-
-    let keystore = FileBasedKeystore::new(&path).map_err(|e| ErrorKeeper {
-        message: e.to_string(),
-        status: 500,
-    })?;
+    let relayer = keeper_state.next_relayer();
 
     let account_events = node
         .event_api()
@@ -111,12 +108,15 @@ pub async fn create_user_if_not_exists(
     });
 
     if let None = account_event {
+        ensure_not_paused(keeper_state.db())?;
+        ensure_chain_not_paused(&keeper_state, admin_object_id).await?;
+
         let gas_budget = 10_000_000;
 
         let tx = node
             .transaction_builder()
             .move_call(
-                admin.clone(),
+                relayer.address(),
                 package_object_id,
                 "account",
                 "create_new_account",
@@ -139,8 +139,9 @@ pub async fn create_user_if_not_exists(
                 status: 500,
             })?;
 
-        let signature = keystore
-            .sign_secure(&admin, &tx, Intent::sui_transaction())
+        let signature = relayer
+            .sign_transaction(&tx)
+            .await
             .map_err(|e| ErrorKeeper {
                 message: e.to_string(),
                 status: 500,
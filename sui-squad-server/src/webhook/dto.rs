@@ -0,0 +1,74 @@
+use std::env;
+
+use serde::{Deserialize, Serialize};
+use sui_squad_core::helpers::dtos::LoginPurpose;
+use utoipa::{IntoParams, ToSchema};
+
+/// OAuth state sent through Google sign-in back to the funding page.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LoginState {
+    pub session: String,
+    /// Custody logins are forwarded from the funding page to the self-custody page.
+    pub purpose: LoginPurpose,
+    /// Amount the funding page prefills, set when offering to move a detected deposit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coin_type: Option<String>,
+}
+
+/// Operation a result page confirms.
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ResultKind {
+    #[default]
+    Funding,
+    Withdrawal,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ResultQuery {
+    pub digest: Option<String>,
+    #[serde(default)]
+    pub kind: ResultKind,
+}
+
+/// Look of the webhook pages, loaded from `BRAND_NAME`, `BRAND_COLOR` and `REDIRECT_BACK`.
+#[derive(Debug, Clone)]
+pub struct Branding {
+    pub name: String,
+    /// Hex color (`#rgb` or `#rrggbb`) of headings, buttons and links.
+    pub color: String,
+    /// Link back to the Telegram bot, shown once funding completes.
+    pub bot_url: String,
+}
+
+fn is_hex_color(color: &str) -> bool {
+    color
+        .strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+impl Branding {
+    pub fn from_env() -> Self {
+        let name = env::var("BRAND_NAME")
+            .ok()
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or_else(|| "Sui Squad".to_string());
+
+        let color = env::var("BRAND_COLOR")
+            .ok()
+            .map(|color| color.trim().to_string())
+            .filter(|color| is_hex_color(color))
+            .unwrap_or_else(|| "#4a6cf7".to_string());
+
+        let bot_url = env::var("REDIRECT_BACK").unwrap_or_default();
+
+        Self {
+            name,
+            color,
+            bot_url,
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use askama::Template;
+use axum::{
+    http::{
+        HeaderMap,
+        header::{ACCEPT_LANGUAGE, COOKIE},
+    },
+    response::Html,
+};
+use serde_json::json;
+
+use crate::{error::ErrorKeeper, fund::helpers::SUI_COIN_TYPE};
+
+use super::i18n::Locale;
+
+/// Cookie remembering the Telegram user's language between the login link and the callback.
+pub const LOCALE_COOKIE: &str = "squad_locale";
+
+const STYLE_CSS: &str = include_str!("../../assets/style.css");
+const FUND_JS: &str = include_str!("../../assets/fund.js");
+const RESULT_JS: &str = include_str!("../../assets/result.js");
+const CUSTODY_JS: &str = include_str!("../../assets/custody.js");
+
+/// Content type and body of an embedded static asset.
+pub fn asset(name: &str) -> Option<(&'static str, &'static str)> {
+    match name {
+        "style.css" => Some(("text/css; charset=utf-8", STYLE_CSS)),
+        "fund.js" => Some(("text/javascript; charset=utf-8", FUND_JS)),
+        "result.js" => Some(("text/javascript; charset=utf-8", RESULT_JS)),
+        "custody.js" => Some(("text/javascript; charset=utf-8", CUSTODY_JS)),
+        _ => None,
+    }
+}
+
+fn cookie_locale(headers: &HeaderMap) -> Option<Locale> {
+    headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(name, _)| *name == LOCALE_COOKIE)
+        .and_then(|(_, value)| Locale::from_tag(value))
+}
+
+fn accept_language(headers: &HeaderMap) -> Option<Locale> {
+    headers
+        .get(ACCEPT_LANGUAGE)?
+        .to_str()
+        .ok()?
+        .split(',')
+        .filter_map(|language| language.split(';').next())
+        .find_map(Locale::from_tag)
+}
+
+/// Locale of a page request: the Telegram language stored in the cookie, then the browser's.
+pub fn request_locale(headers: &HeaderMap) -> Locale {
+    cookie_locale(headers)
+        .or_else(|| accept_language(headers))
+        .unwrap_or_default()
+}
+
+pub fn locale_cookie(locale: Locale) -> String {
+    format!(
+        "{}={}; Path=/webhook; Max-Age=86400; SameSite=Lax; Secure; HttpOnly",
+        LOCALE_COOKIE,
+        locale.code()
+    )
+}
+
+/// JSON configuration of `fund.js` and `custody.js`, safe to inline in a `<script>` element.
+pub fn page_config(locale: Locale) -> Result<String, ErrorKeeper> {
+    let config = json!({
+        "messages": locale.messages(),
+        "coin_type": SUI_COIN_TYPE,
+    });
+
+    let config = serde_json::to_string(&config).map_err(|e| ErrorKeeper {
+        message: e.to_string(),
+        status: 500,
+    })?;
+
+    Ok(config.replace("</", "<\\/"))
+}
+
+pub fn render(template: impl Template) -> Result<Html<String>, ErrorKeeper> {
+    template.render().map(Html).map_err(|e| ErrorKeeper {
+        message: e.to_string(),
+        status: 500,
+    })
+}
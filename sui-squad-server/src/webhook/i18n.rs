@@ -0,0 +1,223 @@
+use serde::Serialize;
+
+/// Languages the webhook pages are translated to; anything else falls back to English.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+}
+
+impl Locale {
+    /// Match an IETF language tag such as Telegram's `language_code` (`es`, `es-AR`, ...).
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.trim().split(['-', '_']).next()?.to_ascii_lowercase();
+
+        match primary.as_str() {
+            "en" => Some(Locale::En),
+            "es" => Some(Locale::Es),
+            _ => None,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Es => "es",
+        }
+    }
+
+    pub fn messages(&self) -> &'static Messages {
+        match self {
+            Locale::En => &EN,
+            Locale::Es => &ES,
+        }
+    }
+}
+
+/// Texts of the login, funding, self-custody and result pages.
+///
+/// Placeholders in braces (`{error}`, `{amount}`, ...) are filled in by `fund.js` and `custody.js`.
+#[derive(Debug, Serialize)]
+pub struct Messages {
+    pub login_title: &'static str,
+    pub processing: &'static str,
+    pub getting_address: &'static str,
+    pub address_retrieved: &'static str,
+    pub address_failed: &'static str,
+    pub address_failed_details: &'static str,
+    pub invalid_link: &'static str,
+    pub invalid_link_details: &'static str,
+    pub fund_heading: &'static str,
+    pub transfer_prompt: &'static str,
+    pub transfer_hint: &'static str,
+    pub copy: &'static str,
+    pub copied: &'static str,
+    pub copy_failed: &'static str,
+    pub checking_balance: &'static str,
+    pub amount_placeholder: &'static str,
+    pub max: &'static str,
+    pub refresh_balance: &'static str,
+    pub available: &'static str,
+    pub fees_covered: &'static str,
+    pub balance_failed: &'static str,
+    pub balance_not_loaded: &'static str,
+    pub invalid_amount: &'static str,
+    pub too_many_decimals: &'static str,
+    pub amount_zero: &'static str,
+    pub amount_too_high: &'static str,
+    pub fund_button: &'static str,
+    pub funding: &'static str,
+    pub funding_failed_button: &'static str,
+    pub funding_failed: &'static str,
+    pub error: &'static str,
+    pub try_again: &'static str,
+    pub funding_success: &'static str,
+    pub funding_success_details: &'static str,
+    pub transaction_details: &'static str,
+    pub view_explorer: &'static str,
+    pub redirecting: &'static str,
+    pub seconds: &'static str,
+    pub open_bot: &'static str,
+    pub expired_title: &'static str,
+    pub expired_details: &'static str,
+    pub expired_hint: &'static str,
+    pub custody_heading: &'static str,
+    pub custody_status_failed: &'static str,
+    pub custody_enable_prompt: &'static str,
+    pub custody_enable_button: &'static str,
+    pub custody_enabling: &'static str,
+    pub custody_enable_failed: &'static str,
+    pub custody_other_owner: &'static str,
+    pub custody_withdraw_prompt: &'static str,
+    pub withdraw_amount_placeholder: &'static str,
+    pub recipient_placeholder: &'static str,
+    pub recipient_required: &'static str,
+    pub withdraw_button: &'static str,
+    pub withdrawing: &'static str,
+    pub withdrawal_failed: &'static str,
+    pub withdrawal_success: &'static str,
+    pub withdrawal_success_details: &'static str,
+}
+
+pub static EN: Messages = Messages {
+    login_title: "Login",
+    processing: "Processing login...",
+    getting_address: "Getting your wallet address...",
+    address_retrieved: "Address Retrieved Successfully!",
+    address_failed: "Failed to get address",
+    address_failed_details: "❌ Sorry, we couldn't get your wallet address.\n\nError: {error}\n\nPlease try again or contact support if the problem persists.",
+    invalid_link: "Invalid login link",
+    invalid_link_details: "⚠️ No login token found in the URL.\n\nThis usually means the login link is invalid or has expired.\n\nPlease try logging in again from the bot.",
+    fund_heading: "💰 Fund Your Account",
+    transfer_prompt: "Please transfer tokens to the following address:",
+    transfer_hint: "Copy the address above and transfer tokens to fund your account.",
+    copy: "📋 Copy",
+    copied: "✅ Copied!",
+    copy_failed: "❌ Failed",
+    checking_balance: "Checking your balance...",
+    amount_placeholder: "Amount to deposit",
+    max: "Max",
+    refresh_balance: "🔄 Refresh balance",
+    available: "Available to deposit: {available} {symbol} (balance {balance} {symbol})",
+    fees_covered: " · network fees are covered for you",
+    balance_failed: "Could not load your balance: {error}",
+    balance_not_loaded: "Your balance is not loaded yet, refresh it after the transfer arrives",
+    invalid_amount: "Enter a valid amount",
+    too_many_decimals: "At most {decimals} decimal places are allowed",
+    amount_zero: "The amount must be greater than zero",
+    amount_too_high: "You can deposit at most {amount} {symbol}",
+    fund_button: "✅ I've Sent the Transfer - Complete Funding",
+    funding: "Processing...",
+    funding_failed_button: "❌ Funding Failed - Try Again",
+    funding_failed: "❌ Funding Failed",
+    error: "Error: {error}",
+    try_again: "Please try again or contact support if the problem persists.",
+    funding_success: "🎉 Funding Successful!",
+    funding_success_details: "Your account has been funded successfully.",
+    transaction_details: "📋 Transaction Details:",
+    view_explorer: "🔗 View on Sui Explorer",
+    redirecting: "Redirecting to the bot in",
+    seconds: "seconds...",
+    open_bot: "Back to the bot",
+    expired_title: "⚠️ Login link expired",
+    expired_details: "This login link has expired or was already used.",
+    expired_hint: "Run /fund in the bot to get a new link.",
+    custody_heading: "🔐 Self-custody",
+    custody_status_failed: "Could not load your self-custody status: {error}",
+    custody_enable_prompt: "Register this zkLogin address as the owner of your Squad account. It will be able to withdraw with its own signature, without relying on the bot operator:",
+    custody_enable_button: "🔐 Enable self-custody",
+    custody_enabling: "Enabling...",
+    custody_enable_failed: "❌ Could not enable self-custody",
+    custody_other_owner: "Self-custody is registered to another address:\n\n{owner}\n\nSign in with the Google account that owns it.",
+    custody_withdraw_prompt: "Withdraw SUI from your Squad account, signed by your zkLogin address:",
+    withdraw_amount_placeholder: "Amount to withdraw (SUI)",
+    recipient_placeholder: "Recipient address or .sui name",
+    recipient_required: "Enter the recipient address or .sui name",
+    withdraw_button: "📤 Withdraw",
+    withdrawing: "Withdrawing...",
+    withdrawal_failed: "❌ Withdrawal Failed",
+    withdrawal_success: "🎉 Withdrawal Sent!",
+    withdrawal_success_details: "The withdrawal was signed by your zkLogin address.",
+};
+
+pub static ES: Messages = Messages {
+    login_title: "Inicio de sesión",
+    processing: "Procesando el inicio de sesión...",
+    getting_address: "Obteniendo la dirección de tu billetera...",
+    address_retrieved: "¡Dirección obtenida!",
+    address_failed: "No se pudo obtener la dirección",
+    address_failed_details: "❌ No pudimos obtener la dirección de tu billetera.\n\nError: {error}\n\nVuelve a intentarlo o contacta con soporte si el problema continúa.",
+    invalid_link: "Enlace de inicio de sesión no válido",
+    invalid_link_details: "⚠️ No se encontró ningún token de inicio de sesión en la URL.\n\nNormalmente significa que el enlace no es válido o ha caducado.\n\nVuelve a iniciar sesión desde el bot.",
+    fund_heading: "💰 Recarga tu cuenta",
+    transfer_prompt: "Transfiere tokens a la siguiente dirección:",
+    transfer_hint: "Copia la dirección de arriba y transfiere tokens para recargar tu cuenta.",
+    copy: "📋 Copiar",
+    copied: "✅ ¡Copiado!",
+    copy_failed: "❌ Error",
+    checking_balance: "Consultando tu saldo...",
+    amount_placeholder: "Cantidad a depositar",
+    max: "Máx.",
+    refresh_balance: "🔄 Actualizar saldo",
+    available: "Disponible para depositar: {available} {symbol} (saldo {balance} {symbol})",
+    fees_covered: " · las comisiones de red corren por nuestra cuenta",
+    balance_failed: "No se pudo cargar tu saldo: {error}",
+    balance_not_loaded: "Tu saldo aún no se ha cargado, actualízalo cuando llegue la transferencia",
+    invalid_amount: "Introduce una cantidad válida",
+    too_many_decimals: "Se permiten como máximo {decimals} decimales",
+    amount_zero: "La cantidad debe ser mayor que cero",
+    amount_too_high: "Puedes depositar como máximo {amount} {symbol}",
+    fund_button: "✅ Ya hice la transferencia - Completar la recarga",
+    funding: "Procesando...",
+    funding_failed_button: "❌ La recarga falló - Reintentar",
+    funding_failed: "❌ La recarga falló",
+    error: "Error: {error}",
+    try_again: "Vuelve a intentarlo o contacta con soporte si el problema continúa.",
+    funding_success: "🎉 ¡Recarga completada!",
+    funding_success_details: "Tu cuenta se ha recargado correctamente.",
+    transaction_details: "📋 Detalles de la transacción:",
+    view_explorer: "🔗 Ver en Sui Explorer",
+    redirecting: "Volviendo al bot en",
+    seconds: "segundos...",
+    open_bot: "Volver al bot",
+    expired_title: "⚠️ Enlace caducado",
+    expired_details: "Este enlace de inicio de sesión ha caducado o ya se utilizó.",
+    expired_hint: "Usa /fund en el bot para obtener un enlace nuevo.",
+    custody_heading: "🔐 Autocustodia",
+    custody_status_failed: "No se pudo cargar el estado de tu autocustodia: {error}",
+    custody_enable_prompt: "Registra esta dirección zkLogin como propietaria de tu cuenta Squad. Podrá retirar fondos con su propia firma, sin depender del operador del bot:",
+    custody_enable_button: "🔐 Activar la autocustodia",
+    custody_enabling: "Activando...",
+    custody_enable_failed: "❌ No se pudo activar la autocustodia",
+    custody_other_owner: "La autocustodia está registrada a otra dirección:\n\n{owner}\n\nInicia sesión con la cuenta de Google propietaria.",
+    custody_withdraw_prompt: "Retira SUI de tu cuenta Squad con la firma de tu dirección zkLogin:",
+    withdraw_amount_placeholder: "Cantidad a retirar (SUI)",
+    recipient_placeholder: "Dirección destino o nombre .sui",
+    recipient_required: "Introduce la dirección destino o el nombre .sui",
+    withdraw_button: "📤 Retirar",
+    withdrawing: "Retirando...",
+    withdrawal_failed: "❌ El retiro falló",
+    withdrawal_success: "🎉 ¡Retiro enviado!",
+    withdrawal_success_details: "El retiro se firmó con tu dirección zkLogin.",
+};
@@ -1 +1,5 @@
+pub mod dto;
 pub mod handler;
+pub mod helpers;
+pub mod i18n;
+pub mod templates;
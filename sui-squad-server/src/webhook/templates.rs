@@ -0,0 +1,44 @@
+use askama::Template;
+
+use super::{dto::Branding, i18n::Messages};
+
+/// Funding page Google sign-in redirects to; `config` is the JSON read by `fund.js`.
+#[derive(Template)]
+#[template(path = "fund.html")]
+pub struct FundPage {
+    pub locale: &'static str,
+    pub branding: Branding,
+    pub t: &'static Messages,
+    pub config: String,
+}
+
+/// Self-custody page the funding page forwards custody logins to; `config` is read by `custody.js`.
+#[derive(Template)]
+#[template(path = "custody.html")]
+pub struct CustodyPage {
+    pub locale: &'static str,
+    pub branding: Branding,
+    pub t: &'static Messages,
+    pub config: String,
+}
+
+#[derive(Template)]
+#[template(path = "result.html")]
+pub struct ResultPage {
+    pub locale: &'static str,
+    pub branding: Branding,
+    pub t: &'static Messages,
+    pub title: &'static str,
+    pub details: &'static str,
+    pub digest: Option<String>,
+    pub explorer_url: String,
+}
+
+/// Shown when a login link is opened after it expired or was already used.
+#[derive(Template)]
+#[template(path = "expired.html")]
+pub struct ExpiredPage {
+    pub locale: &'static str,
+    pub branding: Branding,
+    pub t: &'static Messages,
+}
@@ -2,8 +2,6 @@ use std::str::FromStr;
 use std::{env, sync::Arc};
 
 use axum::extract::{Extension, Json, State};
-use shared_crypto::intent::Intent;
-use sui_keys::keystore::{AccountKeystore, FileBasedKeystore};
 use sui_sdk::types::TypeTag;
 use sui_sdk::{
     json::SuiJsonValue,
@@ -15,17 +13,32 @@ use sui_sdk::{
 };
 use sui_squad_core::helpers::dtos::UserPayload;
 use sui_squad_core::{
-    helpers::dtos::{DigestResponse, WithdrawRequest},
+    helpers::dtos::{Notification, NotificationKind, WithdrawRequest, WithdrawResponse},
     package::dto::Event,
 };
 
-use crate::{error::ErrorKeeper, state::KeeperState};
+use crate::{
+    admin::helpers::{ensure_chain_allows, ensure_not_paused},
+    custody::helpers::ensure_not_self_custody,
+    error::ErrorKeeper,
+    limits::helpers::{release_spending, reserve_spending},
+    notifications::helpers::{executed, notify},
+    state::KeeperState,
+};
+
+use super::helpers::resolve_recipient;
 
 pub async fn withdraw(
     State(keeper_state): State<Arc<KeeperState>>,
     Extension(user): Extension<UserPayload>,
     Json(withdraw_request): Json<WithdrawRequest>,
-) -> Result<Json<DigestResponse>, ErrorKeeper> {
+) -> Result<Json<WithdrawResponse>, ErrorKeeper> {
+    let (recipient, address, name) = resolve_recipient(
+        keeper_state.name_resolver().as_ref(),
+        &withdraw_request.address,
+    )
+    .await?;
+
     let package_id = env::var("SUI_SQUAD_PACKAGE_ID").map_err(|e| ErrorKeeper {
         message: e.to_string(),
         status: 500,
@@ -41,12 +54,7 @@ pub async fn withdraw(
     let node = squad_connect_client.get_node();
 
     let admin = keeper_state.admin();
-    let path = keeper_state.path();
-
-    let keystore = FileBasedKeystore::new(&path).map_err(|e| ErrorKeeper {
-        message: e.to_string(),
-        status: 500,
-    })?;
+    let relayer = keeper_state.next_relayer();
 
     let admin_events = node
         .event_api()
@@ -144,6 +152,9 @@ pub async fn withdraw(
         status: 500,
     })?;
 
+    ensure_chain_allows(&keeper_state, admin_object_id, account_object_id).await?;
+    ensure_not_self_custody(&keeper_state, account_object_id).await?;
+
     let coin_name = "0x2::sui::SUI".to_string();
 
     let coin_type = TypeTag::from_str(&coin_name).map_err(|e| ErrorKeeper {
@@ -151,14 +162,12 @@ pub async fn withdraw(
         status: 500,
     })?;
 
-    let address = &withdraw_request.address;
-
-    let address_object_id = ObjectID::from_address(address.parse().unwrap());
+    let address_object_id = ObjectID::from_address(recipient);
 
     let tx = node
         .transaction_builder()
         .move_call(
-            admin.clone(),
+            relayer.address(),
             package_object_id,
             "account",
             "withdraw",
@@ -185,12 +194,28 @@ pub async fn withdraw(
             status: 500,
         })?;
 
-    let signature = keystore
-        .sign_secure(&admin, &tx, Intent::sui_transaction())
-        .map_err(|e| ErrorKeeper {
+    ensure_not_paused(keeper_state.db())?;
+
+    let spend_record = reserve_spending(
+        keeper_state.db(),
+        keeper_state.spending_policy(),
+        &user.telegram_id,
+        &coin_name,
+        withdraw_request.amount,
+    )?;
+
+    let signature = relayer.sign_transaction(&tx).await.map_err(|e| {
+        release_spending(
+            keeper_state.db(),
+            &user.telegram_id,
+            &coin_name,
+            &spend_record,
+        );
+        ErrorKeeper {
             message: e.to_string(),
             status: 500,
-        })?;
+        }
+    })?;
 
     let transaction_response = node
         .quorum_driver_api()
@@ -200,12 +225,53 @@ pub async fn withdraw(
             Some(ExecuteTransactionRequestType::WaitForLocalExecution),
         )
         .await
-        .unwrap();
+        .map_err(|e| {
+            release_spending(
+                keeper_state.db(),
+                &user.telegram_id,
+                &coin_name,
+                &spend_record,
+            );
+            ErrorKeeper {
+                message: e.to_string(),
+                status: 500,
+            }
+        })?;
 
     println!("{}", transaction_response);
     println!("Transaction created successfully: {:?}", tx);
 
-    Ok(Json(DigestResponse {
+    if executed(&transaction_response) {
+        notify(
+            keeper_state.db(),
+            Notification {
+                id: 0,
+                telegram_id: user.telegram_id.clone(),
+                kind: NotificationKind::WithdrawalExecuted,
+                amount: withdraw_request.amount,
+                coin_type: coin_name,
+                sender: None,
+                recipient: Some(match &name {
+                    Some(name) => format!("{} ({})", name, address),
+                    None => address.clone(),
+                }),
+                digest: transaction_response.digest.to_string(),
+                timestamp: 0,
+            },
+        );
+    } else {
+        // Aborted on chain, nothing was spent
+        release_spending(
+            keeper_state.db(),
+            &user.telegram_id,
+            &coin_name,
+            &spend_record,
+        );
+    }
+
+    Ok(Json(WithdrawResponse {
         digest: transaction_response.digest.to_string(),
+        address,
+        name,
     }))
 }
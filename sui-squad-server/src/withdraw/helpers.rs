@@ -0,0 +1,113 @@
+use std::str::FromStr;
+
+use sui_sdk::types::base_types::SuiAddress;
+use sui_squad_core::{
+    helpers::address::normalize_sui_address,
+    suins::{NameResolver, is_sui_name},
+};
+
+use crate::error::ErrorKeeper;
+
+/// Resolve a withdrawal recipient given as a Sui address or a SuiNS name into the address and,
+/// when a name was used, the normalized name.
+pub async fn resolve_recipient(
+    name_resolver: &dyn NameResolver,
+    recipient: &str,
+) -> Result<(SuiAddress, String, Option<String>), ErrorKeeper> {
+    let (address, name) = if is_sui_name(recipient) {
+        let name = recipient.trim().to_lowercase();
+
+        let resolved = name_resolver
+            .resolve(&name)
+            .await
+            .map_err(|e| ErrorKeeper {
+                message: e.to_string(),
+                status: 502,
+            })?
+            .ok_or_else(|| ErrorKeeper {
+                message: format!("{} does not resolve to an address", name),
+                status: 404,
+            })?;
+
+        let address = normalize_sui_address(&resolved).ok_or_else(|| ErrorKeeper {
+            message: format!("{} resolved to an invalid address: {}", name, resolved),
+            status: 502,
+        })?;
+
+        (address, Some(name))
+    } else {
+        let address = normalize_sui_address(recipient).ok_or_else(|| ErrorKeeper {
+            message: format!(
+                "Invalid Sui address: {}. Expected 0x followed by 64 hex characters or a .sui name",
+                recipient
+            ),
+            status: 400,
+        })?;
+
+        (address, None)
+    };
+
+    let sui_address = SuiAddress::from_str(&address).map_err(|e| ErrorKeeper {
+        message: e.to_string(),
+        status: 400,
+    })?;
+
+    Ok((sui_address, address, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use sui_squad_core::suins::LocalNameResolver;
+
+    use super::*;
+
+    const ALICE: &str = "0x00000000000000000000000000000000000000000000000000000000000a11ce";
+
+    fn resolver() -> LocalNameResolver {
+        LocalNameResolver::new(HashMap::from([
+            ("alice.sui".to_string(), ALICE.to_string()),
+            ("broken.sui".to_string(), "not an address".to_string()),
+        ]))
+    }
+
+    #[tokio::test]
+    async fn resolves_names_case_insensitively() {
+        let (_, address, name) = resolve_recipient(&resolver(), " Alice.SUI ").await.unwrap();
+
+        assert_eq!(address, ALICE);
+        assert_eq!(name.as_deref(), Some("alice.sui"));
+    }
+
+    #[tokio::test]
+    async fn unknown_names_are_not_found() {
+        let error = resolve_recipient(&resolver(), "bob.sui").await.unwrap_err();
+
+        assert_eq!(error.status, 404);
+    }
+
+    #[tokio::test]
+    async fn names_resolving_to_invalid_addresses_are_rejected() {
+        let error = resolve_recipient(&resolver(), "broken.sui")
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.status, 502);
+    }
+
+    #[tokio::test]
+    async fn addresses_are_passed_through_without_a_name() {
+        let (_, address, name) = resolve_recipient(&resolver(), ALICE).await.unwrap();
+
+        assert_eq!(address, ALICE);
+        assert_eq!(name, None);
+    }
+
+    #[tokio::test]
+    async fn invalid_addresses_are_rejected() {
+        let error = resolve_recipient(&resolver(), "0x1234").await.unwrap_err();
+
+        assert_eq!(error.status, 400);
+    }
+}
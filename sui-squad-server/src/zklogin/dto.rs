@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+/// Only the address is returned; the salt never leaves the server.
+#[derive(Serialize, ToSchema)]
+pub struct ZkLoginAddressResponse {
+    pub address: String,
+}
+
+/// Per-user zkLogin session, reused across login links until `max_epoch`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZkLoginRecord {
+    pub public_key: String,
+    pub randomness: String,
+    pub max_epoch: u64,
+    /// Address of the ephemeral key in the shared keystore.
+    pub ephemeral_address: String,
+    /// zkLogin address the cached proof belongs to.
+    pub address: Option<String>,
+    /// Cached zk proof inputs; the prover is only called again for a new key or another account.
+    pub proof: Option<Value>,
+    /// Whether the user was already asked to log in again before `max_epoch`.
+    pub expiry_notified: bool,
+}
@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Json, Path, State},
+    http::HeaderMap,
+};
+use sui_squad_core::helpers::dtos::{ZkLoginAddressInfo, ZkLoginSessionInfo};
+
+use crate::{error::ErrorKeeper, state::KeeperState};
+
+use super::{
+    dto::ZkLoginAddressResponse,
+    helpers::{known_address, lookup_address, reusable_session},
+};
+
+#[utoipa::path(
+    post,
+    path = "/zklogin/address",
+    summary = "Get the zkLogin address of an id_token",
+    description = "Looks up the zkLogin address of the OAuth id_token sent as a Bearer token, keeping the Enoki API key on the server",
+    responses(
+        (status = 200, description = "The zkLogin address", body = ZkLoginAddressResponse),
+        (status = 401, description = "Missing or rejected id_token")
+    )
+)]
+#[axum::debug_handler]
+pub async fn address(
+    State(keeper_state): State<Arc<KeeperState>>,
+    headers: HeaderMap,
+) -> Result<Json<ZkLoginAddressResponse>, ErrorKeeper> {
+    lookup_address(keeper_state.zk_login().as_ref(), &headers)
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    get,
+    path = "/internal/zklogin/{telegram_id}",
+    summary = "Get the reusable zkLogin session of a user",
+    description = "Returns the ephemeral zkLogin parameters of the user while they are valid long enough to be reused for a new login link",
+    params(
+        ("telegram_id" = String, Path, description = "Telegram ID of the user")
+    ),
+    responses(
+        (status = 200, description = "The reusable zkLogin session", body = [ZkLoginSessionInfo]),
+        (status = 404, description = "No session, or it is about to expire")
+    )
+)]
+#[axum::debug_handler]
+pub async fn session_info(
+    State(keeper_state): State<Arc<KeeperState>>,
+    Path(telegram_id): Path<String>,
+) -> Result<Json<ZkLoginSessionInfo>, ErrorKeeper> {
+    let session = reusable_session(&keeper_state, &telegram_id).await?;
+
+    Ok(Json(session))
+}
+
+#[utoipa::path(
+    get,
+    path = "/internal/zklogin/{telegram_id}/address",
+    summary = "Get the known zkLogin address of a user",
+    description = "Returns the zkLogin address the user last logged in with, even after their session expired",
+    params(
+        ("telegram_id" = String, Path, description = "Telegram ID of the user")
+    ),
+    responses(
+        (status = 200, description = "The zkLogin address", body = ZkLoginAddressInfo),
+        (status = 404, description = "The user never completed a zkLogin")
+    )
+)]
+#[axum::debug_handler]
+pub async fn session_address(
+    State(keeper_state): State<Arc<KeeperState>>,
+    Path(telegram_id): Path<String>,
+) -> Result<Json<ZkLoginAddressInfo>, ErrorKeeper> {
+    let address = known_address(keeper_state.db(), &telegram_id)?.ok_or_else(|| ErrorKeeper {
+        message: "No known zkLogin address".to_string(),
+        status: 404,
+    })?;
+
+    Ok(Json(ZkLoginAddressInfo {
+        telegram_id,
+        address,
+    }))
+}
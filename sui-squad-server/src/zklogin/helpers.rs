@@ -0,0 +1,406 @@
+use std::{env, str::FromStr, sync::Arc, time::Duration};
+
+use axum::http::HeaderMap;
+use serde::Serialize;
+use serde_json::Value;
+use sled::Db;
+use squad_connect::client::squad_connect::SquadConnect;
+use sui_sdk::{
+    SuiClient,
+    types::{
+        base_types::SuiAddress,
+        crypto::PublicKey,
+        transaction::{Transaction, TransactionData},
+    },
+};
+use sui_squad_core::{
+    error::CoreError,
+    helpers::dtos::{Notification, NotificationKind, SessionRequest, ZkLoginSessionInfo},
+    zklogin::ZkLoginProvider,
+};
+
+use crate::{
+    error::ErrorKeeper, notifications::helpers::notify, sessions::dto::ZkLoginSession,
+    state::KeeperState,
+};
+
+use super::dto::{ZkLoginAddressResponse, ZkLoginRecord};
+
+const ZK_LOGIN_TREE: &str = "zklogin_records";
+
+fn db_error(e: impl ToString) -> ErrorKeeper {
+    ErrorKeeper {
+        message: e.to_string(),
+        status: 500,
+    }
+}
+
+/// Epochs before `max_epoch` from which a session is no longer reused and the user is asked to
+/// log in again, from `ZKLOGIN_RENEW_EPOCHS` (1 by default).
+fn renew_epochs() -> u64 {
+    env::var("ZKLOGIN_RENEW_EPOCHS")
+        .ok()
+        .and_then(|epochs| epochs.parse().ok())
+        .unwrap_or(1)
+}
+
+pub async fn current_epoch(node: &SuiClient) -> Result<u64, ErrorKeeper> {
+    node.governance_api()
+        .get_latest_sui_system_state()
+        .await
+        .map(|state| state.epoch)
+        .map_err(db_error)
+}
+
+fn get_record(db: &Db, telegram_id: &str) -> Result<Option<ZkLoginRecord>, ErrorKeeper> {
+    let tree = db.open_tree(ZK_LOGIN_TREE).map_err(db_error)?;
+
+    match tree.get(telegram_id).map_err(db_error)? {
+        Some(bytes) => serde_json::from_slice(&bytes).map(Some).map_err(db_error),
+        None => Ok(None),
+    }
+}
+
+fn save_record(db: &Db, telegram_id: &str, record: &ZkLoginRecord) -> Result<(), ErrorKeeper> {
+    let tree = db.open_tree(ZK_LOGIN_TREE).map_err(db_error)?;
+    let bytes = serde_json::to_vec(record).map_err(db_error)?;
+
+    tree.insert(telegram_id, bytes).map_err(db_error)?;
+
+    Ok(())
+}
+
+/// Record the ephemeral key behind a login link; the cached proof survives while the key is the same.
+pub fn register_key(db: &Db, request: &SessionRequest) -> Result<(), ErrorKeeper> {
+    let public_key = PublicKey::from_str(&request.public_key).map_err(|e| ErrorKeeper {
+        message: format!("Invalid ephemeral public key: {}", e),
+        status: 400,
+    })?;
+
+    if let Some(record) = get_record(db, &request.telegram_id)? {
+        if record.public_key == request.public_key
+            && record.randomness == request.randomness
+            && record.max_epoch == request.max_epoch
+        {
+            return Ok(());
+        }
+    }
+
+    save_record(
+        db,
+        &request.telegram_id,
+        &ZkLoginRecord {
+            public_key: request.public_key.clone(),
+            randomness: request.randomness.clone(),
+            max_epoch: request.max_epoch,
+            ephemeral_address: SuiAddress::from(&public_key).to_string(),
+            address: None,
+            proof: None,
+            expiry_notified: false,
+        },
+    )
+}
+
+/// The zkLogin address of the user's last proof, kept after the session itself expires.
+pub fn known_address(db: &Db, telegram_id: &str) -> Result<Option<String>, ErrorKeeper> {
+    Ok(get_record(db, telegram_id)?.and_then(|record| record.address))
+}
+
+/// The user's session when it is far enough from `max_epoch` to back a new login link.
+pub async fn reusable_session(
+    keeper_state: &KeeperState,
+    telegram_id: &str,
+) -> Result<ZkLoginSessionInfo, ErrorKeeper> {
+    let not_found = || ErrorKeeper {
+        message: "No reusable zkLogin session".to_string(),
+        status: 404,
+    };
+
+    let record = get_record(keeper_state.db(), telegram_id)?.ok_or_else(not_found)?;
+
+    let node = keeper_state.squad_connect_client().get_node();
+    let current_epoch = current_epoch(&node).await?;
+
+    if record.max_epoch < current_epoch + renew_epochs() {
+        return Err(not_found());
+    }
+
+    Ok(ZkLoginSessionInfo {
+        telegram_id: telegram_id.to_string(),
+        public_key: record.public_key,
+        max_epoch: record.max_epoch,
+        randomness: record.randomness,
+        current_epoch,
+    })
+}
+
+/// Proof inputs cached for `address` while the session still uses the ephemeral `public_key`.
+pub fn cached_proof(
+    db: &Db,
+    telegram_id: &str,
+    public_key: &str,
+    address: SuiAddress,
+) -> Option<Value> {
+    let record = get_record(db, telegram_id).ok().flatten()?;
+
+    if record.public_key != public_key || record.address != Some(address.to_string()) {
+        return None;
+    }
+
+    record.proof
+}
+
+/// Cache the proof inputs of the session; failures are only logged since the proof can be
+/// requested again.
+pub fn store_proof(
+    db: &Db,
+    telegram_id: &str,
+    public_key: &str,
+    address: SuiAddress,
+    proof: &impl Serialize,
+) {
+    let result = (|| {
+        let Some(mut record) = get_record(db, telegram_id)? else {
+            return Ok(());
+        };
+
+        if record.public_key != public_key {
+            return Ok(());
+        }
+
+        record.address = Some(address.to_string());
+        record.proof = Some(serde_json::to_value(proof).map_err(db_error)?);
+
+        save_record(db, telegram_id, &record)
+    })();
+
+    if let Err(e) = result {
+        println!(
+            "Failed to cache the zk proof of {}: {}",
+            telegram_id, e.message
+        );
+    }
+}
+
+/// Sign `tx` as the zkLogin address `sender` of the session, reusing the cached proof when the
+/// ephemeral key is unchanged.
+pub async fn sign_with_zk_login(
+    keeper_state: &KeeperState,
+    squad_connect_client: &mut SquadConnect,
+    session: &ZkLoginSession,
+    sender: SuiAddress,
+    tx: TransactionData,
+) -> Result<Transaction, ErrorKeeper> {
+    let cached_inputs = cached_proof(
+        keeper_state.db(),
+        &session.telegram_id,
+        &session.public_key,
+        sender,
+    )
+    .and_then(|proof| serde_json::from_value(proof).ok());
+
+    // The proof only depends on the ephemeral key and the account, so it is reused until max_epoch
+    let zk_login_inputs = match cached_inputs {
+        Some(zk_login_inputs) => zk_login_inputs,
+        None => {
+            let zk_login_inputs = squad_connect_client
+                .recover_seed_address()
+                .await
+                .map_err(db_error)?;
+
+            store_proof(
+                keeper_state.db(),
+                &session.telegram_id,
+                &session.public_key,
+                sender,
+                &zk_login_inputs,
+            );
+
+            zk_login_inputs
+        }
+    };
+
+    let signer_pk = PublicKey::from_str(&session.public_key).map_err(db_error)?;
+
+    squad_connect_client
+        .sign_transaction(
+            tx,
+            SuiAddress::from(&signer_pk),
+            zk_login_inputs,
+            session.max_epoch,
+            keeper_state.path().clone(),
+        )
+        .await
+        .map_err(db_error)
+}
+
+/// Drop expired sessions and ask users whose session is about to expire to log in again.
+async fn check_sessions(keeper_state: &KeeperState) -> Result<(), ErrorKeeper> {
+    let node = keeper_state.squad_connect_client().get_node();
+    let current_epoch = current_epoch(&node).await?;
+    let tree = keeper_state
+        .db()
+        .open_tree(ZK_LOGIN_TREE)
+        .map_err(db_error)?;
+
+    for entry in tree.iter() {
+        let (key, bytes) = entry.map_err(db_error)?;
+        let telegram_id = String::from_utf8(key.to_vec()).map_err(db_error)?;
+
+        let Ok(mut record) = serde_json::from_slice::<ZkLoginRecord>(&bytes) else {
+            tree.remove(&key).map_err(db_error)?;
+            continue;
+        };
+
+        if record.max_epoch < current_epoch {
+            tree.remove(&key).map_err(db_error)?;
+            continue;
+        }
+
+        // Only users who completed a login have something to renew
+        if record.expiry_notified
+            || record.address.is_none()
+            || record.max_epoch >= current_epoch + renew_epochs()
+        {
+            continue;
+        }
+
+        notify(
+            keeper_state.db(),
+            Notification {
+                id: 0,
+                telegram_id: telegram_id.clone(),
+                kind: NotificationKind::SessionExpiring,
+                amount: 0,
+                coin_type: String::new(),
+                sender: None,
+                recipient: record.address.clone(),
+                digest: String::new(),
+                timestamp: 0,
+            },
+        );
+
+        record.expiry_notified = true;
+
+        save_record(keeper_state.db(), &telegram_id, &record)?;
+    }
+
+    Ok(())
+}
+
+/// Track the `max_epoch` of every zkLogin session.
+///
+/// Runs every `ZKLOGIN_WATCH_SECS` seconds (600 by default); `0` disables the watcher.
+pub async fn watch_sessions(keeper_state: Arc<KeeperState>) {
+    let watch_secs = env::var("ZKLOGIN_WATCH_SECS")
+        .ok()
+        .and_then(|secs| secs.parse().ok())
+        .unwrap_or(600);
+
+    if watch_secs == 0 {
+        println!("zkLogin session watcher disabled");
+        return;
+    }
+
+    let mut interval = tokio::time::interval(Duration::from_secs(watch_secs));
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = check_sessions(&keeper_state).await {
+            println!("Failed to check zkLogin sessions: {}", e.message);
+        }
+    }
+}
+
+/// zkLogin address of the OAuth id_token sent as a Bearer token; the salt is left out.
+pub async fn lookup_address(
+    zk_login: &dyn ZkLoginProvider,
+    headers: &HeaderMap,
+) -> Result<ZkLoginAddressResponse, ErrorKeeper> {
+    let id_token = headers
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .ok_or_else(|| ErrorKeeper {
+            message: "Authorization header not found".to_string(),
+            status: 401,
+        })?;
+
+    let account = zk_login.lookup(id_token).await.map_err(|e| match e {
+        CoreError::Unauthorized => ErrorKeeper {
+            message: "The login token was rejected, please sign in again".to_string(),
+            status: 401,
+        },
+        e => ErrorKeeper {
+            message: e.to_string(),
+            status: 502,
+        },
+    })?;
+
+    Ok(ZkLoginAddressResponse {
+        address: account.address,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use axum::http::HeaderValue;
+    use serde_json::json;
+    use sui_squad_core::zklogin::{LocalZkLogin, ZkLoginAccount};
+
+    use super::*;
+
+    fn provider() -> LocalZkLogin {
+        LocalZkLogin::new(HashMap::from([(
+            "alice-token".to_string(),
+            ZkLoginAccount {
+                address: "0xa11ce".to_string(),
+                salt: "42".to_string(),
+            },
+        )]))
+    }
+
+    fn bearer(token: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "Authorization",
+            HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+        );
+        headers
+    }
+
+    #[tokio::test]
+    async fn returns_only_the_address() {
+        let response = lookup_address(&provider(), &bearer("alice-token"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            json!({ "address": "0xa11ce" })
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_tokens_are_unauthorized() {
+        let error = lookup_address(&provider(), &bearer("mallory-token"))
+            .await
+            .err()
+            .unwrap();
+
+        assert_eq!(error.status, 401);
+    }
+
+    #[tokio::test]
+    async fn missing_tokens_are_unauthorized() {
+        let error = lookup_address(&provider(), &HeaderMap::new())
+            .await
+            .err()
+            .unwrap();
+
+        assert_eq!(error.status, 401);
+    }
+}